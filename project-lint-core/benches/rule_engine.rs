@@ -19,6 +19,9 @@ fn make_config(rule_count: usize) -> Config {
             severity: RuleSeverity::Warning,
             check_content: false,
             content_pattern: None,
+            content_regex: vec![],
+            content_flags: None,
+            content_match: Default::default(),
             exception_pattern: None,
             condition: None,
             required: false,
@@ -27,6 +30,8 @@ fn make_config(rule_count: usize) -> Config {
             enabled_if_path_exists: None,
             exclude_patterns: vec![],
             triggers: vec!["pre_tool_use".to_string()],
+            when: None,
+            ask: false,
             mode: ExecutionMode::LocalSync,
         });
     }
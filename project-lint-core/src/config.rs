@@ -1,12 +1,80 @@
 use crate::utils::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::{debug, info, warn};
 use walkdir::WalkDir;
 
+/// File name of the lightweight per-directory config accepted alongside a
+/// nested `.config/project-lint/config.toml`.
+pub const NESTED_CONFIG_FILE: &str = ".project-lint.toml";
+
+/// Version of configs predating the `version` key.
+pub const LEGACY_CONFIG_VERSION: u32 = 1;
+
+/// Current config schema version. From version 2 on, the legacy `[git]`,
+/// `[files]` and `[directories]` sections are ignored in favour of modular
+/// rules.
+pub const CURRENT_CONFIG_VERSION: u32 = 2;
+
+/// Names of the modular rules that replace the legacy sections.
+pub const LEGACY_GIT_RULE: &str = "git-branch-rules";
+pub const LEGACY_FILES_RULE: &str = "file-organization";
+pub const LEGACY_DIRECTORIES_RULE: &str = "script-location";
+
+/// Extensions the legacy `[directories]` script check treats as scripts.
+const LEGACY_SCRIPT_EXTENSIONS: &[&str] = &[".sh", ".py", ".js", ".ts", ".rb", ".pl", ".php"];
+
+/// Prefix of environment variables that override config keys. Sections are
+/// separated by a double underscore: `PROJECT_LINT__RULES__MODE=allowlist`.
+pub const ENV_OVERRIDE_PREFIX: &str = "PROJECT_LINT__";
+
+/// The `PROJECT_LINT__*` variables of this process, sorted by name.
+pub fn env_overrides() -> Vec<(String, String)> {
+    let mut env: Vec<(String, String)> = std::env::vars()
+        .filter(|(name, _)| name.starts_with(ENV_OVERRIDE_PREFIX))
+        .collect();
+    env.sort();
+    env
+}
+
+/// Names of the built-in checks. Any other bare id in an `[[overrides]]`
+/// block is taken to be a rule id.
+pub const BUILTIN_CHECKS: &[&str] = &[
+    "file_naming",
+    "ast_analysis",
+    "security_analysis",
+    "typescript_analysis",
+    "dependency_versions",
+    "profile_structure",
+    "web",
+    "kubernetes",
+    "terraform",
+    "rust_conventions",
+    "dev_environment",
+    "ci_cd_parity",
+    "dockerfile_lint",
+    "compose_lint",
+    "typescript_monorepo",
+    "vault_security",
+    "git_branch",
+    "file_location",
+    "directory_structure",
+    "custom_rules",
+    "secret_redaction",
+    "stop_gate",
+];
+
+/// Directories never searched for nested configs.
+const NESTED_SCAN_SKIP_DIRS: &[&str] = &[".git", "node_modules", "target", "dist", "build"];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
+    /// Config schema version. Files without one are version 1, whose legacy
+    /// `[git]`, `[files]` and `[directories]` sections are still honoured;
+    /// `project-lint config migrate` moves them into modular rules.
+    #[serde(default = "legacy_config_version")]
+    pub version: u32,
     #[serde(default)]
     pub git: GitConfig,
     #[serde(default)]
@@ -19,14 +87,45 @@ pub struct Config {
     /// Scanners read these to augment their built-in rule sets without code changes.
     #[serde(default)]
     pub scanner_config: ScannerConfig,
+    /// The `[scanner_config]` keys as written in the config file and nested
+    /// configs, without the defaults of their sections. Slice fragments are
+    /// merged under these.
+    #[serde(skip)]
+    pub explicit_scanner_config: toml::value::Table,
+    /// ESLint-style `[[overrides]]` blocks layered onto matching files, in
+    /// declaration order (later blocks win).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<ConfigOverride>,
     #[serde(skip)]
     pub modular_rules: Vec<ModularRule>,
+    /// Names of the `modular_rules` built from the legacy sections of a
+    /// version 1 config. They are rebuilt whenever the config is overlaid.
+    #[serde(skip)]
+    pub generated_legacy_rules: Vec<String>,
     #[serde(skip)]
     pub active_profiles: Vec<Profile>,
+    /// Every slice under `rules/slices/`, keyed by file stem.
+    #[serde(skip)]
+    pub slices: Vec<Slice>,
+    /// Slices pulled in by the active profiles, in precedence order.
+    #[serde(skip)]
+    pub active_slices: Vec<Slice>,
+    /// What the active profiles and slices decided for each check, resolved
+    /// when they are set (see [`Config::with_active_profiles`]).
+    #[serde(skip)]
+    pub check_toggles: HashMap<String, bool>,
+    /// Problems found while resolving the active profiles (conflicts,
+    /// missing requirements, disagreeing check toggles).
+    #[serde(skip)]
+    pub diagnostics: Vec<String>,
     #[serde(skip)]
     pub active_plugins: Vec<Plugin>,
     #[serde(skip)]
     pub core_config: CoreConfig,
+    /// Environment and `--set` assignments, re-applied on top of nested
+    /// configs so the command line always has the last word.
+    #[serde(skip)]
+    pub runtime_overrides: Vec<ConfigAssignment>,
 }
 
 /// Container for optional per-scanner configuration sections.
@@ -49,6 +148,12 @@ pub struct ScannerConfig {
     pub typescript_monorepo: Option<TypescriptMonorepoConfig>,
     #[serde(default)]
     pub package_manager_enforcement: Option<PackageManagerEnforcementConfig>,
+    #[serde(default)]
+    pub web: Option<WebScannerConfig>,
+    #[serde(default)]
+    pub kubernetes: Option<KubernetesConfig>,
+    #[serde(default)]
+    pub terraform: Option<TerraformConfig>,
 }
 
 /// `[scanner_config.rust_file_naming]` — extra required/forbidden files and
@@ -93,7 +198,8 @@ pub struct VaultSecurityConfig {
     pub allowed_backends: Vec<String>,
 }
 
-/// `[scanner_config.dockerfile_security]` — Dockerfile lint toggles.
+/// `[scanner_config.dockerfile_security]` — Dockerfile and compose lint
+/// toggles.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DockerfileSecurityConfig {
     #[serde(default = "default_true")]
@@ -102,6 +208,12 @@ pub struct DockerfileSecurityConfig {
     pub require_non_root_user: bool,
     #[serde(default = "default_true")]
     pub forbid_copy_dot: bool,
+    /// Compose services must set `security_opt: no-new-privileges:true`.
+    #[serde(default = "default_true")]
+    pub require_no_new_privileges: bool,
+    /// Compose services must declare a healthcheck.
+    #[serde(default = "default_true")]
+    pub require_healthcheck: bool,
 }
 
 /// `[scanner_config.typescript_monorepo]` — TS monorepo catalog mode, path
@@ -128,6 +240,57 @@ pub struct PackageManagerEnforcementConfig {
     pub required_lockfile: Option<String>,
 }
 
+/// `[scanner_config.kubernetes]` — which workload requirements a profile's
+/// `check_kubernetes_manifests` toggle enforces.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KubernetesConfig {
+    #[serde(default = "default_true")]
+    pub require_probes: bool,
+    #[serde(default = "default_true")]
+    pub require_resources: bool,
+}
+
+impl Default for KubernetesConfig {
+    fn default() -> Self {
+        Self {
+            require_probes: true,
+            require_resources: true,
+        }
+    }
+}
+
+/// `[scanner_config.terraform]` — options for a profile's `validate_terraform`
+/// toggle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerraformConfig {
+    #[serde(default = "default_true")]
+    pub check_fmt: bool,
+}
+
+impl Default for TerraformConfig {
+    fn default() -> Self {
+        Self { check_fmt: true }
+    }
+}
+
+/// `[scanner_config.web]` — image limits for a profile's `optimize_images`
+/// toggle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebScannerConfig {
+    #[serde(default = "default_max_image_bytes")]
+    pub max_image_bytes: u64,
+    #[serde(default = "default_max_image_dimension")]
+    pub max_image_dimension: u32,
+}
+
+fn default_max_image_bytes() -> u64 {
+    500_000
+}
+
+fn default_max_image_dimension() -> u32 {
+    2560
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoreConfig {
     pub global: GlobalConfig,
@@ -194,6 +357,20 @@ pub struct Profile {
     pub structure: Option<ProfileStructure>,
     #[serde(default)]
     pub extensions: Option<HashMap<String, String>>,
+    #[serde(default)]
+    pub commands: Option<ProfileCommands>,
+}
+
+/// `[commands]` section of a profile: command rules and an optional
+/// allowlist of programs agents may run while the profile is active.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProfileCommands {
+    /// Program globs (e.g. `"cargo"`, `"git"`). When any active profile sets
+    /// one, programs outside every allowlist are denied.
+    #[serde(default)]
+    pub allowlist: Option<Vec<String>>,
+    #[serde(default)]
+    pub rules: Vec<CommandRule>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -204,6 +381,20 @@ pub struct ProfileMetadata {
     pub scope: String,
     pub updated: String,
     pub description: String,
+    /// Higher-priority profiles win check toggles and conflicts; ties go to
+    /// the alphabetically first name.
+    #[serde(default, skip_serializing_if = "is_default_priority")]
+    pub priority: i32,
+    /// Profiles that must not be active together with this one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conflicts_with: Vec<String>,
+    /// Profiles that must be active for this one to stay active.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requires: Vec<String>,
+}
+
+fn is_default_priority(priority: &i32) -> bool {
+    *priority == 0
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -222,6 +413,29 @@ pub struct ProfileActivation {
     pub content: Vec<ContentTrigger>,
     #[serde(default)]
     pub events: Vec<String>,
+    /// Boolean expression over the same criteria, e.g.
+    /// `when = { all = [{ indicator = "tsconfig.json" }, { not = { glob = "next.config.*" } }] }`.
+    /// The flat lists above act as one implicit `any`; when both are given,
+    /// both must hold.
+    #[serde(default)]
+    pub when: Option<ActivationExpr>,
+}
+
+/// One node of a profile activation expression. Leaves test the same things
+/// as the flat `[activation]` lists; `branch` takes a glob over the current
+/// git branch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivationExpr {
+    All(Vec<ActivationExpr>),
+    Any(Vec<ActivationExpr>),
+    Not(Box<ActivationExpr>),
+    Indicator(String),
+    Path(String),
+    Glob(String),
+    Branch(String),
+    Event(String),
+    Content(ContentTrigger),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -268,6 +482,122 @@ pub struct ProfileSlices {
     pub include: Vec<String>,
 }
 
+/// A reusable rule bundle under `rules/slices/<id>.toml`, pulled in by
+/// profiles through `[checks.slices] include = ["<id>.toml"]`. A slice may
+/// include other slices the same way.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Slice {
+    /// File stem the slice is referenced by.
+    #[serde(skip)]
+    pub id: String,
+    #[serde(default)]
+    pub metadata: Option<ProfileMetadata>,
+    #[serde(default)]
+    pub rules: Vec<CustomRule>,
+    #[serde(default)]
+    pub checks: Option<ProfileChecks>,
+    /// `[scanner_config.*]` fragment merged over the config while active.
+    #[serde(default)]
+    pub scanner_config: Option<toml::Value>,
+}
+
+impl Slice {
+    /// Name of the modular rule carrying this slice's `[[rules]]`.
+    pub fn rule_name(&self) -> &str {
+        self.metadata
+            .as_ref()
+            .map(|m| m.name.as_str())
+            .unwrap_or(&self.id)
+    }
+
+    fn includes(&self) -> Vec<String> {
+        self.checks
+            .as_ref()
+            .and_then(|c| c.slices.as_ref())
+            .map(|s| s.include.iter().map(|i| slice_id(i)).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Order profiles by descending priority, then name.
+fn profile_precedence(a: &Profile, b: &Profile) -> std::cmp::Ordering {
+    b.metadata
+        .priority
+        .cmp(&a.metadata.priority)
+        .then_with(|| a.metadata.name.cmp(&b.metadata.name))
+}
+
+/// Apply `requires` and `conflicts_with` to a set of matching profiles.
+/// Returns the survivors by precedence plus one diagnostic per profile
+/// dropped.
+fn resolve_profile_set(mut profiles: Vec<Profile>) -> (Vec<Profile>, Vec<String>) {
+    profiles.sort_by(profile_precedence);
+    let mut diagnostics = Vec::new();
+
+    drop_unmet_requirements(&mut profiles, &mut diagnostics);
+
+    // Walk in precedence order; a profile conflicting with one already kept
+    // loses
+    let mut kept: Vec<Profile> = Vec::new();
+    for profile in profiles {
+        let winner = kept.iter().find(|k| {
+            k.metadata.conflicts_with.contains(&profile.metadata.name)
+                || profile.metadata.conflicts_with.contains(&k.metadata.name)
+        });
+        if let Some(winner) = winner {
+            diagnostics.push(format!(
+                "Profile '{}' (priority {}) conflicts with '{}' (priority {}) and is deactivated",
+                profile.metadata.name,
+                profile.metadata.priority,
+                winner.metadata.name,
+                winner.metadata.priority
+            ));
+            continue;
+        }
+        kept.push(profile);
+    }
+
+    // Losing a conflict can leave another profile's requirement unmet
+    drop_unmet_requirements(&mut kept, &mut diagnostics);
+    (kept, diagnostics)
+}
+
+fn drop_unmet_requirements(profiles: &mut Vec<Profile>, diagnostics: &mut Vec<String>) {
+    loop {
+        let names: Vec<String> = profiles.iter().map(|p| p.metadata.name.clone()).collect();
+        let before = profiles.len();
+        profiles.retain(|p| {
+            let missing: Vec<&str> = p
+                .metadata
+                .requires
+                .iter()
+                .filter(|r| !names.contains(r))
+                .map(|r| r.as_str())
+                .collect();
+            if missing.is_empty() {
+                return true;
+            }
+            diagnostics.push(format!(
+                "Profile '{}' requires inactive profile(s) {} and is deactivated",
+                p.metadata.name,
+                missing.join(", ")
+            ));
+            false
+        });
+        if profiles.len() == before {
+            break;
+        }
+    }
+}
+
+/// Slice references may carry the `.toml` extension or not.
+fn slice_id(reference: &str) -> String {
+    reference
+        .strip_suffix(".toml")
+        .unwrap_or(reference)
+        .to_string()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WebSpecificConfig {
     pub check_html_semantics: bool,
@@ -489,6 +819,178 @@ pub struct RulesConfig {
     pub enabled_checks: Vec<String>,
     #[serde(default)]
     pub disabled_checks: Vec<String>,
+    /// Per-rule severity overrides keyed by rule or check name (e.g.
+    /// `"pin-image-digests" = "error"`). Applied to custom rules and scanner
+    /// findings alike.
+    #[serde(default)]
+    pub severity_overrides: HashMap<String, RuleSeverity>,
+    /// Policy for shell commands run by agents (`pre_run_command`, Bash tool)
+    #[serde(default)]
+    pub command_rules: Vec<CommandRule>,
+    /// Rewrites applied to agent shell commands before they run
+    #[serde(default)]
+    pub command_rewrites: Vec<CommandRewrite>,
+    /// What to do with credentials found in prompts and tool inputs
+    #[serde(default)]
+    pub secret_redaction: SecretRedactionConfig,
+    /// How per-session state is kept
+    #[serde(default)]
+    pub session: SessionConfig,
+}
+
+/// One `[[overrides]]` block. Applies to files (relative to the project root)
+/// matching any `files` glob and no `excludes` glob.
+///
+/// `enabled_checks`/`disabled_checks` accept check names (`security_analysis`),
+/// bare rule ids (`suspicious_password_var`) or `category/rule`
+/// (`security/suspicious_password_var`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConfigOverride {
+    #[serde(default)]
+    pub files: Vec<String>,
+    #[serde(default)]
+    pub excludes: Vec<String>,
+    #[serde(default)]
+    pub enabled_checks: Vec<String>,
+    #[serde(default)]
+    pub disabled_checks: Vec<String>,
+    #[serde(default)]
+    pub severity_overrides: HashMap<String, RuleSeverity>,
+    /// Fragment merged over `[scanner_config]` for matching files.
+    #[serde(default)]
+    pub scanner_config: Option<toml::Value>,
+}
+
+impl ConfigOverride {
+    pub fn matches(&self, path: &Path) -> bool {
+        let path_str = path.to_string_lossy();
+        let hit = |globs: &[String]| {
+            globs.iter().any(|g| {
+                glob::Pattern::new(g)
+                    .map(|p| p.matches(&path_str))
+                    .unwrap_or(false)
+                    || crate::utils::matches_pattern(&path_str, g)
+            })
+        };
+        hit(&self.files) && !hit(&self.excludes)
+    }
+
+    fn mentions(list: &[String], ids: &[&str]) -> bool {
+        list.iter().any(|c| ids.contains(&c.as_str()))
+    }
+}
+
+/// How a [`ConfigAssignment`] combines with the existing value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AssignOp {
+    /// `key=value` replaces the value.
+    Set,
+    /// `key+=value` appends to an array (creating it if missing).
+    Append,
+    /// `key-=value` removes matching entries from an array.
+    Remove,
+}
+
+/// One override of a dotted config key, from `--set` or a
+/// `PROJECT_LINT__SECTION__KEY` environment variable. Values are parsed as TOML
+/// (`true`, `3`, `["a", "b"]`) and fall back to a plain string.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigAssignment {
+    pub key: Vec<String>,
+    pub op: AssignOp,
+    pub value: toml::Value,
+}
+
+impl ConfigAssignment {
+    /// Parse `rules.mode=allowlist`, `rules.disabled_checks+=ast_analysis` or
+    /// `rules.enabled_checks-=git_branch`.
+    pub fn parse(spec: &str) -> Result<Self> {
+        // Only the character before the first `=` selects the operator, so
+        // values may contain `+=` or `-=` themselves.
+        let Some((key, raw)) = spec.split_once('=') else {
+            return Err(anyhow::anyhow!(
+                "Invalid override '{}': expected key=value, key+=value or key-=value",
+                spec
+            ));
+        };
+        let (key, op) = if let Some(key) = key.strip_suffix('+') {
+            (key, AssignOp::Append)
+        } else if let Some(key) = key.strip_suffix('-') {
+            (key, AssignOp::Remove)
+        } else {
+            (key, AssignOp::Set)
+        };
+
+        let key: Vec<String> = key.trim().split('.').map(str::to_string).collect();
+        if key.iter().any(|k| k.is_empty()) {
+            return Err(anyhow::anyhow!("Invalid override key in '{}'", spec));
+        }
+
+        Ok(Self {
+            key,
+            op,
+            value: parse_override_value(raw.trim()),
+        })
+    }
+
+    /// Build an assignment from an environment variable named
+    /// `PROJECT_LINT__RULES__MODE`. Returns `None` for unrelated variables.
+    pub fn from_env_var(name: &str, value: &str) -> Option<Self> {
+        let key = name.strip_prefix(ENV_OVERRIDE_PREFIX)?;
+        let key: Vec<String> = key.split("__").map(str::to_lowercase).collect();
+        if key.iter().any(|k| k.is_empty()) {
+            return None;
+        }
+        Some(Self {
+            key,
+            op: AssignOp::Set,
+            value: parse_override_value(value),
+        })
+    }
+
+    fn apply(&self, root: &mut toml::Value) -> Result<()> {
+        let (last, parents) = self.key.split_last().expect("key is never empty");
+        let mut table = root
+            .as_table_mut()
+            .ok_or_else(|| anyhow::anyhow!("Config root is not a table"))?;
+        for segment in parents {
+            table = table
+                .entry(segment.clone())
+                .or_insert_with(|| toml::Value::Table(toml::value::Table::new()))
+                .as_table_mut()
+                .ok_or_else(|| {
+                    anyhow::anyhow!("Cannot override '{}': not a table", self.key.join("."))
+                })?;
+        }
+
+        let values = match &self.value {
+            toml::Value::Array(items) => items.clone(),
+            other => vec![other.clone()],
+        };
+        match self.op {
+            AssignOp::Set => {
+                table.insert(last.clone(), self.value.clone());
+            }
+            AssignOp::Append | AssignOp::Remove => {
+                let entry = table
+                    .entry(last.clone())
+                    .or_insert_with(|| toml::Value::Array(Vec::new()));
+                let array = entry.as_array_mut().ok_or_else(|| {
+                    anyhow::anyhow!("Cannot append to '{}': not an array", self.key.join("."))
+                })?;
+                if self.op == AssignOp::Append {
+                    for value in values {
+                        if !array.contains(&value) {
+                            array.push(value);
+                        }
+                    }
+                } else {
+                    array.retain(|v| !values.contains(v));
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -514,6 +1016,17 @@ pub struct CustomRule {
     pub check_content: bool,
     #[serde(default)]
     pub content_pattern: Option<String>,
+    /// Regular expressions matched against file content, alongside the
+    /// literal `content_pattern`. Accepts a single string or a list.
+    #[serde(default, deserialize_with = "string_or_list")]
+    pub content_regex: Vec<String>,
+    /// Inline regex flags applied to every `content_regex` (`i`, `m`, `s`,
+    /// `x`, `U`), e.g. `"im"`.
+    #[serde(default)]
+    pub content_flags: Option<String>,
+    /// Whether any or all of the content patterns must match.
+    #[serde(default)]
+    pub content_match: ContentMatchMode,
     #[serde(default)]
     pub exception_pattern: Option<String>,
     #[serde(default)]
@@ -543,6 +1056,15 @@ pub struct CustomRule {
     pub exclude_patterns: Vec<String>,
     #[serde(default)]
     pub triggers: Vec<String>,
+    /// Condition over the triggering event's fields, e.g.
+    /// `tool_name == "Bash" && command =~ "^git push"`. The rule only
+    /// applies to events it holds for; `lint` runs skip the rule.
+    #[serde(default)]
+    pub when: Option<String>,
+    /// Ask for confirmation when the rule fires instead of warning or
+    /// denying by severity (hooks only).
+    #[serde(default)]
+    pub ask: bool,
     /// Execution mode for the rule: how matching events are dispatched.
     ///
     /// Defaults to `LocalSync` when omitted (backward compatible).
@@ -550,13 +1072,157 @@ pub struct CustomRule {
     pub mode: ExecutionMode,
 }
 
+/// How multiple content patterns of a `CustomRule` combine.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentMatchMode {
+    #[default]
+    Any,
+    All,
+}
+
+/// A shell command policy rule. Every command of a command line (pipeline
+/// stages, `&&`/`;` lists, `sh -c` bodies, with `sudo` and env prefixes
+/// stripped) is matched on its own.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandRule {
+    pub name: String,
+    /// Program name globs (`"rm"`, `"python*"`). One string or a list.
+    #[serde(deserialize_with = "string_or_list")]
+    pub program: Vec<String>,
+    /// Argument globs that must all be present. `-r` also matches a short
+    /// flag cluster such as `-rf`.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Only match when the command reads a pipe from one of these programs
+    /// (e.g. `program = "sh"`, `piped_from = "curl"`).
+    #[serde(default, deserialize_with = "string_or_list")]
+    pub piped_from: Vec<String>,
+    pub action: CommandAction,
+    #[serde(default)]
+    pub message: String,
+}
+
+/// Rewrites an agent's shell command before it runs, e.g. `npm install X`
+/// to `pnpm add X`. `pattern` is a regex matched at the start of each command
+/// of the command line; the matched text is replaced by `replacement`, where
+/// `{1}` or `{name}` insert captures.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandRewrite {
+    pub name: String,
+    pub pattern: String,
+    pub replacement: String,
+    /// Only rewrite when one of these paths (globs, relative to the project
+    /// root) exists...
+    #[serde(default, deserialize_with = "string_or_list")]
+    pub when_exists: Vec<String>,
+    /// ...or one of these files matches its regex. With neither condition
+    /// the rewrite always applies.
+    #[serde(default)]
+    pub when_file_contains: HashMap<String, String>,
+    #[serde(default)]
+    pub message: String,
+    #[serde(default = "default_rewrite_severity")]
+    pub severity: RuleSeverity,
+}
+
+fn default_rewrite_severity() -> RuleSeverity {
+    RuleSeverity::Warning
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandAction {
+    Allow,
+    Ask,
+    Deny,
+}
+
+/// `[rules.secret_redaction]`: credentials in user prompts and tool inputs
+/// are masked before they leave the machine (`redact`), or the event asks
+/// for confirmation (`ask`) or is blocked (`deny`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SecretRedactionConfig {
+    #[serde(default)]
+    pub action: RedactionAction,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RedactionAction {
+    #[default]
+    Redact,
+    Ask,
+    Deny,
+}
+
+/// `[rules.session]`: what session state counts as running the tests.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SessionConfig {
+    /// Globs matched against each command of a command line, e.g.
+    /// `"cargo test*"`.
+    #[serde(default = "default_test_commands")]
+    pub test_commands: Vec<String>,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            test_commands: default_test_commands(),
+        }
+    }
+}
+
+fn default_test_commands() -> Vec<String> {
+    [
+        "cargo test*",
+        "cargo nextest*",
+        "npm test*",
+        "npm run test*",
+        "pnpm test*",
+        "pnpm run test*",
+        "yarn test*",
+        "bun test*",
+        "npx jest*",
+        "npx vitest*",
+        "pytest*",
+        "python* -m pytest*",
+        "go test*",
+        "make test*",
+        "mvn test*",
+        "gradle test*",
+        "gradlew test*",
+    ]
+    .map(String::from)
+    .to_vec()
+}
+
+fn string_or_list<'de, D>(deserializer: D) -> std::result::Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringOrList {
+        One(String),
+        Many(Vec<String>),
+    }
+    Ok(match StringOrList::deserialize(deserializer)? {
+        StringOrList::One(one) => vec![one],
+        StringOrList::Many(many) => many,
+    })
+}
+
 /// How a matched rule is dispatched by the event router.
 ///
 /// - `LocalSync`: evaluate synchronously in-process via the `RuleEngine`.
-/// - `LocalAsync`: spawn a background task; return `Allow` immediately.
-/// - `RemoteSync`: forward to a remote daemon and wait (stub — story 13-005).
-/// - `RemoteAsync`: forward to a remote daemon without waiting (stub — story 12-001).
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+/// - `LocalAsync`: evaluate in a detached process and report the findings on
+///   the session's next event (synchronous when the event has no session).
+/// - `RemoteSync`: evaluate in the `project-lint daemon` and wait, falling
+///   back to in-process evaluation when the daemon is unreachable.
+/// - `RemoteAsync`: like `LocalAsync`, with the detached evaluation handed
+///   to the daemon.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecutionMode {
     LocalSync,
@@ -565,6 +1231,16 @@ pub enum ExecutionMode {
     RemoteAsync,
 }
 
+impl ExecutionMode {
+    pub fn is_async(&self) -> bool {
+        matches!(self, ExecutionMode::LocalAsync | ExecutionMode::RemoteAsync)
+    }
+
+    pub fn is_remote(&self) -> bool {
+        matches!(self, ExecutionMode::RemoteSync | ExecutionMode::RemoteAsync)
+    }
+}
+
 impl Default for ExecutionMode {
     fn default() -> Self {
         ExecutionMode::LocalSync
@@ -593,6 +1269,8 @@ pub struct ModularRule {
     pub messages: Option<HashMap<String, String>>,
     #[serde(default)]
     pub rules: Option<Vec<CustomRule>>,
+    #[serde(default)]
+    pub command_rewrites: Option<Vec<CommandRewrite>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -616,7 +1294,7 @@ pub struct RuleConditions {
     pub check_scripts_in_src: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum RuleSeverity {
     Error,
@@ -624,26 +1302,86 @@ pub enum RuleSeverity {
     Info,
 }
 
+impl RuleSeverity {
+    /// Lowercase name matching the serialized form and the `severity` strings
+    /// carried by scanner issues.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RuleSeverity::Error => "error",
+            RuleSeverity::Warning => "warning",
+            RuleSeverity::Info => "info",
+        }
+    }
+}
+
 fn default_true() -> bool {
     true
 }
 
+/// Parse an override value as TOML, falling back to a bare string so
+/// `allowlist` works without quoting.
+fn parse_override_value(raw: &str) -> toml::Value {
+    toml::from_str::<toml::value::Table>(&format!("value = {}", raw))
+        .ok()
+        .and_then(|mut t| t.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(raw.to_string()))
+}
+
+/// The `[scanner_config]` table of a raw config, or an empty table.
+fn scanner_config_table(raw: &toml::Value) -> toml::value::Table {
+    raw.get("scanner_config")
+        .and_then(toml::Value::as_table)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Recursively merge `overlay` into `base`: tables merge key by key, every
+/// other value in `overlay` replaces the one in `base`.
+fn merge_toml(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base_table), toml::Value::Table(overlay_table)) => {
+            for (key, value) in overlay_table {
+                match base_table.get_mut(&key) {
+                    Some(existing) => merge_toml(existing, value),
+                    None => {
+                        base_table.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
 fn default_scripts_dir() -> String {
     "bin".to_string()
 }
 
+fn legacy_config_version() -> u32 {
+    LEGACY_CONFIG_VERSION
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
+            version: LEGACY_CONFIG_VERSION,
             git: GitConfig::default(),
             files: FilesConfig::default(),
             directories: DirectoriesConfig::default(),
             rules: RulesConfig::default(),
             scanner_config: ScannerConfig::default(),
+            explicit_scanner_config: toml::value::Table::new(),
+            overrides: Vec::new(),
             modular_rules: Vec::new(),
+            generated_legacy_rules: Vec::new(),
             active_profiles: Vec::new(),
+            slices: Vec::new(),
+            active_slices: Vec::new(),
+            check_toggles: HashMap::new(),
+            diagnostics: Vec::new(),
             active_plugins: Vec::new(),
             core_config: CoreConfig::default(),
+            runtime_overrides: Vec::new(),
         }
     }
 }
@@ -762,39 +1500,25 @@ impl Default for RulesConfig {
                 "custom_rules".to_string(),
             ],
             disabled_checks: vec![],
+            severity_overrides: HashMap::new(),
+            command_rules: vec![],
+            command_rewrites: vec![],
+            secret_redaction: SecretRedactionConfig::default(),
+            session: SessionConfig::default(),
         }
     }
 }
 
 impl Config {
     pub fn load() -> Result<Self> {
-        let config_dir = crate::utils::get_config_dir()?;
-        let config_file = config_dir.join("config.toml");
-
-        let mut config = if config_file.exists() {
-            debug!("Loading config from {:?}", config_file);
-            let content = std::fs::read_to_string(&config_file)?;
-            let config: Config = toml::from_str(&content)?;
-            info!("Configuration loaded successfully");
-            config
-        } else {
-            debug!("No config file found, using defaults");
-            Config::default()
-        };
-
-        // Load core configuration
-        config.core_config = Self::load_core_config(&config_dir)?;
-
-        // Load modular rules from .config/project-lint/rules/active/
-        config.modular_rules = Self::load_modular_rules(&config_dir)?;
-
-        // Load profiles
-        config.active_profiles = Self::load_profiles(&config_dir)?;
-
-        // Load plugins
-        config.active_plugins = Self::load_plugins(&config_dir)?;
+        Self::load_in(&std::env::current_dir()?)
+    }
 
-        Ok(config)
+    /// [`Config::load`] as if run from `dir`, for callers (the daemon) that
+    /// serve projects other than their own working directory.
+    pub fn load_in(dir: &std::path::Path) -> Result<Self> {
+        let config_dir = crate::utils::config_dir_for(dir)?;
+        Self::load_from_file(&config_dir.join("config.toml"))
     }
 
     /// Load configuration from an explicit config file path.
@@ -810,7 +1534,9 @@ impl Config {
         let mut config = if config_path.exists() {
             debug!("Loading config from {:?}", config_path);
             let content = std::fs::read_to_string(config_path)?;
-            let config: Config = toml::from_str(&content)?;
+            let raw: toml::Value = toml::from_str(&content)?;
+            let mut config: Config = raw.clone().try_into()?;
+            config.explicit_scanner_config = scanner_config_table(&raw);
             info!("Configuration loaded successfully");
             config
         } else {
@@ -824,15 +1550,112 @@ impl Config {
         // Load modular rules from .config/project-lint/rules/active/
         config.modular_rules = Self::load_modular_rules(&config_dir)?;
 
-        // Load profiles
+        // Load profiles and the slices they may include
         config.active_profiles = Self::load_profiles(&config_dir)?;
+        config.slices = Self::load_slices(&config_dir)?;
+        config.resolve_check_toggles(&[]);
 
         // Load plugins
         config.active_plugins = Self::load_plugins(&config_dir)?;
 
+        config.apply_legacy_sections();
+        config.validate_custom_rules()?;
+
         Ok(config)
     }
 
+    /// Reject custom rules whose `when` condition or content patterns do not
+    /// compile, instead of finding out when an event reaches them.
+    pub fn validate_custom_rules(&self) -> Result<()> {
+        for rule in &self.rules.custom_rules {
+            crate::hooks::engine::validate_rule(rule)
+                .map_err(|e| anyhow::anyhow!("Invalid custom rule '{}': {}", rule.name, e))?;
+        }
+        Ok(())
+    }
+
+    /// Modular rules equivalent to the legacy `[git]`, `[files]` and
+    /// `[directories]` sections. Sections that were switched off produce a
+    /// disabled rule.
+    pub fn legacy_rules(&self) -> Vec<ModularRule> {
+        let rule = |name: &str, description: &str, enabled: bool| ModularRule {
+            name: name.to_string(),
+            description: description.to_string(),
+            enabled,
+            severity: RuleSeverity::Warning,
+            triggers: Vec::new(),
+            git: None,
+            file_mappings: None,
+            ignored_patterns: None,
+            scripts: None,
+            conditions: None,
+            messages: None,
+            rules: None,
+            command_rewrites: None,
+        };
+
+        let mut git = rule(
+            LEGACY_GIT_RULE,
+            "Validate git branch usage (migrated from [git])",
+            self.git.warn_wrong_branch,
+        );
+        git.git = Some(GitRuleConfig {
+            warn_wrong_branch: self.git.warn_wrong_branch,
+            allowed_branches: self.git.allowed_branches.clone(),
+            forbidden_branches: self.git.forbidden_branches.clone(),
+        });
+        git.conditions = Some(RuleConditions {
+            require_git_repo: Some(true),
+            check_root_scripts: None,
+            check_scripts_in_src: None,
+        });
+
+        let mut files = rule(
+            LEGACY_FILES_RULE,
+            "Ensure files are placed in directories by type (migrated from [files])",
+            self.files.auto_move,
+        );
+        files.file_mappings = Some(self.files.type_mappings.clone());
+        files.ignored_patterns = Some(
+            self.files
+                .ignored_patterns
+                .iter()
+                .map(|p| (p.clone(), true))
+                .collect(),
+        );
+
+        let mut scripts = rule(
+            LEGACY_DIRECTORIES_RULE,
+            "Ensure scripts live in the scripts directory (migrated from [directories])",
+            self.directories.warn_scripts_location,
+        );
+        scripts.scripts = Some(ScriptRuleConfig {
+            preferred_directory: self.directories.scripts_directory.clone(),
+            alternative_directories: Vec::new(),
+            script_extensions: LEGACY_SCRIPT_EXTENSIONS
+                .iter()
+                .map(|e| e.to_string())
+                .collect(),
+        });
+
+        vec![git, files, scripts]
+    }
+
+    /// For version 1 configs, add the rules equivalent to the legacy sections
+    /// unless a modular rule with the same name is already loaded.
+    fn apply_legacy_sections(&mut self) {
+        if self.version >= CURRENT_CONFIG_VERSION {
+            return;
+        }
+        debug!("Version {} config: applying legacy sections", self.version);
+        for rule in self.legacy_rules() {
+            if rule.enabled && !self.modular_rules.iter().any(|r| r.name == rule.name) {
+                self.generated_legacy_rules.push(rule.name.clone());
+                self.modular_rules.push(rule);
+            }
+        }
+    }
+
     pub fn load_core_config(config_dir: &PathBuf) -> Result<CoreConfig> {
         let core_file = config_dir.join("rules").join("core.toml");
 
@@ -888,6 +1711,50 @@ impl Config {
         Ok(profiles)
     }
 
+    pub fn load_slices(config_dir: &Path) -> Result<Vec<Slice>> {
+        let slices_dir = config_dir.join("rules").join("slices");
+        let mut slices = Vec::new();
+
+        if slices_dir.exists() {
+            debug!("Loading slices from {:?}", slices_dir);
+
+            for entry in WalkDir::new(&slices_dir)
+                .sort_by_file_name()
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| {
+                    e.file_type().is_file() && e.path().extension().is_some_and(|ext| ext == "toml")
+                })
+            {
+                let slice_path = entry.path();
+                let id = slice_path
+                    .file_stem()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string();
+
+                match std::fs::read_to_string(slice_path) {
+                    Ok(content) => match toml::from_str::<Slice>(&content) {
+                        Ok(mut slice) => {
+                            slice.id = id;
+                            debug!("Loaded slice: {}", slice.id);
+                            slices.push(slice);
+                        }
+                        Err(e) => {
+                            warn!("Failed to parse slice file {:?}: {}", slice_path, e);
+                        }
+                    },
+                    Err(e) => {
+                        warn!("Failed to read slice file {:?}: {}", slice_path, e);
+                    }
+                }
+            }
+        }
+
+        debug!("Loaded {} slices", slices.len());
+        Ok(slices)
+    }
+
     pub fn load_plugins(config_dir: &PathBuf) -> Result<Vec<Plugin>> {
         let plugins_dir = config_dir.join("plugins");
         let mut plugins = Vec::new();
@@ -972,6 +1839,201 @@ impl Config {
         Ok(rules)
     }
 
+    /// Locate a nested config file directly inside `dir`.
+    ///
+    /// A subdirectory may carry either a full `.config/project-lint/config.toml`
+    /// (with its own `rules/` tree) or a lighter `.project-lint.toml`. The full
+    /// layout wins when both exist.
+    pub fn find_nested_config(dir: &Path) -> Option<PathBuf> {
+        let full = dir.join(".config").join("project-lint").join("config.toml");
+        if full.is_file() {
+            return Some(full);
+        }
+        let light = dir.join(NESTED_CONFIG_FILE);
+        if light.is_file() {
+            return Some(light);
+        }
+        None
+    }
+
+    /// Find every subdirectory of `project_root` that carries its own config,
+    /// returned relative to the root and sorted so ancestors come first. The
+    /// root itself is never included.
+    pub fn nested_config_dirs(project_root: &Path) -> Vec<PathBuf> {
+        let mut dirs: Vec<PathBuf> = WalkDir::new(project_root)
+            .min_depth(1)
+            .into_iter()
+            .filter_entry(|e| {
+                let name = e.file_name().to_string_lossy();
+                !(e.file_type().is_dir() && NESTED_SCAN_SKIP_DIRS.contains(&name.as_ref()))
+            })
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_dir())
+            .filter(|e| Self::find_nested_config(e.path()).is_some())
+            .filter_map(|e| {
+                e.path()
+                    .strip_prefix(project_root)
+                    .ok()
+                    .map(Path::to_path_buf)
+            })
+            .collect();
+        dirs.sort();
+        debug!("Found {} nested config scopes", dirs.len());
+        dirs
+    }
+
+    /// Resolve the effective configuration for `path` by layering every nested
+    /// config found between `project_root` and `path` (nearest ancestor last,
+    /// so it wins). `path` may be a file or a directory, absolute or relative
+    /// to the project root. Returns a clone of `self` when no nested config
+    /// applies.
+    pub fn for_path(&self, project_root: &Path, path: &Path) -> Result<Config> {
+        let relative = if path.is_absolute() {
+            match path.strip_prefix(project_root) {
+                Ok(rel) => rel.to_path_buf(),
+                Err(_) => return Ok(self.clone()),
+            }
+        } else {
+            path.to_path_buf()
+        };
+
+        let full = project_root.join(&relative);
+        let dir = if full.is_dir() {
+            relative.as_path()
+        } else {
+            relative.parent().unwrap_or_else(|| Path::new(""))
+        };
+
+        let mut config = self.clone();
+        let mut nested = false;
+        let mut current = project_root.to_path_buf();
+        for component in dir.components() {
+            current.push(component);
+            if let Some(nested_file) = Self::find_nested_config(&current) {
+                debug!("Applying nested config {:?}", nested_file);
+                config = config.with_overlay_file(&nested_file)?;
+                nested = true;
+            }
+        }
+        if nested && !self.runtime_overrides.is_empty() {
+            config = config.with_assignments(&self.runtime_overrides)?;
+        }
+        Ok(config)
+    }
+
+    /// Layer a nested config file on top of `self`.
+    ///
+    /// TOML tables merge key by key; arrays and scalars in the nested file
+    /// replace the inherited value. When the file lives in a
+    /// `.config/project-lint/` directory, its modular rules and profiles are
+    /// added too, replacing inherited entries with the same name.
+    pub fn with_overlay_file(&self, config_path: &Path) -> Result<Config> {
+        let content = std::fs::read_to_string(config_path)?;
+        let overlay: toml::Value = toml::from_str(&content)?;
+        let mut config = self.with_overlay(overlay)?;
+
+        let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+        if config_dir.join("rules").is_dir() {
+            for rule in Self::load_modular_rules(&config_dir.to_path_buf())? {
+                config.modular_rules.retain(|r| r.name != rule.name);
+                config.generated_legacy_rules.retain(|n| *n != rule.name);
+                config.modular_rules.push(rule);
+            }
+            for profile in Self::load_profiles(&config_dir.to_path_buf())? {
+                config
+                    .active_profiles
+                    .retain(|p| p.metadata.name != profile.metadata.name);
+                config.active_profiles.push(profile);
+            }
+            config.resolve_check_toggles(&[]);
+        }
+        config.validate_custom_rules()?;
+
+        Ok(config)
+    }
+
+    /// Deep-merge a parsed TOML fragment over this config's serialized form.
+    /// Fields that are not part of the TOML schema (loaded rules, profiles,
+    /// plugins, core config) are carried over unchanged.
+    pub fn with_overlay(&self, overlay: toml::Value) -> Result<Config> {
+        let mut explicit = toml::Value::Table(self.explicit_scanner_config.clone());
+        merge_toml(
+            &mut explicit,
+            toml::Value::Table(scanner_config_table(&overlay)),
+        );
+        let mut base = toml::Value::try_from(self)?;
+        merge_toml(&mut base, overlay);
+        let mut config = self.rebuild_from(base)?;
+        if let toml::Value::Table(explicit) = explicit {
+            config.explicit_scanner_config = explicit;
+        }
+        Ok(config)
+    }
+
+    /// Layer `PROJECT_LINT__*` environment variables and then `--set`
+    /// assignments on top of this config.
+    pub fn with_runtime_overrides(&self, sets: &[String]) -> Result<Config> {
+        self.with_override_sources(&env_overrides(), sets)
+    }
+
+    /// Layer the given `PROJECT_LINT__*` variables and then `--set`
+    /// assignments on top of this config, for callers (the daemon) whose own
+    /// environment is not the one to honour.
+    pub fn with_override_sources(
+        &self,
+        env: &[(String, String)],
+        sets: &[String],
+    ) -> Result<Config> {
+        let mut assignments: Vec<ConfigAssignment> = env
+            .iter()
+            .filter_map(|(name, value)| ConfigAssignment::from_env_var(name, value))
+            .collect();
+        for spec in sets {
+            assignments.push(ConfigAssignment::parse(spec)?);
+        }
+        if assignments.is_empty() {
+            return Ok(self.clone());
+        }
+
+        let mut config = self.with_assignments(&assignments)?;
+        config.runtime_overrides = assignments;
+        Ok(config)
+    }
+
+    /// Apply `assignments` in order to this config's serialized form.
+    pub fn with_assignments(&self, assignments: &[ConfigAssignment]) -> Result<Config> {
+        let mut base = toml::Value::try_from(self)?;
+        for assignment in assignments {
+            debug!("Applying config override {}", assignment.key.join("."));
+            assignment.apply(&mut base)?;
+        }
+        self.rebuild_from(base)
+            .map_err(|e| anyhow::anyhow!("Invalid config override: {}", e))
+    }
+
+    /// Deserialize `value` and carry over the fields outside the TOML schema.
+    /// Rules built from legacy sections are rebuilt from the merged sections.
+    fn rebuild_from(&self, value: toml::Value) -> Result<Config> {
+        let mut merged: Config = value.try_into()?;
+        merged.modular_rules = self
+            .modular_rules
+            .iter()
+            .filter(|r| !self.generated_legacy_rules.contains(&r.name))
+            .cloned()
+            .collect();
+        merged.active_profiles = self.active_profiles.clone();
+        merged.slices = self.slices.clone();
+        merged.active_slices = self.active_slices.clone();
+        merged.check_toggles = self.check_toggles.clone();
+        merged.diagnostics = self.diagnostics.clone();
+        merged.active_plugins = self.active_plugins.clone();
+        merged.core_config = self.core_config.clone();
+        merged.runtime_overrides = self.runtime_overrides.clone();
+        merged.explicit_scanner_config = self.explicit_scanner_config.clone();
+        merged.apply_legacy_sections();
+        Ok(merged)
+    }
+
     pub fn save(&self) -> Result<()> {
         let config_dir = crate::utils::get_config_dir()?;
         self.save_to(&config_dir)
@@ -1004,6 +2066,345 @@ impl Config {
         }
     }
 
+    /// Whether `check_name` is enabled globally or any `[[overrides]]` block
+    /// enables an id its findings may carry: the check name, a
+    /// `<category>/<rule>` id of the check's `category`, or a bare rule id
+    /// (one that is not a [`BUILTIN_CHECKS`] name). Checks that run
+    /// project-wide use this as their gate and then filter findings per file
+    /// with [`Config::is_enabled_for`].
+    pub fn is_check_enabled_anywhere(&self, check_name: &str, category: Option<&str>) -> bool {
+        let belongs = |id: &String| {
+            id == check_name
+                || category
+                    .is_some_and(|c| id.strip_prefix(c).is_some_and(|rest| rest.starts_with('/')))
+                || (!id.contains('/') && !BUILTIN_CHECKS.contains(&id.as_str()))
+        };
+        self.is_check_enabled(check_name)
+            || self
+                .overrides
+                .iter()
+                .any(|o| o.enabled_checks.iter().any(belongs))
+    }
+
+    /// This config with `check_name` enabled, for a check that an active
+    /// profile section asks for (such as `[structure]`): allowlists need not
+    /// list it, but a check disabled by name stays off. `None` when it does
+    /// not run anywhere.
+    pub fn with_requested_check(&self, check_name: &str) -> Option<Config> {
+        if self.is_check_enabled_anywhere(check_name, Some("structure")) {
+            return Some(self.clone());
+        }
+        if self.rules.mode != RulesMode::Allowlist
+            || self.get_effective_disabled_checks().contains(check_name)
+        {
+            return None;
+        }
+        let mut config = self.clone();
+        config.rules.enabled_checks.push(check_name.to_string());
+        Some(config)
+    }
+
+    /// Overrides applying to `path` (relative to the project root), in order.
+    pub fn overrides_for<'a>(&'a self, path: &'a Path) -> impl Iterator<Item = &'a ConfigOverride> {
+        self.overrides.iter().filter(move |o| o.matches(path))
+    }
+
+    /// Whether a finding addressable by any of `ids` (check name, rule id,
+    /// `category/rule`) is reported for `path`. Global rules apply first, then
+    /// each matching override in order.
+    pub fn is_enabled_for(&self, ids: &[&str], path: &Path) -> bool {
+        let mut enabled = match self.rules.mode {
+            RulesMode::Allowlist => {
+                let effective = self.get_effective_enabled_checks();
+                ids.iter().any(|id| effective.contains(*id))
+            }
+            RulesMode::Denylist => {
+                let effective = self.get_effective_disabled_checks();
+                !ids.iter().any(|id| effective.contains(*id))
+            }
+        };
+        if let Some(state) = self.override_state(ids, path) {
+            enabled = state;
+        }
+        enabled
+    }
+
+    /// Enabled state forced by the last matching `[[overrides]]` block that
+    /// mentions any of `ids`, if any.
+    pub fn override_state(&self, ids: &[&str], path: &Path) -> Option<bool> {
+        let mut state = None;
+        for o in self.overrides_for(path) {
+            if ConfigOverride::mentions(&o.disabled_checks, ids) {
+                state = Some(false);
+            }
+            if ConfigOverride::mentions(&o.enabled_checks, ids) {
+                state = Some(true);
+            }
+        }
+        state
+    }
+
+    /// Severity override for a finding addressable by any of `ids` at `path`,
+    /// if one is configured. Matching `[[overrides]]` win over
+    /// `[rules.severity_overrides]`.
+    pub fn severity_override_for(&self, ids: &[&str], path: &Path) -> Option<RuleSeverity> {
+        let lookup =
+            |map: &HashMap<String, RuleSeverity>| ids.iter().find_map(|id| map.get(*id).cloned());
+        let mut severity = lookup(&self.rules.severity_overrides);
+        for o in self.overrides_for(path) {
+            if let Some(s) = lookup(&o.severity_overrides) {
+                severity = Some(s);
+            }
+        }
+        severity
+    }
+
+    /// Fold every `[[overrides]]` block matching `path` (relative to the
+    /// project root) into a plain config, so consumers that only know about
+    /// global settings see the per-file view.
+    pub fn for_file(&self, path: &Path) -> Result<Config> {
+        let mut config = self.clone();
+        for o in self.overrides_for(path) {
+            if let Some(fragment) = &o.scanner_config {
+                config = config.with_scanner_config_overlay(fragment)?;
+            }
+            let rules = &mut config.rules;
+            for check in &o.enabled_checks {
+                rules.disabled_checks.retain(|c| c != check);
+                if !rules.enabled_checks.contains(check) {
+                    rules.enabled_checks.push(check.clone());
+                }
+            }
+            for check in &o.disabled_checks {
+                rules.enabled_checks.retain(|c| c != check);
+                if !rules.disabled_checks.contains(check) {
+                    rules.disabled_checks.push(check.clone());
+                }
+            }
+            for (rule, severity) in &o.severity_overrides {
+                rules
+                    .severity_overrides
+                    .insert(rule.clone(), severity.clone());
+            }
+        }
+        config.overrides.clear();
+        Ok(config)
+    }
+
+    /// Merge a `[scanner_config]` fragment over this config.
+    pub fn with_scanner_config_overlay(&self, fragment: &toml::Value) -> Result<Config> {
+        let mut overlay = toml::value::Table::new();
+        overlay.insert("scanner_config".to_string(), fragment.clone());
+        self.with_overlay(toml::Value::Table(overlay))
+    }
+
+    /// Make `profiles` the active set and apply the slices they include.
+    ///
+    /// Profiles are first resolved against each other: `requires` and
+    /// `conflicts_with` may deactivate some, and the survivors are ordered by
+    /// descending priority. Each deactivation is recorded in `diagnostics`.
+    ///
+    /// Each slice then ranks right after the profile that included it, and a
+    /// slice right after the slice that included it. The same precedence
+    /// decides check toggles and `[scanner_config]` fragments: the highest
+    /// ranked source to set a value wins. Fragments rank below the
+    /// `[scanner_config]` written in config files and runtime overrides.
+    /// Slice `[[rules]]` become a modular rule named after the slice, unless a
+    /// rule of that name is loaded.
+    pub fn with_active_profiles(&self, profiles: Vec<Profile>) -> Result<Config> {
+        let mut config = self.clone();
+        let (profiles, mut diagnostics) = resolve_profile_set(profiles);
+        config.active_profiles = profiles;
+        let slices = config.resolve_slices();
+        config.active_slices = slices.iter().map(|(_, s)| s.clone()).collect();
+        diagnostics.extend(config.resolve_check_toggles(&slices));
+        config.diagnostics = diagnostics;
+
+        // Lowest ranked first, so higher ranked fragments merge over them;
+        // the user's own settings and runtime overrides go on top
+        let mut fragments = toml::Value::Table(toml::value::Table::new());
+        for slice in config.active_slices.iter().rev() {
+            if let Some(fragment) = &slice.scanner_config {
+                merge_toml(&mut fragments, fragment.clone());
+            }
+        }
+        if fragments.as_table().is_some_and(|t| !t.is_empty()) {
+            merge_toml(
+                &mut fragments,
+                toml::Value::Table(config.explicit_scanner_config.clone()),
+            );
+            let mut overlay = toml::value::Table::new();
+            overlay.insert("scanner_config".to_string(), fragments);
+            let mut base = toml::Value::try_from(&config)?;
+            merge_toml(&mut base, toml::Value::Table(overlay));
+            config = config.rebuild_from(base)?;
+            let scanner_overrides: Vec<ConfigAssignment> = config
+                .runtime_overrides
+                .iter()
+                .filter(|a| a.key.first().is_some_and(|k| k == "scanner_config"))
+                .cloned()
+                .collect();
+            config = config.with_assignments(&scanner_overrides)?;
+        }
+        for slice in config.active_slices.clone() {
+            let name = slice.rule_name();
+            if slice.rules.is_empty() || config.modular_rules.iter().any(|r| r.name == name) {
+                continue;
+            }
+            config.modular_rules.push(ModularRule {
+                name: name.to_string(),
+                description: slice
+                    .metadata
+                    .as_ref()
+                    .map(|m| m.description.clone())
+                    .unwrap_or_default(),
+                enabled: true,
+                severity: RuleSeverity::Warning,
+                triggers: Vec::new(),
+                git: None,
+                file_mappings: None,
+                ignored_patterns: None,
+                scripts: None,
+                conditions: None,
+                messages: None,
+                rules: Some(slice.rules.clone()),
+                command_rewrites: None,
+            });
+        }
+
+        Ok(config)
+    }
+
+    /// Slices reachable from the active profiles, each once, with the name
+    /// of the profile that first included it. A slice comes before the
+    /// slices it includes. Unknown references and include cycles are
+    /// reported and skipped.
+    fn resolve_slices(&self) -> Vec<(String, Slice)> {
+        fn visit(
+            config: &Config,
+            owner: &str,
+            id: &str,
+            stack: &mut Vec<String>,
+            resolved: &mut Vec<(String, Slice)>,
+        ) {
+            if stack.iter().any(|s| s == id) {
+                warn!(
+                    "Slice include cycle: {} -> {}; skipping '{}'",
+                    stack.join(" -> "),
+                    id,
+                    id
+                );
+                return;
+            }
+            if resolved.iter().any(|(_, s)| s.id == id) {
+                return;
+            }
+            let Some(slice) = config.slices.iter().find(|s| s.id == id) else {
+                warn!("Slice '{}' not found in rules/slices", id);
+                return;
+            };
+            resolved.push((owner.to_string(), slice.clone()));
+            stack.push(id.to_string());
+            for include in slice.includes() {
+                visit(config, owner, &include, stack, resolved);
+            }
+            stack.pop();
+        }
+
+        let mut resolved = Vec::new();
+        for profile in &self.active_profiles {
+            let includes = profile
+                .checks
+                .as_ref()
+                .and_then(|c| c.slices.as_ref())
+                .map(|s| s.include.clone())
+                .unwrap_or_default();
+            for include in includes {
+                visit(
+                    self,
+                    &profile.metadata.name,
+                    &slice_id(&include),
+                    &mut Vec::new(),
+                    &mut resolved,
+                );
+            }
+        }
+        resolved
+    }
+
+    /// Severity for `rule_name` after applying `[rules.severity_overrides]`.
+    pub fn severity_for(&self, rule_name: &str, default: &RuleSeverity) -> RuleSeverity {
+        self.rules
+            .severity_overrides
+            .get(rule_name)
+            .cloned()
+            .unwrap_or_else(|| default.clone())
+    }
+
+    /// Decide each check the active profiles and `slices` (with the name of
+    /// the profile that included them) toggle, into `check_toggles`. Returns
+    /// a diagnostic per disagreement.
+    ///
+    /// Sources are consulted in precedence order: profiles by descending
+    /// priority (ties by name), each followed by the slices it included. The
+    /// first source to mention a check decides it.
+    fn resolve_check_toggles(&mut self, slices: &[(String, Slice)]) -> Vec<String> {
+        let mut profiles: Vec<&Profile> = self.active_profiles.iter().collect();
+        profiles.sort_by(|a, b| profile_precedence(a, b));
+        let sources = profiles.into_iter().flat_map(|p| {
+            let priority = p.metadata.priority;
+            std::iter::once((
+                format!("profile '{}'", p.metadata.name),
+                priority,
+                p.checks.as_ref(),
+            ))
+            .chain(
+                slices
+                    .iter()
+                    .filter(move |(owner, _)| *owner == p.metadata.name)
+                    .map(move |(_, s)| (format!("slice '{}'", s.id), priority, s.checks.as_ref())),
+            )
+        });
+
+        let mut verdicts: HashMap<String, (bool, String)> = HashMap::new();
+        let mut diagnostics = Vec::new();
+        for (source, priority, checks) in sources {
+            let Some(checks) = checks else {
+                continue;
+            };
+            let toggles = checks
+                .enable
+                .iter()
+                .map(|c| (c, true))
+                .chain(checks.disable.iter().map(|c| (c, false)));
+            for (check, on) in toggles {
+                match verdicts.get(check) {
+                    None => {
+                        verdicts.insert(check.clone(), (on, source.clone()));
+                    }
+                    Some((decided, winner)) if *decided != on => {
+                        diagnostics.push(format!(
+                            "{} {} check '{}' but {} (priority {}) {} it; {} wins",
+                            winner,
+                            if *decided { "enables" } else { "disables" },
+                            check,
+                            source,
+                            priority,
+                            if on { "enables" } else { "disables" },
+                            winner
+                        ));
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        self.check_toggles = verdicts
+            .into_iter()
+            .map(|(check, (on, _))| (check, on))
+            .collect();
+        diagnostics
+    }
+
     fn get_effective_enabled_checks(&self) -> std::collections::HashSet<String> {
         let mut enabled = std::collections::HashSet::new();
 
@@ -1012,12 +2413,10 @@ impl Config {
             enabled.insert(check.clone());
         }
 
-        // Add profile rules
-        for profile in &self.active_profiles {
-            if let Some(checks) = &profile.checks {
-                for check in &checks.enable {
-                    enabled.insert(check.clone());
-                }
+        // Add profile and slice rules
+        for (check, on) in &self.check_toggles {
+            if *on {
+                enabled.insert(check.clone());
             }
         }
 
@@ -1032,12 +2431,10 @@ impl Config {
             disabled.insert(check.clone());
         }
 
-        // Add profile rules
-        for profile in &self.active_profiles {
-            if let Some(checks) = &profile.checks {
-                for check in &checks.disable {
-                    disabled.insert(check.clone());
-                }
+        // Add profile and slice rules
+        for (check, on) in &self.check_toggles {
+            if !*on {
+                disabled.insert(check.clone());
             }
         }
 
@@ -1094,6 +2491,9 @@ mod tests {
                 scope: "test".to_string(),
                 updated: "today".to_string(),
                 description: "test".to_string(),
+                priority: 0,
+                conflicts_with: Vec::new(),
+                requires: Vec::new(),
             },
             activation: ProfileActivation {
                 paths: vec![],
@@ -1103,6 +2503,7 @@ mod tests {
                 globs: vec![],
                 content: vec![],
                 events: vec![],
+                when: None,
             },
             enable: ProfileEnable {
                 domains: vec![],
@@ -1117,9 +2518,10 @@ mod tests {
             devops_specific: None,
             structure: None,
             extensions: None,
+            commands: None,
         };
 
-        config.active_profiles.push(profile);
+        let config = config.with_active_profiles(vec![profile]).unwrap();
 
         assert!(config.is_check_enabled("repo_check"));
         assert!(config.is_check_enabled("profile_check"));
@@ -1139,6 +2541,9 @@ mod tests {
                 scope: "test".to_string(),
                 updated: "today".to_string(),
                 description: "test".to_string(),
+                priority: 0,
+                conflicts_with: Vec::new(),
+                requires: Vec::new(),
             },
             activation: ProfileActivation {
                 paths: vec![],
@@ -1148,6 +2553,7 @@ mod tests {
                 globs: vec![],
                 content: vec![],
                 events: vec![],
+                when: None,
             },
             enable: ProfileEnable {
                 domains: vec![],
@@ -1162,9 +2568,10 @@ mod tests {
             devops_specific: None,
             structure: None,
             extensions: None,
+            commands: None,
         };
 
-        config.active_profiles.push(profile);
+        let config = config.with_active_profiles(vec![profile]).unwrap();
 
         assert!(!config.is_check_enabled("repo_disabled"));
         assert!(!config.is_check_enabled("profile_disabled"));
@@ -1271,10 +2678,750 @@ required_lockfile = "pnpm-lock.yaml"
         assert_eq!(pme.required_lockfile.as_deref(), Some("pnpm-lock.yaml"));
     }
 
+    #[test]
+    fn test_terraform_config_default_matches_an_empty_section() {
+        let empty: TerraformConfig = toml::from_str("").unwrap();
+        assert!(empty.check_fmt);
+        assert!(TerraformConfig::default().check_fmt);
+    }
+
+    #[test]
+    fn test_kubernetes_config_default_matches_an_empty_section() {
+        let empty: KubernetesConfig = toml::from_str("").unwrap();
+        let default = KubernetesConfig::default();
+        assert!(empty.require_probes && empty.require_resources);
+        assert_eq!(
+            (default.require_probes, default.require_resources),
+            (empty.require_probes, empty.require_resources)
+        );
+    }
+
     #[test]
     fn test_scanner_config_defaults_to_none() {
         let config: Config = toml::from_str("").expect("empty");
         assert!(config.scanner_config.rust_file_naming.is_none());
         assert!(config.scanner_config.dockerfile_security.is_none());
     }
+
+    #[test]
+    fn test_nested_overlay_merges_tables_and_replaces_arrays() {
+        let mut base = Config::default();
+        base.rules.disabled_checks = vec!["git_branch".to_string()];
+        base.scanner_config.dockerfile_security = Some(DockerfileSecurityConfig {
+            require_pinned_digests: true,
+            require_non_root_user: true,
+            forbid_copy_dot: true,
+            require_no_new_privileges: true,
+            require_healthcheck: true,
+        });
+
+        let overlay: toml::Value = toml::from_str(
+            r#"
+[rules]
+disabled_checks = ["ast_analysis"]
+
+[rules.severity_overrides]
+"pin-image-digests" = "error"
+
+[scanner_config.dockerfile_security]
+forbid_copy_dot = false
+"#,
+        )
+        .expect("overlay parses");
+
+        let merged = base.with_overlay(overlay).expect("merge");
+        assert_eq!(merged.rules.disabled_checks, vec!["ast_analysis"]);
+        assert_eq!(merged.rules.mode, RulesMode::Denylist);
+        let docker = merged.scanner_config.dockerfile_security.as_ref().unwrap();
+        assert!(docker.require_pinned_digests);
+        assert!(!docker.forbid_copy_dot);
+        assert_eq!(
+            merged.severity_for("pin-image-digests", &RuleSeverity::Warning),
+            RuleSeverity::Error
+        );
+        assert_eq!(
+            merged.severity_for("no-copy-dot", &RuleSeverity::Warning),
+            RuleSeverity::Warning
+        );
+    }
+
+    #[test]
+    fn test_for_path_applies_nearest_ancestor_last() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("crates/core/src")).unwrap();
+        std::fs::create_dir_all(root.join("scripts")).unwrap();
+        std::fs::write(
+            root.join("crates/.project-lint.toml"),
+            "[rules]\ndisabled_checks = [\"typescript_analysis\"]\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("crates/core/.config/project-lint")).unwrap();
+        std::fs::write(
+            root.join("crates/core/.config/project-lint/config.toml"),
+            "[rules]\ndisabled_checks = [\"ast_analysis\"]\n",
+        )
+        .unwrap();
+
+        let base = Config::default();
+        let core = base
+            .for_path(root, Path::new("crates/core/src/lib.rs"))
+            .unwrap();
+        assert!(!core.is_check_enabled("ast_analysis"));
+        assert!(core.is_check_enabled("typescript_analysis"));
+
+        let crates = base.for_path(root, &root.join("crates/other.rs")).unwrap();
+        assert!(!crates.is_check_enabled("typescript_analysis"));
+
+        let scripts = base.for_path(root, Path::new("scripts")).unwrap();
+        assert!(scripts.is_check_enabled("typescript_analysis"));
+
+        assert_eq!(
+            Config::nested_config_dirs(root),
+            vec![PathBuf::from("crates"), PathBuf::from("crates/core")]
+        );
+    }
+
+    #[test]
+    fn test_overrides_toggle_rules_per_path() {
+        let config: Config = toml::from_str(
+            r#"
+[rules]
+disabled_checks = ["docker/pin-image-digests"]
+
+[[overrides]]
+files = ["tests/fixtures/**"]
+disabled_checks = ["security/suspicious_password_var"]
+
+[[overrides]]
+files = ["deploy/**"]
+excludes = ["deploy/dev/**"]
+enabled_checks = ["docker/pin-image-digests"]
+severity_overrides = { "pin-image-digests" = "error" }
+"#,
+        )
+        .expect("overrides parse");
+
+        let password = [
+            "security_analysis",
+            "suspicious_password_var",
+            "security/suspicious_password_var",
+        ];
+        assert!(!config.is_enabled_for(&password, Path::new("tests/fixtures/creds.py")));
+        assert!(config.is_enabled_for(&password, Path::new("src/creds.py")));
+
+        let digests = [
+            "dockerfile_lint",
+            "pin-image-digests",
+            "docker/pin-image-digests",
+        ];
+        assert!(config.is_enabled_for(&digests, Path::new("deploy/api/Dockerfile")));
+        assert!(!config.is_enabled_for(&digests, Path::new("deploy/dev/Dockerfile")));
+        assert!(!config.is_enabled_for(&digests, Path::new("Dockerfile")));
+        assert_eq!(
+            config.severity_override_for(&digests, Path::new("deploy/api/Dockerfile")),
+            Some(RuleSeverity::Error)
+        );
+        assert_eq!(
+            config.severity_override_for(&digests, Path::new("Dockerfile")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_overrides_enable_globally_disabled_check() {
+        let config: Config = toml::from_str(
+            r#"
+[rules]
+disabled_checks = ["dockerfile_lint"]
+
+[[overrides]]
+files = ["deploy/**"]
+enabled_checks = ["dockerfile_lint"]
+"#,
+        )
+        .expect("overrides parse");
+
+        assert!(!config.is_check_enabled("dockerfile_lint"));
+        assert!(config.is_check_enabled_anywhere("dockerfile_lint", Some("docker")));
+    }
+
+    #[test]
+    fn test_overrides_enabling_a_rule_open_its_check() {
+        let config: Config = toml::from_str(
+            r#"
+[rules]
+mode = "allowlist"
+enabled_checks = ["file_naming"]
+
+[[overrides]]
+files = ["deploy/**"]
+enabled_checks = ["docker/pin-image-digests"]
+"#,
+        )
+        .expect("overrides parse");
+
+        assert!(config.is_check_enabled_anywhere("dockerfile_lint", Some("docker")));
+        assert!(!config.is_check_enabled_anywhere("compose_lint", Some("compose")));
+
+        // A bare rule id may belong to any check
+        let bare: Config = toml::from_str(
+            r#"
+[rules]
+mode = "allowlist"
+enabled_checks = []
+
+[[overrides]]
+files = ["infra/**"]
+enabled_checks = ["open-ingress"]
+"#,
+        )
+        .expect("overrides parse");
+        assert!(bare.is_check_enabled_anywhere("terraform", Some("terraform")));
+    }
+
+    #[test]
+    fn test_for_file_folds_matching_overrides() {
+        let config: Config = toml::from_str(
+            r#"
+[scanner_config.dockerfile_security]
+require_pinned_digests = false
+
+[[overrides]]
+files = ["deploy/**"]
+disabled_checks = ["ast_analysis"]
+scanner_config = { dockerfile_security = { require_pinned_digests = true } }
+"#,
+        )
+        .expect("overrides parse");
+
+        let deploy = config.for_file(Path::new("deploy/Dockerfile")).unwrap();
+        assert!(deploy.overrides.is_empty());
+        assert!(!deploy.is_check_enabled("ast_analysis"));
+        assert!(
+            deploy
+                .scanner_config
+                .dockerfile_security
+                .as_ref()
+                .unwrap()
+                .require_pinned_digests
+        );
+
+        let other = config.for_file(Path::new("src/main.rs")).unwrap();
+        assert!(other.is_check_enabled("ast_analysis"));
+        assert!(
+            !other
+                .scanner_config
+                .dockerfile_security
+                .as_ref()
+                .unwrap()
+                .require_pinned_digests
+        );
+    }
+
+    #[test]
+    fn test_config_assignment_parse_ops_and_values() {
+        let set = ConfigAssignment::parse("rules.mode=allowlist").unwrap();
+        assert_eq!(set.key, vec!["rules", "mode"]);
+        assert_eq!(set.op, AssignOp::Set);
+        assert_eq!(set.value, toml::Value::String("allowlist".to_string()));
+
+        let append = ConfigAssignment::parse("rules.disabled_checks+=ast_analysis").unwrap();
+        assert_eq!(append.op, AssignOp::Append);
+
+        let flag =
+            ConfigAssignment::parse("scanner_config.dockerfile_security.forbid_copy_dot=false")
+                .unwrap();
+        assert_eq!(flag.value, toml::Value::Boolean(false));
+
+        // Operators inside the value do not change the assignment
+        let message = ConfigAssignment::parse("git.message=use x+=1, not y-=2").unwrap();
+        assert_eq!(message.key, vec!["git", "message"]);
+        assert_eq!(message.op, AssignOp::Set);
+        assert_eq!(
+            message.value,
+            toml::Value::String("use x+=1, not y-=2".to_string())
+        );
+        let remove = ConfigAssignment::parse("rules.enabled_checks-=a+=b").unwrap();
+        assert_eq!(remove.key, vec!["rules", "enabled_checks"]);
+        assert_eq!(remove.op, AssignOp::Remove);
+
+        assert!(ConfigAssignment::parse("rules.mode").is_err());
+        assert!(ConfigAssignment::parse("rules..mode=x").is_err());
+    }
+
+    #[test]
+    fn test_config_assignment_from_env_var() {
+        let env = ConfigAssignment::from_env_var("PROJECT_LINT__RULES__MODE", "allowlist").unwrap();
+        assert_eq!(env.key, vec!["rules", "mode"]);
+        assert!(ConfigAssignment::from_env_var("PROJECT_LINT_LOG_LEVEL", "debug").is_none());
+    }
+
+    #[test]
+    fn test_with_assignments_layers_in_order() {
+        let mut base = Config::default();
+        base.rules.enabled_checks = Vec::new();
+        base.rules.disabled_checks = vec!["git_branch".to_string()];
+
+        let assignments = vec![
+            ConfigAssignment::from_env_var("PROJECT_LINT__RULES__MODE", "allowlist").unwrap(),
+            ConfigAssignment::parse("rules.enabled_checks+=[\"ast_analysis\", \"file_naming\"]")
+                .unwrap(),
+            ConfigAssignment::parse("rules.enabled_checks-=file_naming").unwrap(),
+            ConfigAssignment::parse("rules.disabled_checks+=ast_analysis").unwrap(),
+        ];
+        let config = base.with_assignments(&assignments).unwrap();
+        assert_eq!(config.rules.mode, RulesMode::Allowlist);
+        assert_eq!(config.rules.enabled_checks, vec!["ast_analysis"]);
+        assert_eq!(
+            config.rules.disabled_checks,
+            vec!["git_branch", "ast_analysis"]
+        );
+
+        let bad = ConfigAssignment::parse("rules.mode=sometimes").unwrap();
+        assert!(base.with_assignments(&[bad]).is_err());
+    }
+
+    #[test]
+    fn test_runtime_overrides_win_over_nested_config() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("app")).unwrap();
+        std::fs::write(
+            root.join("app/.project-lint.toml"),
+            "[rules]\nmode = \"allowlist\"\n",
+        )
+        .unwrap();
+
+        let config = Config::default()
+            .with_runtime_overrides(&["rules.mode=denylist".to_string()])
+            .unwrap();
+        let app = config.for_path(root, Path::new("app/main.rs")).unwrap();
+        assert_eq!(app.rules.mode, RulesMode::Denylist);
+    }
+
+    #[test]
+    fn test_legacy_sections_only_apply_to_version_1() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config_file = dir.path().join("config.toml");
+
+        std::fs::write(
+            &config_file,
+            "[directories]\nscripts_directory = \"tools\"\n",
+        )
+        .unwrap();
+        let legacy = Config::load_from_file(&config_file).unwrap();
+        assert_eq!(legacy.version, LEGACY_CONFIG_VERSION);
+        let scripts = legacy
+            .modular_rules
+            .iter()
+            .find(|r| r.name == LEGACY_DIRECTORIES_RULE)
+            .and_then(|r| r.scripts.as_ref())
+            .expect("legacy [directories] becomes a modular rule");
+        assert_eq!(scripts.preferred_directory, "tools");
+
+        std::fs::write(
+            &config_file,
+            "version = 2\n[directories]\nscripts_directory = \"tools\"\n",
+        )
+        .unwrap();
+        let current = Config::load_from_file(&config_file).unwrap();
+        assert!(current.modular_rules.is_empty());
+    }
+
+    #[test]
+    fn test_legacy_sections_follow_nested_and_runtime_overrides() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        let config_file = root.join("config.toml");
+        std::fs::write(
+            &config_file,
+            "[git]\nwarn_wrong_branch = true\nforbidden_branches = [\"main\"]\n\
+             [directories]\nwarn_scripts_location = true\nscripts_directory = \"tools\"\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("app")).unwrap();
+        std::fs::write(
+            root.join("app/.project-lint.toml"),
+            "[directories]\nscripts_directory = \"bin\"\n",
+        )
+        .unwrap();
+        let legacy = Config::load_from_file(&config_file).unwrap();
+        let rule = |config: &Config, name: &str| {
+            config
+                .modular_rules
+                .iter()
+                .find(|r| r.name == name)
+                .cloned()
+        };
+        assert!(rule(&legacy, LEGACY_GIT_RULE).is_some());
+
+        // A nested section rebuilds the rule for its subtree
+        let app = legacy.for_path(root, Path::new("app/run.sh")).unwrap();
+        let scripts = rule(&app, LEGACY_DIRECTORIES_RULE)
+            .unwrap()
+            .scripts
+            .unwrap();
+        assert_eq!(scripts.preferred_directory, "bin");
+        assert_eq!(
+            app.modular_rules
+                .iter()
+                .filter(|r| r.name == LEGACY_DIRECTORIES_RULE)
+                .count(),
+            1
+        );
+
+        // `--set` and environment overrides switch the legacy check off
+        let set = legacy
+            .with_runtime_overrides(&["git.warn_wrong_branch=false".to_string()])
+            .unwrap();
+        assert!(rule(&set, LEGACY_GIT_RULE).is_none());
+        let env = legacy
+            .with_override_sources(
+                &[(
+                    "PROJECT_LINT__GIT__WARN_WRONG_BRANCH".to_string(),
+                    "false".to_string(),
+                )],
+                &[],
+            )
+            .unwrap();
+        assert!(rule(&env, LEGACY_GIT_RULE).is_none());
+        assert!(rule(&env, LEGACY_DIRECTORIES_RULE).is_some());
+    }
+
+    #[test]
+    fn test_load_rejects_malformed_custom_rules() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config_file = dir.path().join("config.toml");
+        let rule = |when: &str| {
+            format!(
+                "[[rules.custom_rules]]\nname = \"no-push\"\npattern = \"*\"\nmessage = \"no\"\nseverity = \"error\"\nwhen = '{}'\n",
+                when
+            )
+        };
+
+        std::fs::write(&config_file, rule(r#"command =~ "^git push""#)).unwrap();
+        assert!(Config::load_from_file(&config_file).is_ok());
+
+        std::fs::write(&config_file, rule(r#"comand =~ "^git push""#)).unwrap();
+        let err = Config::load_from_file(&config_file).unwrap_err();
+        assert!(err.to_string().contains("no-push"), "{}", err);
+
+        let bad_regex = rule(r#"command =~ "^git push""#) + "content_regex = '(unclosed'\n";
+        std::fs::write(&config_file, bad_regex).unwrap();
+        let err = Config::load_from_file(&config_file).unwrap_err();
+        assert!(err.to_string().contains("content_regex"), "{}", err);
+    }
+
+    #[test]
+    fn test_active_profile_pulls_in_slices() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let rules_dir = dir.path().join("rules");
+        std::fs::create_dir_all(rules_dir.join("profiles")).unwrap();
+        std::fs::create_dir_all(rules_dir.join("slices")).unwrap();
+        let config_file = dir.path().join("config.toml");
+        std::fs::write(
+            &config_file,
+            "version = 2\n[rules]\nmode = \"allowlist\"\nenabled_checks = []\n",
+        )
+        .unwrap();
+        std::fs::write(
+            rules_dir.join("profiles/containers.toml"),
+            r#"
+[metadata]
+name = "containers"
+version = "1.0.0"
+updated = "2025-01-01"
+description = "containers"
+
+[activation]
+indicators = ["Dockerfile"]
+
+[enable]
+domains = []
+plugins = []
+
+[checks.slices]
+include = ["hardening.toml", "ops"]
+"#,
+        )
+        .unwrap();
+        // `hardening` and `ops` include each other: each must load exactly once
+        std::fs::write(
+            rules_dir.join("slices/hardening.toml"),
+            r#"
+[metadata]
+name = "container-hardening"
+version = "1.0.0"
+updated = "2025-01-01"
+description = "hardening"
+
+[checks]
+enable = ["dockerfile_lint"]
+
+[checks.slices]
+include = ["ops"]
+
+[scanner_config.dockerfile_security]
+require_pinned_digests = false
+
+[[rules]]
+name = "image_latest"
+pattern = "**/Dockerfile*"
+message = "pin the base image"
+severity = "warning"
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            rules_dir.join("slices/ops.toml"),
+            "[checks]\nenable = [\"ci_cd_parity\"]\n[checks.slices]\ninclude = [\"hardening\"]\n",
+        )
+        .unwrap();
+
+        let config = Config::load_from_file(&config_file).unwrap();
+        assert_eq!(config.slices.len(), 2);
+        assert!(!config.is_check_enabled("dockerfile_lint"));
+
+        let inactive = config.with_active_profiles(Vec::new()).unwrap();
+        assert!(inactive.active_slices.is_empty());
+        assert!(inactive.modular_rules.is_empty());
+
+        let active = config
+            .with_active_profiles(config.active_profiles.clone())
+            .unwrap();
+        let ids: Vec<&str> = active.active_slices.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["hardening", "ops"]);
+        assert!(active.is_check_enabled("dockerfile_lint"));
+        assert!(active.is_check_enabled("ci_cd_parity"));
+        let docker = active.scanner_config.dockerfile_security.as_ref().unwrap();
+        assert!(!docker.require_pinned_digests);
+        let rule = active
+            .modular_rules
+            .iter()
+            .find(|r| r.name == "container-hardening")
+            .expect("slice rules become a modular rule");
+        assert_eq!(rule.rules.as_ref().unwrap()[0].name, "image_latest");
+    }
+
+    fn profile(extra_metadata: &str, checks: &str) -> Profile {
+        toml::from_str(&format!(
+            "[metadata]\nversion = \"1.0.0\"\nupdated = \"2025-01-01\"\ndescription = \"\"\n{}\n\
+             [activation]\n[enable]\ndomains = []\nplugins = []\n[checks]\n{}\n",
+            extra_metadata, checks
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_slice_precedence_and_missing_slices() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let rules_dir = dir.path().join("rules");
+        std::fs::create_dir_all(rules_dir.join("profiles")).unwrap();
+        std::fs::create_dir_all(rules_dir.join("slices")).unwrap();
+        let config_file = dir.path().join("config.toml");
+        std::fs::write(&config_file, "version = 2\n").unwrap();
+        std::fs::write(
+            rules_dir.join("profiles/service.toml"),
+            r#"
+[metadata]
+name = "service"
+version = "1.0.0"
+updated = "2025-01-01"
+description = "service"
+priority = 10
+
+[activation]
+indicators = ["Dockerfile"]
+
+[enable]
+domains = []
+plugins = []
+
+[checks]
+enable = ["ci_cd_parity"]
+
+[checks.slices]
+include = ["first", "missing", "second"]
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            rules_dir.join("profiles/baseline.toml"),
+            r#"
+[metadata]
+name = "baseline"
+version = "1.0.0"
+updated = "2025-01-01"
+description = "baseline"
+
+[activation]
+indicators = ["Dockerfile"]
+
+[enable]
+domains = []
+plugins = []
+
+[checks]
+enable = ["dockerfile_lint"]
+
+[checks.slices]
+include = ["third"]
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            rules_dir.join("slices/first.toml"),
+            r#"
+[checks]
+disable = ["dockerfile_lint", "ci_cd_parity"]
+
+[scanner_config.dockerfile_security]
+require_pinned_digests = false
+"#,
+        )
+        .unwrap();
+        for id in ["second", "third"] {
+            std::fs::write(
+                rules_dir.join(format!("slices/{}.toml", id)),
+                r#"
+[checks]
+enable = ["dockerfile_lint"]
+
+[scanner_config.dockerfile_security]
+require_pinned_digests = true
+"#,
+            )
+            .unwrap();
+        }
+
+        let config = Config::load_from_file(&config_file).unwrap();
+        let active = config
+            .with_active_profiles(config.active_profiles.clone())
+            .unwrap();
+
+        // The unknown slice is skipped; the others still apply, ranked after
+        // the profile that included them
+        let ids: Vec<&str> = active.active_slices.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["first", "second", "third"]);
+
+        // A profile outranks its slices, earlier slices outrank later ones,
+        // and a higher-priority profile's slices outrank lower profiles
+        assert!(active.is_check_enabled("ci_cd_parity"));
+        assert!(!active.is_check_enabled("dockerfile_lint"));
+        assert!(active.diagnostics.iter().any(|d| d.contains(
+            "slice 'first' disables check 'dockerfile_lint' but profile 'baseline' (priority 0) enables it"
+        )));
+
+        // Scanner fragments follow the same precedence
+        let docker = active.scanner_config.dockerfile_security.as_ref().unwrap();
+        assert!(!docker.require_pinned_digests);
+    }
+
+    #[test]
+    fn test_slice_scanner_config_ranks_below_user_config() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let rules_dir = dir.path().join("rules");
+        std::fs::create_dir_all(rules_dir.join("profiles")).unwrap();
+        std::fs::create_dir_all(rules_dir.join("slices")).unwrap();
+        let config_file = dir.path().join("config.toml");
+        std::fs::write(
+            &config_file,
+            r#"
+version = 2
+
+[scanner_config.dockerfile_security]
+require_pinned_digests = true
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            rules_dir.join("profiles/service.toml"),
+            r#"
+[metadata]
+name = "service"
+version = "1.0.0"
+updated = "2025-01-01"
+description = "service"
+
+[activation]
+
+[enable]
+domains = []
+plugins = []
+
+[checks.slices]
+include = ["relaxed"]
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            rules_dir.join("slices/relaxed.toml"),
+            r#"
+[scanner_config.dockerfile_security]
+require_pinned_digests = false
+forbid_copy_dot = false
+"#,
+        )
+        .unwrap();
+
+        let config = Config::load_from_file(&config_file).unwrap();
+        let active = config
+            .with_active_profiles(config.active_profiles.clone())
+            .unwrap();
+        // config.toml keeps its key; the slice fills in the one it left unset
+        let docker = active.scanner_config.dockerfile_security.as_ref().unwrap();
+        assert!(docker.require_pinned_digests);
+        assert!(!docker.forbid_copy_dot);
+
+        let overridden = config
+            .with_runtime_overrides(&[
+                "scanner_config.dockerfile_security.forbid_copy_dot=true".to_string()
+            ])
+            .unwrap();
+        let active = overridden
+            .with_active_profiles(overridden.active_profiles.clone())
+            .unwrap();
+        let docker = active.scanner_config.dockerfile_security.as_ref().unwrap();
+        assert!(docker.require_pinned_digests);
+        assert!(docker.forbid_copy_dot);
+    }
+
+    #[test]
+    fn test_profile_priority_conflicts_and_requires() {
+        let mut config = Config::default();
+        config.rules.mode = RulesMode::Denylist;
+        let profiles = vec![
+            profile(
+                "name = \"web\"\nconflicts_with = [\"cli\"]",
+                "enable = [\"file_naming\"]",
+            ),
+            profile(
+                "name = \"cli\"\npriority = 10",
+                "disable = [\"file_naming\"]",
+            ),
+            profile("name = \"react\"\nrequires = [\"web\"]", ""),
+            profile(
+                "name = \"strict\"\npriority = 5",
+                "enable = [\"file_naming\"]",
+            ),
+        ];
+
+        let resolved = config.with_active_profiles(profiles).unwrap();
+        let names: Vec<&str> = resolved
+            .active_profiles
+            .iter()
+            .map(|p| p.metadata.name.as_str())
+            .collect();
+        // `web` loses to higher-priority `cli`, which takes `react` with it
+        assert_eq!(names, vec!["cli", "strict"]);
+        assert!(!resolved.is_check_enabled("file_naming"));
+        assert_eq!(resolved.diagnostics.len(), 3);
+        assert!(resolved.diagnostics[0].contains("Profile 'web' (priority 0) conflicts with 'cli'"));
+        assert!(
+            resolved.diagnostics[1].contains("Profile 'react' requires inactive profile(s) web")
+        );
+        assert!(resolved.diagnostics[2].contains("check 'file_naming'"));
+        assert!(resolved.diagnostics[2].ends_with("profile 'cli' wins"));
+    }
 }
@@ -0,0 +1,200 @@
+//! Content matching for custom rules: the literal `content_pattern` plus any
+//! `content_regex` patterns, combined with `any`/`all`. Matchers are compiled
+//! once per distinct rule and shared between lint runs and hook events.
+
+use crate::config::{ContentMatchMode, CustomRule};
+use crate::utils::{CompiledCache, Result};
+use regex::{Regex, RegexBuilder};
+use std::sync::Arc;
+
+enum ContentPattern {
+    Literal(String),
+    Regex(Regex),
+}
+
+pub struct ContentMatcher {
+    patterns: Vec<ContentPattern>,
+    mode: ContentMatchMode,
+}
+
+/// Where the content matched and what the regex captured.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentMatch {
+    /// 1-based line of the first match.
+    pub line: usize,
+    /// 1-based column (in characters) of the first match.
+    pub column: usize,
+    captures: Vec<(String, String)>,
+}
+
+impl ContentMatcher {
+    /// The compiled matcher for a rule, or `None` when the rule has no
+    /// content patterns. Compiled matchers, and compile errors, are cached
+    /// for the process.
+    pub fn for_rule(rule: &CustomRule) -> Result<Option<Arc<ContentMatcher>>> {
+        if rule.content_pattern.is_none() && rule.content_regex.is_empty() {
+            return Ok(None);
+        }
+
+        static CACHE: CompiledCache<std::result::Result<Arc<ContentMatcher>, String>> =
+            CompiledCache::new();
+        let key = format!(
+            "{:?}|{:?}|{:?}|{:?}",
+            rule.content_pattern, rule.content_regex, rule.content_flags, rule.content_match
+        );
+        let compiled = CACHE.get_or_compile(&key, || {
+            Self::new(
+                rule.content_pattern.as_deref(),
+                &rule.content_regex,
+                rule.content_flags.as_deref().unwrap_or(""),
+                rule.content_match,
+            )
+            .map(Arc::new)
+            .map_err(|e| e.to_string())
+        });
+        match compiled {
+            Ok(matcher) => Ok(Some(matcher)),
+            Err(e) => Err(anyhow::anyhow!("{}", e)),
+        }
+    }
+
+    pub fn new(
+        literal: Option<&str>,
+        regexes: &[String],
+        flags: &str,
+        mode: ContentMatchMode,
+    ) -> Result<Self> {
+        let mut patterns: Vec<ContentPattern> = literal
+            .map(|l| ContentPattern::Literal(l.to_string()))
+            .into_iter()
+            .collect();
+        for source in regexes {
+            let mut builder = RegexBuilder::new(source);
+            for flag in flags.chars() {
+                match flag {
+                    'i' => builder.case_insensitive(true),
+                    'm' => builder.multi_line(true),
+                    's' => builder.dot_matches_new_line(true),
+                    'x' => builder.ignore_whitespace(true),
+                    'U' => builder.swap_greed(true),
+                    other => anyhow::bail!("unknown content_flags flag '{}'", other),
+                };
+            }
+            let regex = builder
+                .build()
+                .map_err(|e| anyhow::anyhow!("invalid content_regex '{}': {}", source, e))?;
+            patterns.push(ContentPattern::Regex(regex));
+        }
+        Ok(Self { patterns, mode })
+    }
+
+    /// The earliest match in `content`. With `all`, every pattern must match
+    /// somewhere; the captures of all regexes are then available.
+    pub fn find(&self, content: &str) -> Option<ContentMatch> {
+        let mut first: Option<usize> = None;
+        let mut captures = Vec::new();
+        for pattern in &self.patterns {
+            let found = match pattern {
+                ContentPattern::Literal(literal) => content.find(literal.as_str()),
+                ContentPattern::Regex(regex) => regex.captures(content).map(|caps| {
+                    let whole = caps.get(0).map(|m| m.start()).unwrap_or(0);
+                    for (index, name) in regex.capture_names().enumerate() {
+                        let Some(value) = caps.get(index) else {
+                            continue;
+                        };
+                        let key = name.map(str::to_string).unwrap_or(index.to_string());
+                        if !captures.iter().any(|(k, _)| *k == key) {
+                            captures.push((key, value.as_str().to_string()));
+                        }
+                    }
+                    whole
+                }),
+            };
+            match (found, self.mode) {
+                (Some(start), _) => first = Some(first.map_or(start, |f| f.min(start))),
+                (None, ContentMatchMode::All) => return None,
+                (None, ContentMatchMode::Any) => {}
+            }
+        }
+
+        let offset = first?;
+        let before = &content[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Some(ContentMatch {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            captures,
+        })
+    }
+}
+
+impl ContentMatch {
+    /// Replace `{0}`, `{1}`, ... and `{name}` capture placeholders, plus
+    /// `{line}` and `{column}`, in a rule message.
+    pub fn interpolate(&self, template: &str) -> String {
+        let mut message = template
+            .replace("{line}", &self.line.to_string())
+            .replace("{column}", &self.column.to_string());
+        for (key, value) in &self.captures {
+            message = message.replace(&format!("{{{}}}", key), value);
+        }
+        message
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_any_and_all_with_captures() -> Result<()> {
+        let content = "fn main() {\n    let key = \"AKIA1234\";\n    todo!()\n}\n";
+        let any = ContentMatcher::new(
+            Some("todo!"),
+            &[r#"let (?P<var>\w+) = "(AKIA\d+)""#.to_string()],
+            "",
+            ContentMatchMode::Any,
+        )?;
+        let found = any.find(content).unwrap();
+        assert_eq!((found.line, found.column), (2, 5));
+        assert_eq!(
+            found.interpolate("{var} holds {2} at {line}:{column}"),
+            "key holds AKIA1234 at 2:5"
+        );
+
+        let all = ContentMatcher::new(
+            None,
+            &["TODO!".to_string(), "unsafe".to_string()],
+            "i",
+            ContentMatchMode::All,
+        )?;
+        assert!(all.find(content).is_none());
+        assert!(all.find("unsafe {}\n// todo!").is_some());
+
+        assert!(ContentMatcher::new(None, &["(".to_string()], "", ContentMatchMode::Any).is_err());
+        assert!(ContentMatcher::new(None, &["a".to_string()], "q", ContentMatchMode::Any).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn for_rule_caches_compile_errors() -> Result<()> {
+        let rule: CustomRule = toml::from_str(
+            r#"
+name = "bad-regex"
+pattern = "*"
+message = "m"
+severity = "error"
+content_regex = "(unclosed"
+"#,
+        )?;
+        let first = ContentMatcher::for_rule(&rule).err().unwrap().to_string();
+        let second = ContentMatcher::for_rule(&rule).err().unwrap().to_string();
+        assert!(
+            first.contains("invalid content_regex '(unclosed'"),
+            "{}",
+            first
+        );
+        assert_eq!(first, second);
+        Ok(())
+    }
+}
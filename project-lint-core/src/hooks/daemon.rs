@@ -0,0 +1,599 @@
+//! `project-lint daemon`: one long-running process that keeps configuration
+//! loaded (and compiled content matchers cached) and evaluates hook events
+//! sent over a Unix domain socket, so a hook does not pay a full config load
+//! per tool call.
+//!
+//! The protocol is JSON-RPC 2.0 style with one JSON object per line:
+//!
+//! - `ping`: daemon version and pid.
+//! - `evaluate` (`event`, `context`): the whole event, as
+//!   [`dispatch::evaluate`] would answer it in-process.
+//! - `evaluate_rules` (`event`, `context`, `modes`): only the triggered
+//!   rules of the given execution modes.
+//! - `shutdown`: stop listening.
+//!
+//! A cached configuration is reloaded when a file in its config directory
+//! changes.
+
+use crate::config::{Config, ExecutionMode};
+use crate::hooks::dispatch::{self, Evaluation, HookContext};
+use crate::hooks::engine::DetectedIssue;
+use crate::hooks::session::SessionState;
+use crate::hooks::{ProjectLintEvent, RuleEngine};
+use crate::utils::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tracing::{debug, info};
+use walkdir::WalkDir;
+
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const EVALUATION_FAILED: i64 = -32000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcRequest {
+    pub jsonrpc: String,
+    pub id: Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcResponse {
+    pub jsonrpc: String,
+    pub id: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, error: impl std::fmt::Display) -> Self {
+        Self {
+            code,
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Parameters of `evaluate` and `evaluate_rules`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvaluateParams {
+    pub event: ProjectLintEvent,
+    pub context: HookContext,
+    #[serde(default)]
+    pub modes: Vec<ExecutionMode>,
+    /// The session state `evaluate_rules` sees, as the caller read it. The
+    /// caller may hold the session's lock, so the daemon must not read the
+    /// store itself.
+    #[serde(default)]
+    pub session: Option<SessionState>,
+}
+
+/// Synchronous client for hooks. Each call uses its own connection and
+/// fails once `timeout` passes without an answer.
+pub struct DaemonClient {
+    socket: PathBuf,
+    timeout: Duration,
+}
+
+impl DaemonClient {
+    pub fn new(socket: PathBuf, timeout: Duration) -> Self {
+        Self { socket, timeout }
+    }
+
+    /// `$XDG_RUNTIME_DIR/project-lint/daemon.sock`, or the cache directory
+    /// where there is no runtime directory. The shared temp directory is the
+    /// last resort, in a directory of the user's own.
+    pub fn default_socket() -> PathBuf {
+        let dir = match dirs::runtime_dir().or_else(dirs::cache_dir) {
+            Some(dir) => dir.join("project-lint"),
+            None => std::env::temp_dir().join(format!("project-lint-{}", current_uid())),
+        };
+        dir.join("daemon.sock")
+    }
+
+    pub fn ping(&self) -> Result<Value> {
+        self.call("ping", Value::Null)
+    }
+
+    pub fn shutdown(&self) -> Result<()> {
+        self.call("shutdown", Value::Null).map(|_| ())
+    }
+
+    pub fn evaluate(&self, event: &ProjectLintEvent, context: &HookContext) -> Result<Evaluation> {
+        let params = EvaluateParams {
+            event: event.clone(),
+            context: context.clone(),
+            modes: Vec::new(),
+            session: None,
+        };
+        Ok(serde_json::from_value(
+            self.call("evaluate", serde_json::to_value(params)?)?,
+        )?)
+    }
+
+    pub fn evaluate_rules(
+        &self,
+        event: &ProjectLintEvent,
+        context: &HookContext,
+        session: &SessionState,
+        modes: &[ExecutionMode],
+    ) -> Result<Vec<DetectedIssue>> {
+        let params = EvaluateParams {
+            event: event.clone(),
+            context: context.clone(),
+            modes: modes.to_vec(),
+            session: Some(session.clone()),
+        };
+        Ok(serde_json::from_value(
+            self.call("evaluate_rules", serde_json::to_value(params)?)?,
+        )?)
+    }
+
+    #[cfg(unix)]
+    fn call(&self, method: &str, params: Value) -> Result<Value> {
+        use std::io::{BufRead, BufReader, Write};
+        use std::os::unix::net::UnixStream;
+
+        let mut stream = UnixStream::connect(&self.socket)?;
+        stream.set_read_timeout(Some(self.timeout))?;
+        stream.set_write_timeout(Some(self.timeout))?;
+        let request = RpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: json!(1),
+            method: method.to_string(),
+            params,
+        };
+        let mut line = serde_json::to_vec(&request)?;
+        line.push(b'\n');
+        stream.write_all(&line)?;
+
+        let mut answer = String::new();
+        BufReader::new(stream).read_line(&mut answer)?;
+        let response: RpcResponse = serde_json::from_str(&answer)?;
+        if let Some(error) = response.error {
+            anyhow::bail!("Daemon error {}: {}", error.code, error.message);
+        }
+        Ok(response.result.unwrap_or(Value::Null))
+    }
+
+    #[cfg(not(unix))]
+    fn call(&self, _method: &str, _params: Value) -> Result<Value> {
+        anyhow::bail!("The project-lint daemon needs Unix domain sockets")
+    }
+}
+
+/// Listen on `socket` until a `shutdown` request. A stale socket file is
+/// replaced; a socket another daemon still answers on is an error.
+#[cfg(unix)]
+pub async fn serve(socket: &Path) -> Result<()> {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::UnixListener;
+
+    let dir = match socket.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    if !dir.exists() {
+        std::fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(dir)?;
+    }
+    check_socket_dir(dir)?;
+    if socket.exists() {
+        if std::os::unix::net::UnixStream::connect(socket).is_ok() {
+            anyhow::bail!("A daemon is already listening on {}", socket.display());
+        }
+        std::fs::remove_file(socket)?;
+    }
+    // Owner-only from the start: no window between bind and chmod where
+    // another user could connect
+    // SAFETY: umask only swaps the process file mode mask
+    let umask = unsafe { libc::umask(0o177) };
+    let bound = UnixListener::bind(socket);
+    // SAFETY: as above, restoring the previous mask
+    unsafe { libc::umask(umask) };
+    let listener = bound?;
+    std::fs::set_permissions(socket, std::fs::Permissions::from_mode(0o600))?;
+    info!("project-lint daemon listening on {}", socket.display());
+
+    let daemon = Arc::new(Daemon::default());
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, _) = accepted?;
+                let daemon = Arc::clone(&daemon);
+                tokio::spawn(async move {
+                    let (reader, mut writer) = stream.into_split();
+                    let mut lines = BufReader::new(reader).lines();
+                    while let Ok(Some(line)) = lines.next_line().await {
+                        if line.trim().is_empty() {
+                            continue;
+                        }
+                        let handler = Arc::clone(&daemon);
+                        let Ok(response) =
+                            tokio::task::spawn_blocking(move || handler.respond(&line)).await
+                        else {
+                            break;
+                        };
+                        let Ok(mut answer) = serde_json::to_vec(&response) else {
+                            break;
+                        };
+                        answer.push(b'\n');
+                        if writer.write_all(&answer).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+            _ = daemon.shutdown.notified() => break,
+        }
+    }
+
+    let _ = std::fs::remove_file(socket);
+    info!("project-lint daemon stopped");
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub async fn serve(_socket: &Path) -> Result<()> {
+    anyhow::bail!("The project-lint daemon needs Unix domain sockets")
+}
+
+/// The socket's directory must belong to the user and be closed to others,
+/// or another local user could swap the socket (a shared temp directory).
+#[cfg(unix)]
+fn check_socket_dir(dir: &Path) -> Result<()> {
+    use std::os::unix::fs::MetadataExt;
+
+    let metadata = std::fs::metadata(dir)?;
+    if metadata.uid() != current_uid() {
+        anyhow::bail!(
+            "Refusing socket directory {}: owned by another user",
+            dir.display()
+        );
+    }
+    if metadata.mode() & 0o022 != 0 {
+        anyhow::bail!(
+            "Refusing socket directory {}: writable by other users",
+            dir.display()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn current_uid() -> u32 {
+    // SAFETY: getuid cannot fail
+    unsafe { libc::getuid() }
+}
+
+#[cfg(not(unix))]
+fn current_uid() -> u32 {
+    0
+}
+
+/// Base configurations by config file, `--set` overrides and the hook's
+/// environment overrides, with the fingerprint of the config directory they
+/// were loaded from.
+type ConfigCache = HashMap<ConfigKey, (u64, Config)>;
+type ConfigKey = (PathBuf, Vec<String>, Vec<(String, String)>);
+
+#[derive(Default)]
+struct Daemon {
+    configs: Mutex<ConfigCache>,
+    shutdown: Notify,
+}
+
+impl Daemon {
+    fn respond(&self, line: &str) -> RpcResponse {
+        let (id, outcome) = match serde_json::from_str::<RpcRequest>(line) {
+            Ok(request) => (request.id.clone(), self.dispatch(request)),
+            Err(e) => (Value::Null, Err(RpcError::new(PARSE_ERROR, e))),
+        };
+        let (result, error) = match outcome {
+            Ok(result) => (Some(result), None),
+            Err(error) => (None, Some(error)),
+        };
+        RpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id,
+            result,
+            error,
+        }
+    }
+
+    fn dispatch(&self, request: RpcRequest) -> std::result::Result<Value, RpcError> {
+        match request.method.as_str() {
+            "ping" => Ok(json!({
+                "version": env!("CARGO_PKG_VERSION"),
+                "pid": std::process::id(),
+            })),
+            "shutdown" => {
+                self.shutdown.notify_one();
+                Ok(Value::Null)
+            }
+            "evaluate" | "evaluate_rules" => {
+                let mut params: EvaluateParams = serde_json::from_value(request.params)
+                    .map_err(|e| RpcError::new(INVALID_PARAMS, e))?;
+                // The engine falls back to the working directory for paths
+                params
+                    .event
+                    .cwd
+                    .get_or_insert_with(|| params.context.cwd.clone());
+                self.evaluate(&request.method, &params)
+                    .map_err(|e| RpcError::new(EVALUATION_FAILED, e))
+            }
+            other => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Unknown method '{}'", other),
+            )),
+        }
+    }
+
+    fn evaluate(&self, method: &str, params: &EvaluateParams) -> Result<Value> {
+        let base = self.base_config(&params.context)?;
+        let config = params.context.resolve_config(&base, &params.event)?;
+        if method == "evaluate_rules" {
+            let session = match &params.session {
+                Some(session) => session.clone(),
+                None => params.context.session_state(&params.event),
+            };
+            let issues = RuleEngine::new(&config)
+                .with_session(&session)
+                .evaluate_rules(&params.event, |mode| params.modes.contains(mode))?;
+            Ok(serde_json::to_value(issues)?)
+        } else {
+            let evaluation = dispatch::evaluate(&config, &params.context, &params.event, None)?;
+            Ok(serde_json::to_value(evaluation)?)
+        }
+    }
+
+    fn base_config(&self, context: &HookContext) -> Result<Config> {
+        let config_path = context.config_path()?;
+        let fingerprint = config_fingerprint(config_path.parent().unwrap_or(Path::new(".")));
+        let key = (
+            config_path,
+            context.overrides.clone(),
+            context.env_overrides.clone(),
+        );
+
+        let mut configs = self.configs.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some((cached, config)) = configs.get(&key) {
+            if *cached == fingerprint {
+                return Ok(config.clone());
+            }
+        }
+        debug!("Loading configuration {:?}", key.0);
+        let config = context.load_base_config()?;
+        configs.insert(key, (fingerprint, config.clone()));
+        Ok(config)
+    }
+}
+
+/// Hash of the paths, sizes and mtimes under a config directory (rules,
+/// profiles, slices and plugins sit at most three levels down).
+fn config_fingerprint(config_dir: &Path) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for entry in WalkDir::new(config_dir)
+        .max_depth(3)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        entry.path().hash(&mut hasher);
+        if let Ok(metadata) = entry.metadata() {
+            metadata.len().hash(&mut hasher);
+            metadata.modified().ok().hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::hooks::{EventContext, EventType};
+    use tempfile::TempDir;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn serves_rule_evaluation_and_reloads_changed_config() -> Result<()> {
+        let dir = TempDir::new()?;
+        let config_file = dir.path().join("config.toml");
+        let rule = |message: &str| {
+            format!(
+                r#"
+[[rules.custom_rules]]
+name = "remote-check"
+pattern = "*"
+message = "{}"
+severity = "error"
+triggers = ["pre_user_prompt"]
+mode = "remote_sync"
+"#,
+                message
+            )
+        };
+        std::fs::write(&config_file, rule("first"))?;
+
+        let socket = dir.path().join("daemon.sock");
+        let server = tokio::spawn({
+            let socket = socket.clone();
+            async move { serve(&socket).await }
+        });
+        while !socket.exists() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let client = DaemonClient::new(socket.clone(), Duration::from_secs(5));
+        let event = ProjectLintEvent {
+            event_type: EventType::PreUserPrompt,
+            session_id: None,
+            timestamp: None,
+            cwd: None,
+            context: EventContext {
+                user_prompt: Some("hi".to_string()),
+                ide_source: "claude".to_string(),
+                ..Default::default()
+            },
+        };
+        let context = HookContext {
+            cwd: dir.path().to_path_buf(),
+            project_path: PathBuf::from("."),
+            config_file: Some(PathBuf::from("config.toml")),
+            overrides: Vec::new(),
+            env_overrides: Vec::new(),
+            session_dir: None,
+        };
+
+        assert_eq!(client.ping()?["version"], env!("CARGO_PKG_VERSION"));
+        let issues = client.evaluate_rules(
+            &event,
+            &context,
+            &SessionState::default(),
+            &[ExecutionMode::RemoteSync],
+        )?;
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].message, "first");
+        assert!(client
+            .evaluate_rules(
+                &event,
+                &context,
+                &SessionState::default(),
+                &[ExecutionMode::LocalSync]
+            )?
+            .is_empty());
+
+        // A config edit is picked up without restarting the daemon
+        std::fs::write(&config_file, rule("second, longer"))?;
+        let evaluation = client.evaluate(&event, &context)?;
+        assert!(evaluation
+            .result
+            .message
+            .unwrap()
+            .contains("second, longer"));
+
+        client.shutdown()?;
+        server.await??;
+        assert!(!socket.exists());
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn applies_the_hook_environment_not_its_own() -> Result<()> {
+        let dir = TempDir::new()?;
+        std::fs::write(
+            dir.path().join("config.toml"),
+            r#"
+[[rules.custom_rules]]
+name = "remote-check"
+pattern = "*"
+message = "from the config file"
+severity = "error"
+triggers = ["pre_user_prompt"]
+mode = "remote_sync"
+"#,
+        )?;
+        let socket = dir.path().join("daemon.sock");
+        let server = tokio::spawn({
+            let socket = socket.clone();
+            async move { serve(&socket).await }
+        });
+        while !socket.exists() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let client = DaemonClient::new(socket, Duration::from_secs(5));
+        let event = ProjectLintEvent {
+            event_type: EventType::PreUserPrompt,
+            session_id: None,
+            timestamp: None,
+            cwd: None,
+            context: EventContext {
+                user_prompt: Some("hi".to_string()),
+                ide_source: "claude".to_string(),
+                ..Default::default()
+            },
+        };
+        let mut context = HookContext {
+            cwd: dir.path().to_path_buf(),
+            project_path: PathBuf::from("."),
+            config_file: Some(PathBuf::from("config.toml")),
+            overrides: Vec::new(),
+            env_overrides: Vec::new(),
+            session_dir: None,
+        };
+        let message = |context: &HookContext| -> Result<String> {
+            let issues = client.evaluate_rules(
+                &event,
+                context,
+                &SessionState::default(),
+                &[ExecutionMode::RemoteSync],
+            )?;
+            Ok(issues[0].message.clone())
+        };
+
+        // The daemon's own environment does not carry the hook's variable
+        let name = "PROJECT_LINT__RULES__CUSTOM_RULES";
+        assert!(std::env::var_os(name).is_none());
+        assert_eq!(message(&context)?, "from the config file");
+        context.env_overrides = vec![(
+            name.to_string(),
+            r#"[{ name = "remote-check", pattern = "*", message = "from the hook environment", severity = "error", triggers = ["pre_user_prompt"], mode = "remote_sync" }]"#
+                .to_string(),
+        )];
+        assert_eq!(message(&context)?, "from the hook environment");
+        context.env_overrides.clear();
+        assert_eq!(message(&context)?, "from the config file");
+
+        client.shutdown()?;
+        server.await??;
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn creates_a_private_socket_dir_and_refuses_shared_ones() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new()?;
+        let shared = dir.path().join("shared");
+        std::fs::create_dir(&shared)?;
+        std::fs::set_permissions(&shared, std::fs::Permissions::from_mode(0o1777))?;
+        let error = serve(&shared.join("daemon.sock")).await.unwrap_err();
+        assert!(error.to_string().contains("writable by other users"));
+
+        let private = dir.path().join("run/project-lint");
+        let server = tokio::spawn({
+            let socket = private.join("daemon.sock");
+            async move { serve(&socket).await }
+        });
+        while !private.join("daemon.sock").exists() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let mode = |path: &Path| std::fs::metadata(path).map(|m| m.permissions().mode() & 0o777);
+        assert_eq!(mode(&private)?, 0o700);
+        assert_eq!(mode(&private.join("daemon.sock"))?, 0o600);
+
+        DaemonClient::new(private.join("daemon.sock"), Duration::from_secs(5)).shutdown()?;
+        server.await??;
+        Ok(())
+    }
+}
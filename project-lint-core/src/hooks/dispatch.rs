@@ -0,0 +1,367 @@
+//! Evaluates a mapped hook event for a project: resolves its configuration
+//! and decides where each rule runs. Sync rules are answered with the event,
+//! async rules of a session run detached, and remote rules go to the daemon
+//! when one is reachable. Rules see the state of the event's session, and
+//! the event is recorded in it once answered, under one lock of the session.
+//! Shared by `project-lint hook` and the daemon.
+
+use crate::config::{Config, ExecutionMode};
+use crate::hooks::daemon::DaemonClient;
+use crate::hooks::engine::DetectedIssue;
+use crate::hooks::session::{SessionState, SessionStore};
+use crate::hooks::{EventType, HookResult, ProjectLintEvent, RuleEngine};
+use crate::profiles::ActivationCache;
+use crate::utils::{config_dir_for, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Where and how a hook was invoked. Relative paths resolve against `cwd`,
+/// so the daemon can serve hooks started from any directory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HookContext {
+    pub cwd: PathBuf,
+    pub project_path: PathBuf,
+    pub config_file: Option<PathBuf>,
+    /// `--set` assignments.
+    #[serde(default)]
+    pub overrides: Vec<String>,
+    /// The hook's `PROJECT_LINT__*` variables, sorted by name. The daemon
+    /// applies these rather than its own environment.
+    #[serde(default)]
+    pub env_overrides: Vec<(String, String)>,
+    /// Where session state is kept; without it rules see an empty state.
+    #[serde(default)]
+    pub session_dir: Option<PathBuf>,
+}
+
+/// Sessions that ended without a `SessionEnd` event are dropped after this.
+const STALE_SESSION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+impl HookContext {
+    pub fn project_root(&self) -> PathBuf {
+        self.cwd.join(&self.project_path)
+    }
+
+    /// The `config.toml` the hook loads: `--config-file`, or the one
+    /// discovered from `cwd`.
+    pub fn config_path(&self) -> Result<PathBuf> {
+        match &self.config_file {
+            Some(config_file) => Ok(self.cwd.join(config_file)),
+            None => Ok(config_dir_for(&self.cwd)?.join("config.toml")),
+        }
+    }
+
+    /// Configuration with runtime overrides, before it is narrowed to an event.
+    pub fn load_base_config(&self) -> Result<Config> {
+        Config::load_from_file(&self.config_path()?)?
+            .with_override_sources(&self.env_overrides, &self.overrides)
+    }
+
+    /// Narrow `base` to the event: nested configs of the file it touches,
+    /// then the profiles active for it.
+    pub fn resolve_config(&self, base: &Config, event: &ProjectLintEvent) -> Result<Config> {
+        let project_root = self.project_root();
+        let config = match &event.context.file_path {
+            Some(file_path) => base.for_path(&project_root, file_path)?,
+            None => base.clone(),
+        };
+
+        // Active profiles are cached on the tree's mtimes
+        let active_profiles = ActivationCache::new(ActivationCache::default_dir())
+            .active_profiles(&project_root, &config.active_profiles, Some(event))?;
+        let config = config.with_active_profiles(active_profiles)?;
+        for diagnostic in &config.diagnostics {
+            warn!("{}", diagnostic);
+        }
+        Ok(config)
+    }
+
+    pub fn load_config(&self, event: &ProjectLintEvent) -> Result<Config> {
+        self.resolve_config(&self.load_base_config()?, event)
+    }
+
+    /// The state of the event's session so far.
+    pub fn session_state(&self, event: &ProjectLintEvent) -> SessionState {
+        let (Some(dir), Some(session_id)) = (&self.session_dir, &event.session_id) else {
+            return SessionState::default();
+        };
+        SessionStore::new(dir.clone())
+            .state(session_id)
+            .unwrap_or_else(|e| {
+                warn!("Failed to read session state: {}", e);
+                SessionState::default()
+            })
+    }
+
+    /// Answer an event against the state of its session and record it
+    /// there. The session stays locked from the read to the write, so
+    /// concurrent hooks of a session each see the events answered before
+    /// them. Ending a session drops it instead.
+    fn answer_in_session(
+        &self,
+        config: &Config,
+        event: &ProjectLintEvent,
+        answer: impl FnOnce(&SessionState) -> Result<Evaluation>,
+    ) -> Result<Evaluation> {
+        let (Some(dir), Some(session_id)) = (&self.session_dir, &event.session_id) else {
+            return answer(&SessionState::default());
+        };
+        let store = SessionStore::new(dir.clone());
+        match event.event_type {
+            EventType::SessionEnd => {
+                let evaluation = answer(&self.session_state(event));
+                if let Err(e) = store.end(session_id) {
+                    warn!("Failed to end session: {}", e);
+                }
+                evaluation
+            }
+            EventType::SessionStart => {
+                if let Err(e) = store.prune(STALE_SESSION) {
+                    warn!("Failed to prune sessions: {}", e);
+                }
+                answer(&self.session_state(event))
+            }
+            _ => {
+                let mut answer = Some(answer);
+                let mut answered = None;
+                let updated = store.update_state(session_id, |state| {
+                    let evaluation = answer.take().expect("answered once")(state);
+                    if let Ok(evaluation) = &evaluation {
+                        let test_commands = &config.rules.session.test_commands;
+                        state.record(event, &evaluation.result.decision, test_commands);
+                    }
+                    answered = Some(evaluation);
+                });
+                if let Err(e) = updated {
+                    warn!("Failed to update session state: {}", e);
+                }
+                match (answered, answer) {
+                    (Some(evaluation), _) => evaluation,
+                    // The store failed before the state was read
+                    (None, Some(answer)) => answer(&SessionState::default()),
+                    (None, None) => unreachable!("an answer is either given or still pending"),
+                }
+            }
+        }
+    }
+}
+
+/// The answer to an event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Evaluation {
+    pub result: HookResult,
+    /// The event triggered rules that run detached; the hook starts them.
+    pub detached_rules: bool,
+}
+
+/// Evaluate an event. Remote rules answered with the event are sent to
+/// `daemon` when given, and evaluated in-process if it does not answer.
+pub fn evaluate(
+    config: &Config,
+    context: &HookContext,
+    event: &ProjectLintEvent,
+    daemon: Option<&DaemonClient>,
+) -> Result<Evaluation> {
+    let detached = |mode: &ExecutionMode| RuleEngine::runs_detached(mode, event);
+    let remote = |mode: &ExecutionMode| mode.is_remote() && !detached(mode);
+
+    context.answer_in_session(config, event, |session| {
+        let engine = RuleEngine::new(config).with_session(session);
+        let from_daemon = match daemon {
+            Some(daemon) if engine.triggers(event, remote)? => {
+                let modes: Vec<ExecutionMode> =
+                    [ExecutionMode::RemoteSync, ExecutionMode::RemoteAsync]
+                        .into_iter()
+                        .filter(|mode| remote(mode))
+                        .collect();
+                daemon
+                    .evaluate_rules(event, context, session, &modes)
+                    .map_err(|e| {
+                        debug!(
+                            "Daemon unavailable, evaluating remote rules in-process: {}",
+                            e
+                        )
+                    })
+                    .ok()
+            }
+            _ => None,
+        };
+        let result = match from_daemon {
+            Some(issues) => {
+                engine.evaluate_event_with(event, |mode| detached(mode) || remote(mode), issues)?
+            }
+            None => engine.evaluate_event(event)?,
+        };
+
+        Ok(Evaluation {
+            result,
+            detached_rules: engine.triggers(event, detached)?,
+        })
+    })
+}
+
+/// Evaluate the rules that run detached. `RemoteAsync` rules are sent to
+/// `daemon` when given, and evaluated in-process if it does not answer.
+pub fn evaluate_detached(
+    config: &Config,
+    context: &HookContext,
+    event: &ProjectLintEvent,
+    daemon: Option<&DaemonClient>,
+) -> Result<Vec<DetectedIssue>> {
+    let session = context.session_state(event);
+    let engine = RuleEngine::new(config).with_session(&session);
+    let remote = |mode: &ExecutionMode| *mode == ExecutionMode::RemoteAsync;
+    if let Some(daemon) = daemon {
+        if engine.triggers(event, remote)? {
+            match daemon.evaluate_rules(event, context, &session, &[ExecutionMode::RemoteAsync]) {
+                Ok(from_daemon) => {
+                    let mut issues =
+                        engine.evaluate_rules(event, |mode| *mode == ExecutionMode::LocalAsync)?;
+                    issues.extend(from_daemon);
+                    return Ok(issues);
+                }
+                Err(e) => debug!(
+                    "Daemon unavailable, evaluating remote rules in-process: {}",
+                    e
+                ),
+            }
+        }
+    }
+    engine.evaluate_rules(event, ExecutionMode::is_async)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hooks::{Decision, EventContext, FileEdit};
+    use tempfile::TempDir;
+
+    #[test]
+    fn rules_see_the_session_state() -> Result<()> {
+        let dir = TempDir::new()?;
+        let mut config = Config::default();
+        for rule in [
+            r#"
+name = "read-before-edit"
+pattern = "*"
+message = "Read the file before editing it"
+severity = "error"
+triggers = ["pre_tool_use"]
+when = 'tool_name == "Edit" && file_path not in session["/files_read"]'
+"#,
+            r#"
+name = "test-before-stop"
+pattern = "*"
+message = "Run the tests before finishing"
+severity = "error"
+triggers = ["stop"]
+when = 'session["/untested_writes"] > 0'
+"#,
+        ] {
+            config.rules.custom_rules.push(toml::from_str(rule)?);
+        }
+        let context = HookContext {
+            cwd: dir.path().to_path_buf(),
+            project_path: PathBuf::from("."),
+            config_file: None,
+            overrides: Vec::new(),
+            env_overrides: Vec::new(),
+            session_dir: Some(dir.path().join("sessions")),
+        };
+        let event = |event_type: EventType, tool_name: &str, file: &str| ProjectLintEvent {
+            event_type,
+            session_id: Some("s-1".to_string()),
+            timestamp: None,
+            cwd: Some(dir.path().to_path_buf()),
+            context: EventContext {
+                tool_name: Some(tool_name.to_string()),
+                file_path: Some(PathBuf::from(file)).filter(|_| !file.is_empty()),
+                edits: (tool_name == "Edit").then(|| {
+                    vec![FileEdit {
+                        old_string: None,
+                        new_string: "fn a() {}".to_string(),
+                        start_line: None,
+                        end_line: None,
+                    }]
+                }),
+                command: (tool_name == "Bash").then(|| "cargo test".to_string()),
+                ide_source: "claude".to_string(),
+                ..Default::default()
+            },
+        };
+        let decision = |event: &ProjectLintEvent| -> Result<Decision> {
+            Ok(evaluate(&config, &context, event, None)?.result.decision)
+        };
+
+        let edit = event(EventType::PreToolUse, "Edit", "src/a.rs");
+        let stop = event(EventType::Stop, "", "");
+        assert_eq!(decision(&edit)?, Decision::Deny);
+        assert_eq!(decision(&stop)?, Decision::Allow);
+        decision(&event(EventType::PreToolUse, "Read", "src/a.rs"))?;
+        assert_eq!(decision(&edit)?, Decision::Allow);
+        assert_eq!(decision(&stop)?, Decision::Deny);
+        decision(&event(EventType::PreToolUse, "Bash", ""))?;
+        assert_eq!(decision(&stop)?, Decision::Allow);
+
+        let state = context.session_state(&edit);
+        assert_eq!((state.denied_writes, state.test_runs), (1, 1));
+        decision(&event(EventType::SessionEnd, "", ""))?;
+        assert_eq!(context.session_state(&edit), SessionState::default());
+        Ok(())
+    }
+
+    #[test]
+    fn concurrent_events_of_a_session_see_each_other() -> Result<()> {
+        let dir = TempDir::new()?;
+        let mut config = Config::default();
+        config.rules.custom_rules.push(toml::from_str(
+            r#"
+name = "first-event"
+pattern = "*"
+message = "Only the first event of a session is denied"
+severity = "error"
+triggers = ["pre_tool_use"]
+when = 'session["/events"] == 0'
+"#,
+        )?);
+        let context = HookContext {
+            cwd: dir.path().to_path_buf(),
+            project_path: PathBuf::from("."),
+            config_file: None,
+            overrides: Vec::new(),
+            env_overrides: Vec::new(),
+            session_dir: Some(dir.path().join("sessions")),
+        };
+        let event = ProjectLintEvent {
+            event_type: EventType::PreToolUse,
+            session_id: Some("s-1".to_string()),
+            timestamp: None,
+            cwd: Some(dir.path().to_path_buf()),
+            context: EventContext {
+                tool_name: Some("Read".to_string()),
+                file_path: Some(PathBuf::from("src/a.rs")),
+                ide_source: "claude".to_string(),
+                ..Default::default()
+            },
+        };
+
+        let decisions: Vec<Decision> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| scope.spawn(|| evaluate(&config, &context, &event, None)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| h.join().unwrap().map(|e| e.result.decision))
+                .collect::<Result<_>>()
+        })?;
+        // Each event reads the state after the previous one was recorded
+        assert_eq!(
+            decisions.iter().filter(|d| **d == Decision::Deny).count(),
+            1
+        );
+        assert_eq!(context.session_state(&event).events, 8);
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+//! Command policy: each command of a shell command line is checked against
+//! `[[rules.command_rules]]`, then the `[commands]` rules of the active
+//! profiles, then their allowlists. The first matching rule decides a command.
+
+use super::DetectedIssue;
+use crate::config::{CommandAction, CommandRule, Config, RuleSeverity};
+use crate::hooks::shell::{parse_command_line, ShellCommand};
+use crate::hooks::Decision;
+
+pub(crate) fn evaluate_command(config: &Config, command_line: &str) -> Vec<DetectedIssue> {
+    let rules: Vec<&CommandRule> = config
+        .rules
+        .command_rules
+        .iter()
+        .chain(
+            config
+                .active_profiles
+                .iter()
+                .filter_map(|p| p.commands.as_ref())
+                .flat_map(|c| c.rules.iter()),
+        )
+        .collect();
+    let allowlists: Vec<(&str, &Vec<String>)> = config
+        .active_profiles
+        .iter()
+        .filter_map(|p| {
+            let allowlist = p.commands.as_ref()?.allowlist.as_ref()?;
+            Some((p.metadata.name.as_str(), allowlist))
+        })
+        .collect();
+
+    let mut issues: Vec<DetectedIssue> = Vec::new();
+    for command in parse_command_line(command_line) {
+        let issue = match rules.iter().find(|rule| rule_matches(rule, &command)) {
+            Some(rule) => {
+                let (severity, decision) = match rule.action {
+                    CommandAction::Allow => continue,
+                    CommandAction::Ask => (RuleSeverity::Warning, Decision::Ask),
+                    CommandAction::Deny => (RuleSeverity::Error, Decision::Deny),
+                };
+                let message = if rule.message.is_empty() {
+                    format!("`{}` is not allowed", display(&command))
+                } else {
+                    format!("{} (`{}`)", rule.message, display(&command))
+                };
+                DetectedIssue {
+                    name: rule.name.clone(),
+                    message,
+                    severity,
+                    decision: Some(decision),
+                }
+            }
+            None if !allowlists.is_empty()
+                && !allowlists
+                    .iter()
+                    .any(|(_, list)| list.iter().any(|p| glob_matches(p, &command.program))) =>
+            {
+                let profiles: Vec<&str> = allowlists.iter().map(|(name, _)| *name).collect();
+                DetectedIssue {
+                    name: "command-allowlist".to_string(),
+                    message: format!(
+                        "'{}' is not in the command allowlist of profile(s) {}",
+                        command.program,
+                        profiles.join(", ")
+                    ),
+                    severity: RuleSeverity::Error,
+                    decision: Some(Decision::Deny),
+                }
+            }
+            None => continue,
+        };
+        if !issues
+            .iter()
+            .any(|i| i.name == issue.name && i.message == issue.message)
+        {
+            issues.push(issue);
+        }
+    }
+    issues
+}
+
+fn rule_matches(rule: &CommandRule, command: &ShellCommand) -> bool {
+    rule.program
+        .iter()
+        .any(|p| glob_matches(p, &command.program))
+        && rule
+            .args
+            .iter()
+            .all(|pattern| command.args.iter().any(|arg| arg_matches(pattern, arg)))
+        && (rule.piped_from.is_empty()
+            || command
+                .piped_from
+                .as_deref()
+                .is_some_and(|from| rule.piped_from.iter().any(|p| glob_matches(p, from))))
+}
+
+/// Glob match, where a single short flag (`-f`) also matches a flag cluster
+/// containing it (`-rf`).
+fn arg_matches(pattern: &str, arg: &str) -> bool {
+    if let Some(flag) = pattern.strip_prefix('-').filter(|f| f.len() == 1) {
+        if arg.starts_with('-') && !arg.starts_with("--") && arg[1..].contains(flag) {
+            return true;
+        }
+    }
+    glob_matches(pattern, arg)
+}
+
+fn glob_matches(pattern: &str, value: &str) -> bool {
+    glob::Pattern::new(pattern)
+        .map(|p| p.matches(value))
+        .unwrap_or(pattern == value)
+}
+
+fn display(command: &ShellCommand) -> String {
+    std::iter::once(command.program.as_str())
+        .chain(command.args.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
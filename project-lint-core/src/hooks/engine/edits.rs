@@ -0,0 +1,150 @@
+//! Reconstructs what a write event is about to put on disk, so content rules
+//! can look at the lines an edit introduces instead of the whole file.
+
+use crate::hooks::FileEdit;
+use std::collections::HashMap;
+use std::ops::Range;
+
+pub(crate) struct EditedContent {
+    /// The file after the edits. Edits that could not be placed are appended.
+    pub post_edit: String,
+    /// `post_edit` with every line the edits did not introduce blanked, so
+    /// match positions are post-edit file positions. Unplaced edits follow.
+    pub introduced: String,
+    /// Lines of `introduced` that are real file lines; matches past this are
+    /// in unplaced edits and have no file location.
+    pub located_lines: usize,
+}
+
+impl EditedContent {
+    /// Apply `edits` in order to `original` (the file on disk, if it could
+    /// be read). An edit without `old_string` rewrites the whole file.
+    pub fn apply(original: Option<&str>, edits: &[FileEdit]) -> Self {
+        let mut content = original.unwrap_or_default().to_string();
+        let mut added: Vec<Range<usize>> = Vec::new();
+        let mut unplaced: Vec<String> = Vec::new();
+
+        for edit in edits {
+            let (start, old_len, previous) = match edit.old_string.as_deref() {
+                None => {
+                    added.clear();
+                    (0, content.len(), content.as_str())
+                }
+                Some(old) => match content.find(old) {
+                    Some(start) if !old.is_empty() => (start, old.len(), old),
+                    _ => {
+                        unplaced
+                            .extend(new_lines(old, &edit.new_string).map(|(_, l)| l.to_string()));
+                        continue;
+                    }
+                },
+            };
+
+            let fresh: Vec<Range<usize>> = new_lines(previous, &edit.new_string)
+                .map(|(offset, line)| start + offset..start + offset + line.len())
+                .collect();
+            let end = start + old_len;
+            let delta = edit.new_string.len() as isize - old_len as isize;
+            added.retain(|r| r.end <= start || r.start >= end);
+            for range in added.iter_mut().filter(|r| r.start >= end) {
+                range.start = (range.start as isize + delta) as usize;
+                range.end = (range.end as isize + delta) as usize;
+            }
+            added.extend(fresh);
+            content.replace_range(start..end, &edit.new_string);
+        }
+
+        let mut introduced = String::with_capacity(content.len());
+        let mut offset = 0;
+        for line in content.split_inclusive('\n') {
+            let span = offset..offset + line.len();
+            offset = span.end;
+            if added
+                .iter()
+                .any(|r| r.start < span.end && span.start < r.end.max(r.start + 1))
+            {
+                introduced.push_str(line);
+            } else if line.ends_with('\n') {
+                introduced.push('\n');
+            }
+        }
+        let located_lines = content.lines().count();
+
+        let mut post_edit = content;
+        for line in &unplaced {
+            for text in [&mut post_edit, &mut introduced] {
+                if !text.is_empty() && !text.ends_with('\n') {
+                    text.push('\n');
+                }
+                text.push_str(line);
+            }
+        }
+
+        Self {
+            post_edit,
+            introduced,
+            located_lines,
+        }
+    }
+}
+
+/// Lines of `new` (with their byte offsets) that are not lines of `old`,
+/// counting repeated lines.
+fn new_lines<'a>(old: &str, new: &'a str) -> impl Iterator<Item = (usize, &'a str)> {
+    let mut remaining: HashMap<String, usize> = HashMap::new();
+    for line in old.lines() {
+        *remaining.entry(line.to_string()).or_default() += 1;
+    }
+    let mut offset = 0;
+    new.split_inclusive('\n').filter_map(move |raw| {
+        let start = offset;
+        offset += raw.len();
+        let line = raw.trim_end_matches(['\n', '\r']);
+        match remaining.get_mut(line) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                None
+            }
+            _ => Some((start, line)),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit(old: Option<&str>, new: &str) -> FileEdit {
+        FileEdit {
+            old_string: old.map(str::to_string),
+            new_string: new.to_string(),
+            start_line: None,
+            end_line: None,
+        }
+    }
+
+    #[test]
+    fn keeps_only_introduced_lines_in_place() {
+        let original = "fn a() {\n    x.unwrap();\n}\n\nfn b() {\n}\n";
+        let edited = EditedContent::apply(
+            Some(original),
+            &[
+                edit(Some("fn b() {\n}"), "fn b() {\n    y.unwrap();\n}"),
+                edit(Some("missing"), "z.unwrap();"),
+            ],
+        );
+        assert_eq!(
+            edited.post_edit,
+            "fn a() {\n    x.unwrap();\n}\n\nfn b() {\n    y.unwrap();\n}\nz.unwrap();"
+        );
+        assert_eq!(
+            edited.introduced,
+            "\n\n\n\n\n    y.unwrap();\n\nz.unwrap();"
+        );
+        assert_eq!(edited.located_lines, 7);
+
+        let rewritten = EditedContent::apply(Some("a\nb\n"), &[edit(None, "a\nc\n")]);
+        assert_eq!(rewritten.post_edit, "a\nc\n");
+        assert_eq!(rewritten.introduced, "\nc\n");
+    }
+}
@@ -1,106 +1,258 @@
-use crate::config::{Config, CustomRule, ModularRule, RuleSeverity};
-use crate::hooks::{Decision, HookResult, ProjectLintEvent};
+use crate::config::{Config, CustomRule, ExecutionMode, ModularRule, RuleSeverity};
+use crate::content_match::ContentMatcher;
+use crate::hooks::{Decision, EventType, HookResult, ProjectLintEvent};
 use crate::utils::{matches_pattern, path_exists_glob, Result};
+use serde::{Deserialize, Serialize};
 use serde_json;
 use std::fs;
-use std::path::Path;
-use tracing::{debug, info, warn};
+use tracing::{debug, warn};
+
+mod commands;
+mod edits;
+mod redact;
+mod rewrites;
+mod security;
+mod when;
+
+use crate::hooks::mappers;
+use crate::hooks::session::SessionState;
+use commands::evaluate_command;
+use edits::EditedContent;
+use redact::redact_event;
+use rewrites::rewrite_command;
+use security::scan_write;
+use when::{Subject, When};
 
 pub struct RuleEngine<'a> {
     config: &'a Config,
+    /// State of the event's session, as `when` conditions see it.
+    session: serde_json::Value,
 }
 
 impl<'a> RuleEngine<'a> {
     pub fn new(config: &'a Config) -> Self {
-        Self { config }
+        Self {
+            config,
+            session: serde_json::Value::Null,
+        }
+    }
+
+    /// Let `when` conditions read the state of the event's session.
+    pub fn with_session(mut self, state: &SessionState) -> Self {
+        self.session = state.to_value();
+        self
     }
 
+    /// Evaluate the event, leaving out the rules that run detached (see
+    /// [`RuleEngine::runs_detached`]).
     pub fn evaluate_event(&self, event: &ProjectLintEvent) -> Result<HookResult> {
+        self.evaluate_event_with(event, |mode| Self::runs_detached(mode, event), Vec::new())
+    }
+
+    /// Evaluate the event without the rules whose mode `skip` selects, and
+    /// fold in `extra` issues that were found elsewhere (by the daemon).
+    pub fn evaluate_event_with(
+        &self,
+        event: &ProjectLintEvent,
+        skip: impl Fn(&ExecutionMode) -> bool,
+        extra: Vec<DetectedIssue>,
+    ) -> Result<HookResult> {
         let mut result = HookResult::default();
         let mut issues = Vec::new();
 
-        // 1. Evaluate modular rules
-        for rule in &self.config.modular_rules {
-            if !rule.enabled {
+        // 0. Secrets in the prompt or tool input. When they are masked, the
+        // rest of the evaluation sees the event the IDE will run.
+        let redaction = redact_event(self.config, event);
+        let mut redacted: Vec<String> = Vec::new();
+        if let Some(redaction) = &redaction {
+            if redaction.replaces_input {
+                redacted = redaction.issues.iter().map(|i| i.name.clone()).collect();
+            }
+            issues.extend(redaction.issues.iter().cloned());
+        }
+        let original = event;
+        let event = match &redaction {
+            Some(redaction) if redaction.replaces_input => &redaction.redacted,
+            _ => event,
+        };
+
+        // 1. Modular and top-level custom rules
+        for rule in self.triggered_rules(event)? {
+            if skip(&rule.mode) {
                 continue;
             }
+            if let Some(issue) = self.evaluate_custom_rule(rule, event)? {
+                issues.push(issue);
+            }
+        }
 
-            if self.matches_triggers(&rule.triggers, event)? {
-                debug!("Rule '{}' triggered by event", rule.name);
-                if let Some(custom_rules) = &rule.rules {
-                    for custom_rule in custom_rules {
-                        if let Some(issue) = self.evaluate_custom_rule(custom_rule, event)? {
-                            issues.push(issue);
-                        }
-                    }
+        issues.extend(extra);
+        let project_root = event
+            .cwd
+            .clone()
+            .or_else(|| std::env::current_dir().ok())
+            .unwrap_or_else(|| std::path::PathBuf::from("."));
+
+        // 2. Security scanners on the content a write introduces, as written
+        issues.extend(scan_write(self.config, original, &project_root));
+
+        // 3. Command rewrites, then the shell command policy for the command
+        // that will actually run
+        let mut rewritten: Option<(String, Vec<String>)> = None;
+        if matches!(
+            event.event_type,
+            EventType::PreRunCommand | EventType::PreToolUse
+        ) {
+            if let Some(command) = self.event_command(event) {
+                let mut command_to_run = command;
+                if let Some((new_command, rewrite_issues)) =
+                    rewrite_command(self.config, &project_root, &command_to_run)
+                {
+                    let names = rewrite_issues.iter().map(|i| i.name.clone()).collect();
+                    issues.extend(rewrite_issues);
+                    command_to_run = new_command.clone();
+                    rewritten = Some((new_command, names));
                 }
+                issues.extend(evaluate_command(self.config, &command_to_run));
             }
         }
 
-        // 2. Evaluate top-level custom rules
-        for rule in &self.config.rules.custom_rules {
-            if self.matches_triggers(&rule.triggers, event)? {
-                debug!("Top-level rule '{}' triggered by event", rule.name);
-                if let Some(issue) = self.evaluate_custom_rule(rule, event)? {
-                    issues.push(issue);
-                }
+        // Per-path `[[overrides]]` and severity overrides
+        let override_path = Self::relative_event_path(event);
+        issues.retain(|issue| {
+            self.config
+                .override_state(&[issue.name.as_str()], &override_path)
+                != Some(false)
+        });
+        for issue in &mut issues {
+            if let Some(severity) = self
+                .config
+                .severity_override_for(&[issue.name.as_str()], &override_path)
+            {
+                issue.severity = severity;
             }
         }
 
-        // 3. Process issues and determine result
+        // 4. Process issues and determine result
         if !issues.is_empty() {
-            let has_errors = issues
+            let decision = issues
                 .iter()
-                .any(|i| matches!(i.severity, RuleSeverity::Error));
+                .map(|i| match (&i.decision, &i.severity) {
+                    (Some(decision), _) => decision.clone(),
+                    (None, RuleSeverity::Error) => Decision::Deny,
+                    (None, _) => Decision::Warn,
+                })
+                .max_by_key(|d| match d {
+                    Decision::Allow => 0,
+                    Decision::Warn => 1,
+                    Decision::Ask => 2,
+                    Decision::Deny => 3,
+                })
+                .unwrap_or(Decision::Warn);
 
             let mut message = String::from("Project Lint violations detected:\n");
-            let mut modified_input: Option<serde_json::Value> = None;
-
             for issue in &issues {
-                let icon = match issue.severity {
-                    RuleSeverity::Error => "❌",
-                    RuleSeverity::Warning => "⚠️",
-                    RuleSeverity::Info => "ℹ️",
+                let icon = match (&issue.decision, &issue.severity) {
+                    (Some(Decision::Ask), _) => "❓",
+                    (_, RuleSeverity::Error) => "❌",
+                    (_, RuleSeverity::Warning) => "⚠️",
+                    (_, RuleSeverity::Info) => "ℹ️",
                 };
                 message.push_str(&format!("{} {}: {}\n", icon, issue.name, issue.message));
+            }
+            result.message = Some(message);
 
-                // Check if this issue includes a command rewrite suggestion
-                if issue.name == "pnpm-workspace-enforcer" {
-                    {
-                        if let Some(tool_input) = &event.context.tool_input {
-                            if let Some(command_str) = self.extract_command_from_input(tool_input) {
-                                if command_str.starts_with("npm ") {
-                                    let rewritten_command = command_str.replace("npm ", "pnpm ");
-                                    // Create modified input with the rewritten command
-                                    if tool_input.get("input").is_some() {
-                                        let mut new_input = tool_input.clone();
-                                        new_input["input"] =
-                                            serde_json::Value::String(rewritten_command);
-                                        modified_input = Some(new_input);
-                                    } else if tool_input.get("tool_input").is_some() {
-                                        let mut new_input = tool_input.clone();
-                                        new_input["tool_input"] =
-                                            serde_json::Value::String(rewritten_command);
-                                        modified_input = Some(new_input);
-                                    }
-                                }
-                            }
-                        }
-                    }
+            // Send the rewritten command or masked input back unless
+            // overrides dropped the rewrite or redaction
+            if let Some((command, names)) = rewritten {
+                if issues.iter().any(|i| names.contains(&i.name)) {
+                    result.modified_input = mappers::command_input(event, &command);
                 }
             }
+            if result.modified_input.is_none() && issues.iter().any(|i| redacted.contains(&i.name))
+            {
+                result.modified_input = mappers::modified_input(event);
+            }
+            result.decision = decision;
+        }
 
-            result.message = Some(message);
-            result.modified_input = modified_input;
+        Ok(result)
+    }
+
+    /// Async rules run detached when the event has a session to report
+    /// their findings to; without one they run with the event.
+    pub fn runs_detached(mode: &ExecutionMode, event: &ProjectLintEvent) -> bool {
+        mode.is_async() && event.session_id.is_some()
+    }
+
+    /// Whether the event triggers a rule whose mode `select` selects.
+    pub fn triggers(
+        &self,
+        event: &ProjectLintEvent,
+        select: impl Fn(&ExecutionMode) -> bool,
+    ) -> Result<bool> {
+        Ok(self.triggered_rules(event)?.iter().any(|r| select(&r.mode)))
+    }
 
-            if has_errors {
-                result.decision = Decision::Deny;
-            } else {
-                result.decision = Decision::Warn;
+    /// Evaluate only the triggered rules whose mode `select` selects.
+    /// Overrides apply as for [`RuleEngine::evaluate_event`].
+    pub fn evaluate_rules(
+        &self,
+        event: &ProjectLintEvent,
+        select: impl Fn(&ExecutionMode) -> bool,
+    ) -> Result<Vec<DetectedIssue>> {
+        let mut issues = Vec::new();
+        for rule in self.triggered_rules(event)? {
+            if !select(&rule.mode) {
+                continue;
+            }
+            if let Some(issue) = self.evaluate_custom_rule(rule, event)? {
+                issues.push(issue);
             }
         }
+        let override_path = Self::relative_event_path(event);
+        issues.retain(|issue| {
+            self.config
+                .override_state(&[issue.name.as_str()], &override_path)
+                != Some(false)
+        });
+        Ok(issues)
+    }
 
-        Ok(result)
+    /// Custom rules of enabled modular rules, then top-level custom rules,
+    /// whose triggers match the event.
+    fn triggered_rules(&self, event: &ProjectLintEvent) -> Result<Vec<&CustomRule>> {
+        let mut rules = Vec::new();
+        for rule in &self.config.modular_rules {
+            if !rule.enabled {
+                continue;
+            }
+            if self.matches_triggers(&rule.triggers, event)? {
+                debug!("Rule '{}' triggered by event", rule.name);
+                rules.extend(rule.rules.iter().flatten());
+            }
+        }
+        for rule in &self.config.rules.custom_rules {
+            if self.matches_triggers(&rule.triggers, event)? {
+                debug!("Top-level rule '{}' triggered by event", rule.name);
+                rules.push(rule);
+            }
+        }
+        Ok(rules)
+    }
+
+    /// The event's file path relative to its working directory, or an empty
+    /// path when the event carries no file.
+    fn relative_event_path(event: &ProjectLintEvent) -> std::path::PathBuf {
+        let Some(file_path) = &event.context.file_path else {
+            return std::path::PathBuf::new();
+        };
+        event
+            .cwd
+            .as_ref()
+            .and_then(|cwd| file_path.strip_prefix(cwd).ok())
+            .unwrap_or(file_path)
+            .to_path_buf()
     }
 
     fn matches_triggers(&self, triggers: &[String], event: &ProjectLintEvent) -> Result<bool> {
@@ -225,42 +377,119 @@ impl<'a> RuleEngine<'a> {
             }
         }
 
-        // Special handling for pnpm enforcement
-        if rule.name == "pnpm-workspace-enforcer" {
-            return self.evaluate_pnpm_rule(rule, event);
+        if let Some(source) = &rule.when {
+            // Config loading rejects malformed conditions; a rule built some
+            // other way still must not lose a deny or ask to a typo.
+            let holds = match When::parse_cached(source) {
+                Ok(when) => {
+                    let subject =
+                        Subject::new(event, self.event_command(event), &cwd_buf, &self.session);
+                    when.holds(&subject)
+                }
+                Err(e) if rule.ask || rule.severity == RuleSeverity::Error => {
+                    warn!("Applying rule '{}' unconditionally: {}", rule.name, e);
+                    true
+                }
+                Err(e) => {
+                    warn!("Skipping rule '{}': {}", rule.name, e);
+                    return Ok(None);
+                }
+            };
+            if !holds {
+                debug!(
+                    "Rule '{}' matched but its when condition does not hold",
+                    rule.name
+                );
+                return Ok(None);
+            }
         }
 
         // Check content patterns against user prompt or file content if available
         if rule.check_content {
-            let mut content_to_check = String::new();
+            let matcher = match ContentMatcher::for_rule(rule) {
+                Ok(matcher) => matcher,
+                Err(e) => {
+                    warn!("Skipping rule '{}': {}", rule.name, e);
+                    return Ok(None);
+                }
+            };
+            let Some(matcher) = matcher else {
+                return Ok(None);
+            };
+
+            // Writes are judged on what they introduce; `must_contain` needs
+            // the whole file after the edits.
+            let must_contain = rule.condition.as_deref() == Some("must_contain");
+            let edited = event.context.edits.as_ref().map(|edits| {
+                let original = event
+                    .context
+                    .file_path
+                    .as_ref()
+                    .and_then(|path| fs::read_to_string(cwd_buf.join(path)).ok());
+                EditedContent::apply(original.as_deref(), edits)
+            });
+            let (file_content, located_lines) = match &edited {
+                Some(edited) if must_contain => {
+                    (edited.post_edit.as_str(), edited.post_edit.lines().count())
+                }
+                Some(edited) => (edited.introduced.as_str(), edited.located_lines),
+                None => {
+                    let content = event.context.file_content.as_deref().unwrap_or("");
+                    (content, content.lines().count())
+                }
+            };
+
+            // File content first so match lines are file lines
+            let mut content_to_check = file_content.to_string();
             if let Some(prompt) = &event.context.user_prompt {
+                content_to_check.push('\n');
                 content_to_check.push_str(prompt);
             }
-            if let Some(file_content) = &event.context.file_content {
-                content_to_check.push_str(file_content);
-            }
-
-            if let Some(pattern) = &rule.content_pattern {
-                let contains = content_to_check.contains(pattern);
-                let is_violation = match rule.condition.as_deref() {
-                    Some("must_contain") => !contains,
-                    _ => contains, // default is denylist
-                };
 
-                if is_violation {
-                    return Ok(Some(DetectedIssue {
-                        name: rule.name.clone(),
-                        message: rule.message.clone(),
-                        severity: rule.severity.clone(),
-                    }));
+            let found = matcher.find(&content_to_check);
+            let is_violation = match must_contain {
+                true => found.is_none(),
+                false => found.is_some(), // default is denylist
+            };
+            if !is_violation {
+                return Ok(None);
+            }
+            if let Some(exception) = &rule.exception_pattern {
+                let in_file = edited
+                    .as_ref()
+                    .is_some_and(|e| e.post_edit.contains(exception.as_str()));
+                if in_file || content_to_check.contains(exception.as_str()) {
+                    debug!(
+                        "Rule '{}' matched but exception pattern '{}' is present",
+                        rule.name, exception
+                    );
+                    return Ok(None);
                 }
             }
+
+            let message = match &found {
+                Some(found) if found.line <= located_lines => format!(
+                    "{} (line {}, column {})",
+                    found.interpolate(&rule.message),
+                    found.line,
+                    found.column
+                ),
+                Some(found) => found.interpolate(&rule.message),
+                None => rule.message.clone(),
+            };
+            return Ok(Some(DetectedIssue {
+                name: rule.name.clone(),
+                message,
+                severity: rule.severity.clone(),
+                decision: rule.ask.then_some(Decision::Ask),
+            }));
         } else if !rule.required {
             // If it's a denylist rule (not required) and we matched the pattern, it's an issue
             return Ok(Some(DetectedIssue {
                 name: rule.name.clone(),
                 message: rule.message.clone(),
                 severity: rule.severity.clone(),
+                decision: rule.ask.then_some(Decision::Ask),
             }));
         }
 
@@ -268,125 +497,31 @@ impl<'a> RuleEngine<'a> {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DetectedIssue {
     pub name: String,
     pub message: String,
     pub severity: RuleSeverity,
+    /// Decision this issue forces; otherwise errors deny and the rest warn.
+    pub decision: Option<Decision>,
 }
 
-impl<'a> RuleEngine<'a> {
-    /// Evaluate pnpm workspace enforcement rule
-    fn evaluate_pnpm_rule(
-        &self,
-        rule: &CustomRule,
-        event: &ProjectLintEvent,
-    ) -> Result<Option<DetectedIssue>> {
-        // Only check on PreToolUse events
-        if event.event_type != crate::hooks::EventType::PreToolUse {
-            return Ok(None);
-        }
-
-        // Get the current working directory
-        let cwd_buf = event
-            .cwd
-            .clone()
-            .or_else(|| std::env::current_dir().ok())
-            .unwrap_or_else(|| std::path::PathBuf::from("."));
-        let cwd = cwd_buf.as_path();
-
-        // Check if this is a pnpm workspace
-        if !self.is_pnpm_workspace(cwd)? {
-            debug!("Not a pnpm workspace, skipping pnpm enforcement");
-            return Ok(None);
-        }
-
-        // Check tool input for npm commands
-        if let Some(tool_input) = &event.context.tool_input {
-            if let Some(command_str) = self.extract_command_from_input(tool_input) {
-                if command_str.starts_with("npm ") {
-                    info!("Detected npm command in pnpm workspace: {}", command_str);
-
-                    let rewritten_command = command_str.replace("npm ", "pnpm ");
-
-                    return Ok(Some(DetectedIssue {
-                        name: rule.name.clone(),
-                        message: format!(
-                            "🚫 This project uses pnpm (detected in package.json).\n\nFound: {}\nSuggested: {}\n\nThe command has been automatically rewritten to use pnpm.",
-                            command_str, rewritten_command
-                        ),
-                        severity: rule.severity.clone(),
-                    }));
-                }
-            }
-        }
-
-        Ok(None)
-    }
-
-    /// Check if the current directory is a pnpm workspace
-    fn is_pnpm_workspace(&self, project_path: &Path) -> Result<bool> {
-        let package_json_path = project_path.join("package.json");
-
-        if !package_json_path.exists() {
-            return Ok(false);
-        }
-
-        let content = fs::read_to_string(package_json_path)?;
-
-        // Parse package.json
-        if let Ok(package_json) = serde_json::from_str::<serde_json::Value>(&content) {
-            // Check for pnpm packageManager field
-            if let Some(package_manager) = package_json.get("packageManager") {
-                if let Some(pm_str) = package_manager.as_str() {
-                    if pm_str.starts_with("pnpm") {
-                        info!("Detected pnpm workspace via packageManager: {}", pm_str);
-                        return Ok(true);
-                    }
-                }
-            }
-
-            // Check for pnpm-workspace.yaml
-            if project_path.join("pnpm-workspace.yaml").exists()
-                || project_path.join("pnpm-workspace.yml").exists()
-            {
-                info!("Detected pnpm workspace via pnpm-workspace.yaml");
-                return Ok(true);
-            }
-
-            // Check for pnpm-lock.yaml
-            if project_path.join("pnpm-lock.yaml").exists() {
-                info!("Detected pnpm workspace via pnpm-lock.yaml");
-                return Ok(true);
-            }
-        }
-
-        Ok(false)
+/// Check the parts of a custom rule that are only compiled when an event
+/// reaches it, so that mistakes surface when the config is loaded.
+pub(crate) fn validate_rule(rule: &CustomRule) -> Result<()> {
+    if let Some(source) = &rule.when {
+        When::parse_cached(source)?;
     }
+    ContentMatcher::for_rule(rule)?;
+    Ok(())
+}
 
-    /// Extract command string from tool input
-    fn extract_command_from_input(&self, tool_input: &serde_json::Value) -> Option<String> {
-        // Handle different IDE formats
-
-        // Windsurf format
-        if let Some(input) = tool_input.get("input").and_then(|i| i.as_str()) {
-            return Some(input.to_string());
-        }
-
-        // Claude format
-        if let Some(tool_input) = tool_input.get("tool_input").and_then(|i| i.as_str()) {
-            return Some(tool_input.to_string());
-        }
-
-        // Generic format - try common fields
-        for field in ["command", "cmd", "input", "tool_input"] {
-            if let Some(value) = tool_input.get(field) {
-                if let Some(s) = value.as_str() {
-                    return Some(s.to_string());
-                }
-            }
-        }
-
-        None
+impl<'a> RuleEngine<'a> {
+    /// The shell command an event is about to run, as the mapper took it
+    /// from a shell tool (Claude `Bash`, Windsurf `pre_run_command`). Other
+    /// tools' inputs are not commands, even when they have a `command` field.
+    fn event_command(&self, event: &ProjectLintEvent) -> Option<String> {
+        event.context.command.clone()
     }
 }
 
@@ -394,99 +529,421 @@ impl<'a> RuleEngine<'a> {
 mod tests {
     use super::*;
     use crate::config::{Config, CustomRule, ExecutionMode, RuleSeverity};
-    use crate::hooks::{EventContext, EventType, ProjectLintEvent};
+    use crate::hooks::{Decision, EventContext, EventType, ProjectLintEvent};
     use serde_json::json;
     use std::path::PathBuf;
 
     #[test]
-    fn test_pnpm_enforcement_rule() {
-        // Create a mock config with the pnpm rule
+    fn test_command_rewrites_follow_project_markers() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.rules.command_rewrites = toml::from_str::<crate::config::RulesConfig>(
+            r#"
+[[command_rewrites]]
+name = "pnpm-add"
+pattern = 'npm (?:install|i|add) (?P<packages>\S.*)'
+replacement = "pnpm add {packages}"
+when_file_contains = { "package.json" = '"packageManager"\s*:\s*"pnpm' }
+message = "This project uses pnpm"
+
+[[command_rewrites]]
+name = "pnpm-dlx"
+pattern = 'npx(?P<rest>\s|$)'
+replacement = "pnpm dlx{rest}"
+when_exists = "pnpm-lock.yaml"
+"#,
+        )
+        .unwrap()
+        .command_rewrites;
+        let event = |command: &str| ProjectLintEvent {
+            event_type: EventType::PreToolUse,
+            session_id: None,
+            timestamp: None,
+            cwd: Some(dir.path().to_path_buf()),
+            context: EventContext {
+                tool_name: Some("Bash".to_string()),
+                tool_input: Some(json!({ "command": command, "timeout": 5 })),
+                command: Some(command.to_string()),
+                ide_source: "claude".to_string(),
+                ..Default::default()
+            },
+        };
+        let engine = RuleEngine::new(&config);
+
+        // Not a pnpm project: nothing happens
+        let result = engine
+            .evaluate_event(&event("npm install express"))
+            .unwrap();
+        assert!(result.message.is_none());
+
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{"packageManager": "pnpm@9.0.0"}"#,
+        )
+        .unwrap();
+        let result = engine
+            .evaluate_event(&event("cd web && npm i -D vitest && pnpm test && echo npm"))
+            .unwrap();
+        assert_eq!(result.decision, Decision::Warn);
+        assert_eq!(
+            result.modified_input,
+            Some(json!({
+                "command": "cd web && pnpm add -D vitest && pnpm test && echo npm",
+                "timeout": 5
+            }))
+        );
+
+        std::fs::write(dir.path().join("pnpm-lock.yaml"), "").unwrap();
+        let result = engine
+            .evaluate_event(&event("npm run build | npx prettier"))
+            .unwrap();
+        assert_eq!(
+            result.modified_input.unwrap()["command"],
+            json!("npm run build | pnpm dlx prettier")
+        );
+    }
+
+    #[test]
+    fn test_content_regex_reports_location_and_honors_exception() {
         let mut config = Config::default();
         config.rules.custom_rules.push(CustomRule {
-            name: "pnpm-workspace-enforcer".to_string(),
-            pattern: "*".to_string(),
-            message: "Use pnpm instead".to_string(),
-            severity: RuleSeverity::Warning,
-            check_content: false,
+            name: "no-hardcoded-key".to_string(),
+            pattern: "**/*.rs".to_string(),
+            message: "hardcoded key in '{var}'".to_string(),
+            severity: RuleSeverity::Error,
+            check_content: true,
             content_pattern: None,
-            exception_pattern: None,
+            content_regex: vec![r#"let (?P<var>\w+) = "akia"#.to_string()],
+            content_flags: Some("i".to_string()),
+            content_match: Default::default(),
+            exception_pattern: Some("// allow-key".to_string()),
             condition: None,
             required: false,
             required_if_path_exists: None,
             disabled_if_path_exists: None,
             enabled_if_path_exists: None,
             exclude_patterns: vec![],
-            triggers: vec!["pre_tool_use".to_string()],
+            triggers: vec!["pre_write_code".to_string()],
+            when: None,
+            ask: false,
             mode: ExecutionMode::LocalSync,
         });
-
-        // Create a mock event for npm command
-        let event = ProjectLintEvent {
-            event_type: EventType::PreToolUse,
-            session_id: Some("test-session".to_string()),
-            timestamp: Some("2025-01-28T20:00:00Z".to_string()),
+        let engine = RuleEngine::new(&config);
+        let event = |content: &str| ProjectLintEvent {
+            event_type: EventType::PreWriteCode,
+            session_id: None,
+            timestamp: None,
             cwd: Some(PathBuf::from("/tmp/test-project")),
             context: EventContext {
-                file_path: None,
-                file_content: None,
-                edits: None,
-                tool_name: Some("bash".to_string()),
-                tool_input: Some(json!({
-                    "input": "npm install express"
-                })),
-                tool_result: None,
-                command: None,
-                exit_code: None,
-                cwd: None,
-                user_prompt: None,
-                model_response: None,
-                ide_source: "windsurf".to_string(),
-                original_payload: Some(json!({
-                    "agent_action_name": "pre_mcp_tool_use"
-                })),
+                file_path: Some(PathBuf::from("src/main.rs")),
+                file_content: Some(content.to_string()),
+                ide_source: "claude".to_string(),
+                ..Default::default()
             },
         };
 
-        // Evaluate the rule (this will check for pnpm workspace)
-        let engine = RuleEngine::new(&config);
-        let result = engine.evaluate_event(&event);
+        let result = engine
+            .evaluate_event(&event("fn main() {\n    let key = \"AKIA1\";\n}\n"))
+            .unwrap();
+        let message = result.message.unwrap();
+        assert!(message.contains("hardcoded key in 'key' (line 2, column 5)"));
 
-        // Should not trigger without pnpm workspace
-        assert!(result.is_ok());
-        let hook_result = result.unwrap();
-        assert!(hook_result.message.is_none()); // No pnpm workspace detected
+        let result = engine
+            .evaluate_event(&event("let key = \"AKIA1\"; // allow-key\n"))
+            .unwrap();
+        assert!(result.message.is_none());
     }
 
     #[test]
-    fn test_command_extraction() {
-        let config = Config::default();
-        let engine = RuleEngine::new(&config);
+    fn test_edit_rules_only_see_introduced_lines() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/lib.rs"),
+            "fn a() {\n    x.unwrap();\n}\n\nfn b() {}\n",
+        )
+        .unwrap();
 
-        // Test Windsurf format
-        let windsurf_input = json!({
-            "input": "npm run dev"
+        let mut config = Config::default();
+        config.rules.custom_rules.push(CustomRule {
+            name: "no-unwrap-in-lib".to_string(),
+            pattern: "**/lib.rs".to_string(),
+            message: "unwrap() in library code".to_string(),
+            severity: RuleSeverity::Error,
+            check_content: true,
+            content_pattern: Some(".unwrap()".to_string()),
+            content_regex: vec![],
+            content_flags: None,
+            content_match: Default::default(),
+            exception_pattern: None,
+            condition: None,
+            required: false,
+            required_if_path_exists: None,
+            disabled_if_path_exists: None,
+            enabled_if_path_exists: None,
+            exclude_patterns: vec![],
+            triggers: vec!["pre_write_code".to_string()],
+            when: None,
+            ask: false,
+            mode: ExecutionMode::LocalSync,
         });
+        let engine = RuleEngine::new(&config);
+        let event = |old: &str, new: &str| ProjectLintEvent {
+            event_type: EventType::PreWriteCode,
+            session_id: None,
+            timestamp: None,
+            cwd: Some(dir.path().to_path_buf()),
+            context: EventContext {
+                file_path: Some(PathBuf::from("src/lib.rs")),
+                edits: Some(vec![crate::hooks::FileEdit {
+                    old_string: Some(old.to_string()),
+                    new_string: new.to_string(),
+                    start_line: None,
+                    end_line: None,
+                }]),
+                ide_source: "windsurf".to_string(),
+                ..Default::default()
+            },
+        };
+
+        // Editing a file that already has an unwrap is fine
+        let result = engine
+            .evaluate_event(&event("fn b() {}", "fn b() {\n    let y = 1;\n}"))
+            .unwrap();
+        assert!(result.message.is_none());
+
+        let result = engine
+            .evaluate_event(&event("fn b() {}", "fn b() {\n    y.unwrap();\n}"))
+            .unwrap();
+        assert!(result
+            .message
+            .unwrap()
+            .contains("unwrap() in library code (line 6, column 6)"));
+    }
+
+    #[test]
+    fn test_command_policy_decisions() {
+        let mut config = Config::default();
+        config.rules.command_rules = toml::from_str::<crate::config::RulesConfig>(
+            r#"
+[[command_rules]]
+name = "no-rm-root"
+program = "rm"
+args = ["-r", "/"]
+action = "deny"
+message = "Refusing to delete the filesystem root"
+
+[[command_rules]]
+name = "force-push"
+program = "git"
+args = ["push", "--force*"]
+action = "ask"
+
+[[command_rules]]
+name = "pipe-to-shell"
+program = ["sh", "bash", "zsh"]
+piped_from = ["curl", "wget"]
+action = "deny"
+message = "Do not pipe downloads into a shell"
+"#,
+        )
+        .unwrap()
+        .command_rules;
+        let engine_with = |config: &Config, command: &str| {
+            let event = ProjectLintEvent {
+                event_type: EventType::PreRunCommand,
+                session_id: None,
+                timestamp: None,
+                cwd: None,
+                context: EventContext {
+                    command: Some(command.to_string()),
+                    ide_source: "windsurf".to_string(),
+                    ..Default::default()
+                },
+            };
+            RuleEngine::new(config).evaluate_event(&event).unwrap()
+        };
+
+        let result = engine_with(&config, "cd build && sudo rm -fr /");
+        assert_eq!(result.decision, Decision::Deny);
+        assert!(result.message.unwrap().contains("no-rm-root"));
+        assert_eq!(
+            engine_with(&config, "rm -rf ./target").decision,
+            Decision::Allow
+        );
+        assert_eq!(
+            engine_with(&config, "bash -c 'git push --force-with-lease'").decision,
+            Decision::Ask
+        );
         assert_eq!(
-            engine.extract_command_from_input(&windsurf_input),
-            Some("npm run dev".to_string())
+            engine_with(&config, "curl -fsSL https://get.example | sudo sh").decision,
+            Decision::Deny
+        );
+        assert_eq!(
+            engine_with(&config, "sh ./install.sh").decision,
+            Decision::Allow
         );
 
-        // Test Claude format
-        let claude_input = json!({
-            "tool_input": "npm test"
-        });
+        let profile: crate::config::Profile = toml::from_str(
+            r#"
+[metadata]
+name = "locked-down"
+version = "1.0.0"
+updated = "2025-01-01"
+description = "only build tools"
+
+[enable]
+domains = []
+plugins = []
+
+[activation]
+
+[commands]
+allowlist = ["cargo", "git", "ls"]
+"#,
+        )
+        .unwrap();
+        config.active_profiles.push(profile);
         assert_eq!(
-            engine.extract_command_from_input(&claude_input),
-            Some("npm test".to_string())
+            engine_with(&config, "cargo test | ls").decision,
+            Decision::Allow
         );
+        let result = engine_with(&config, "FOO=1 cargo build && python3 x.py");
+        assert_eq!(result.decision, Decision::Deny);
+        assert!(result
+            .message
+            .unwrap()
+            .contains("'python3' is not in the command allowlist of profile(s) locked-down"));
+    }
 
-        // Test generic format
-        let generic_input = json!({
-            "command": "npm build"
+    #[test]
+    fn test_local_async_rules_are_deferred_for_sessions() {
+        let mut config = Config::default();
+        config.rules.custom_rules.push(CustomRule {
+            name: "slow-check".to_string(),
+            pattern: "*".to_string(),
+            message: "slow finding".to_string(),
+            severity: RuleSeverity::Error,
+            check_content: false,
+            content_pattern: None,
+            content_regex: vec![],
+            content_flags: None,
+            content_match: Default::default(),
+            exception_pattern: None,
+            condition: None,
+            required: false,
+            required_if_path_exists: None,
+            disabled_if_path_exists: None,
+            enabled_if_path_exists: None,
+            exclude_patterns: vec![],
+            triggers: vec!["pre_user_prompt".to_string()],
+            when: None,
+            ask: false,
+            mode: ExecutionMode::LocalAsync,
         });
-        assert_eq!(
-            engine.extract_command_from_input(&generic_input),
-            Some("npm build".to_string())
+        let engine = RuleEngine::new(&config);
+        let event = |session_id: Option<&str>| ProjectLintEvent {
+            event_type: EventType::PreUserPrompt,
+            session_id: session_id.map(str::to_string),
+            timestamp: None,
+            cwd: None,
+            context: EventContext {
+                user_prompt: Some("hi".to_string()),
+                ide_source: "claude".to_string(),
+                ..Default::default()
+            },
+        };
+
+        let with_session = event(Some("s-1"));
+        let result = engine.evaluate_event(&with_session).unwrap();
+        assert_eq!(result.decision, Decision::Allow);
+        assert!(engine
+            .triggers(&with_session, ExecutionMode::is_async)
+            .unwrap());
+        let deferred = engine
+            .evaluate_rules(&with_session, ExecutionMode::is_async)
+            .unwrap();
+        assert_eq!(deferred.len(), 1);
+        assert_eq!(deferred[0].message, "slow finding");
+
+        // Without a session there is nowhere to report later: run inline
+        let result = engine.evaluate_event(&event(None)).unwrap();
+        assert_eq!(result.decision, Decision::Deny);
+    }
+
+    #[test]
+    fn test_when_conditions_gate_rules() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        let signature = git2::Signature::now("t", "t@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "init", &tree, &[])
+            .unwrap();
+        let branch = repo.head().unwrap().shorthand().unwrap().to_string();
+
+        let mut config = Config::default();
+        let rule = format!(
+            r#"
+name = "no-push-to-{branch}"
+pattern = "*"
+message = "Open a pull request instead"
+severity = "error"
+triggers = ["pre_tool_use"]
+when = 'tool_name == "Bash" && command =~ "^git push" && branch in ["{branch}"]'
+"#
         );
+        config
+            .rules
+            .custom_rules
+            .push(toml::from_str(&rule).unwrap());
+        let engine = RuleEngine::new(&config);
+        let event = |tool_name: &str, command: &str| ProjectLintEvent {
+            event_type: EventType::PreToolUse,
+            session_id: None,
+            timestamp: None,
+            cwd: Some(dir.path().to_path_buf()),
+            context: EventContext {
+                tool_name: Some(tool_name.to_string()),
+                tool_input: Some(serde_json::json!({ "command": command })),
+                command: (tool_name == "Bash").then(|| command.to_string()),
+                ide_source: "claude".to_string(),
+                ..Default::default()
+            },
+        };
+
+        let result = engine
+            .evaluate_event(&event("Bash", "git push origin HEAD"))
+            .unwrap();
+        assert_eq!(result.decision, Decision::Deny);
+        assert!(result.message.unwrap().contains("Open a pull request"));
+        for (tool_name, command) in [("Bash", "git status"), ("Task", "git push")] {
+            let result = engine.evaluate_event(&event(tool_name, command)).unwrap();
+            assert_eq!(result.decision, Decision::Allow);
+        }
+
+        // `ask` leaves the push to the user
+        config.rules.custom_rules[0].ask = true;
+        let result = RuleEngine::new(&config)
+            .evaluate_event(&event("Bash", "git push"))
+            .unwrap();
+        assert_eq!(result.decision, Decision::Ask);
+
+        // A malformed condition fails closed for rules that deny or ask ...
+        config.rules.custom_rules[0].when = Some("command =~".to_string());
+        assert!(validate_rule(&config.rules.custom_rules[0]).is_err());
+        let result = RuleEngine::new(&config)
+            .evaluate_event(&event("Bash", "git status"))
+            .unwrap();
+        assert_eq!(result.decision, Decision::Ask);
+
+        // ... and skips rules that only warn
+        config.rules.custom_rules[0].ask = false;
+        config.rules.custom_rules[0].severity = RuleSeverity::Warning;
+        let result = RuleEngine::new(&config)
+            .evaluate_event(&event("Bash", "git push"))
+            .unwrap();
+        assert_eq!(result.decision, Decision::Allow);
     }
 }
@@ -0,0 +1,209 @@
+//! Command rewrites: `[[rules.command_rewrites]]` and the `command_rewrites`
+//! of enabled modular rules, applied to each top-level command of a command
+//! line. The first rewrite whose condition holds and whose pattern matches at
+//! the start of a command replaces the matched text.
+
+use super::DetectedIssue;
+use crate::config::{CommandRewrite, Config};
+use crate::hooks::shell::command_spans;
+use crate::utils::{path_exists_glob, CompiledCache};
+use regex::{Captures, Regex};
+use std::path::Path;
+use std::sync::OnceLock;
+use tracing::warn;
+
+/// The rewritten command line and one issue per applied rewrite, or `None`
+/// when nothing changed.
+pub(crate) fn rewrite_command(
+    config: &Config,
+    project_root: &Path,
+    command_line: &str,
+) -> Option<(String, Vec<DetectedIssue>)> {
+    let rewrites: Vec<(&CommandRewrite, Regex)> = config
+        .rules
+        .command_rewrites
+        .iter()
+        .chain(
+            config
+                .modular_rules
+                .iter()
+                .filter(|r| r.enabled)
+                .filter_map(|r| r.command_rewrites.as_ref())
+                .flatten(),
+        )
+        .filter_map(|rewrite| {
+            let regex = compiled(&format!("^(?:{})", rewrite.pattern), || {
+                format!("Skipping command rewrite '{}'", rewrite.name)
+            })?;
+            Some((rewrite, regex))
+        })
+        .collect();
+    if rewrites.is_empty() {
+        return None;
+    }
+
+    let mut conditions: Vec<Option<bool>> = vec![None; rewrites.len()];
+    let mut rewritten = command_line.to_string();
+    let mut issues: Vec<DetectedIssue> = Vec::new();
+    // Back to front so earlier spans stay valid
+    for span in command_spans(command_line).into_iter().rev() {
+        let command = &command_line[span.clone()];
+        for (index, (rewrite, regex)) in rewrites.iter().enumerate() {
+            let Some(captures) = regex.captures(command) else {
+                continue;
+            };
+            if !*conditions[index].get_or_insert_with(|| condition_holds(rewrite, project_root)) {
+                continue;
+            }
+            let matched = captures.get(0).map_or(0, |m| m.end());
+            let replaced = format!(
+                "{}{}",
+                interpolate(&rewrite.replacement, &captures),
+                &command[matched..]
+            );
+            let message = if rewrite.message.is_empty() {
+                format!("Rewrote `{}` to `{}`", command, replaced)
+            } else {
+                format!("{} (`{}` → `{}`)", rewrite.message, command, replaced)
+            };
+            rewritten.replace_range(span.clone(), &replaced);
+            issues.insert(
+                0,
+                DetectedIssue {
+                    name: rewrite.name.clone(),
+                    message,
+                    severity: rewrite.severity.clone(),
+                    decision: None,
+                },
+            );
+            break;
+        }
+    }
+
+    (rewritten != command_line).then_some((rewritten, issues))
+}
+
+fn condition_holds(rewrite: &CommandRewrite, project_root: &Path) -> bool {
+    if rewrite.when_exists.is_empty() && rewrite.when_file_contains.is_empty() {
+        return true;
+    }
+    rewrite
+        .when_exists
+        .iter()
+        .any(|marker| path_exists_glob(project_root, marker))
+        || rewrite.when_file_contains.iter().any(|(file, pattern)| {
+            let Some(regex) = compiled(pattern, || {
+                format!(
+                    "Invalid when_file_contains pattern in command rewrite '{}'",
+                    rewrite.name
+                )
+            }) else {
+                return false;
+            };
+            std::fs::read_to_string(project_root.join(file))
+                .is_ok_and(|content| regex.is_match(&content))
+        })
+}
+
+/// `pattern` compiled once per process; an invalid pattern is reported the
+/// first time only.
+fn compiled(pattern: &str, context: impl FnOnce() -> String) -> Option<Regex> {
+    static CACHE: CompiledCache<Option<Regex>> = CompiledCache::new();
+    CACHE.get_or_compile(pattern, || {
+        Regex::new(pattern)
+            .map_err(|e| warn!("{}: {}", context(), e))
+            .ok()
+    })
+}
+
+/// Replace `{1}`, `{name}` placeholders with captures; unmatched optional
+/// groups insert nothing.
+fn interpolate(template: &str, captures: &Captures) -> String {
+    static PLACEHOLDER: OnceLock<Regex> = OnceLock::new();
+    PLACEHOLDER
+        .get_or_init(|| Regex::new(r"\{(\w+)\}").expect("valid placeholder regex"))
+        .replace_all(template, |caps: &Captures| {
+            let key = &caps[1];
+            let value = match key.parse::<usize>() {
+                Ok(index) => captures.get(index),
+                Err(_) => captures.name(key),
+            };
+            value.map_or(String::new(), |m| m.as_str().to_string())
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn config_with(rewrite: &str) -> Config {
+        let mut config = Config::default();
+        config
+            .rules
+            .command_rewrites
+            .push(toml::from_str(rewrite).unwrap());
+        config
+    }
+
+    #[test]
+    fn substitutes_numbered_and_named_captures() {
+        let dir = TempDir::new().unwrap();
+        let config = config_with(
+            r#"
+name = "pnpm-add"
+pattern = 'npm (install|i|add)(?P<dev> -D)? (?P<packages>.+)'
+replacement = "pnpm add{dev} {packages} # was {1}"
+"#,
+        );
+
+        let (command, issues) =
+            rewrite_command(&config, dir.path(), "npm i -D vitest && npm install zod").unwrap();
+        assert_eq!(
+            command,
+            "pnpm add -D vitest # was i && pnpm add zod # was install"
+        );
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].name, "pnpm-add");
+        // Only at the start of a command
+        assert!(rewrite_command(&config, dir.path(), "echo npm i zod").is_none());
+    }
+
+    #[test]
+    fn applies_only_when_a_condition_holds() {
+        let dir = TempDir::new().unwrap();
+        let exists = config_with(
+            r#"
+name = "uv-pip"
+pattern = 'pip install'
+replacement = "uv pip install"
+when_exists = "uv.lock"
+"#,
+        );
+        let contains = config_with(
+            r#"
+name = "pnpm"
+pattern = 'npm install'
+replacement = "pnpm install"
+when_file_contains = { "package.json" = '"packageManager"\s*:\s*"pnpm' }
+"#,
+        );
+        assert!(rewrite_command(&exists, dir.path(), "pip install x").is_none());
+        assert!(rewrite_command(&contains, dir.path(), "npm install").is_none());
+
+        std::fs::write(dir.path().join("uv.lock"), "").unwrap();
+        std::fs::write(dir.path().join("package.json"), r#"{"name": "app"}"#).unwrap();
+        let (command, _) = rewrite_command(&exists, dir.path(), "pip install x").unwrap();
+        assert_eq!(command, "uv pip install x");
+        assert!(rewrite_command(&contains, dir.path(), "npm install").is_none());
+
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{"packageManager": "pnpm@9.0.0"}"#,
+        )
+        .unwrap();
+        let (command, _) = rewrite_command(&contains, dir.path(), "npm install").unwrap();
+        assert_eq!(command, "pnpm install");
+    }
+}
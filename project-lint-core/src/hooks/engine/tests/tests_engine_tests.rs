@@ -15,6 +15,9 @@ fn test_pnpm_enforcement_rule() {
         severity: RuleSeverity::Warning,
         check_content: false,
         content_pattern: None,
+        content_regex: vec![],
+        content_flags: None,
+        content_match: Default::default(),
         exception_pattern: None,
         condition: None,
         required: false,
@@ -0,0 +1,647 @@
+//! `when` conditions of custom rules: a small expression language over the
+//! fields of a hook event, e.g.
+//! `tool_name == "Bash" && command =~ "^git push" && branch in ["main"]`.
+//! Expressions are parsed once per distinct source and cached.
+
+use crate::hooks::{EventType, ProjectLintEvent};
+use crate::utils::{CompiledCache, Result};
+use anyhow::{anyhow, bail};
+use regex::Regex;
+use serde_json::Value;
+use std::cell::OnceCell;
+use std::path::Path;
+use std::sync::Arc;
+
+/// A parsed `when` expression.
+#[derive(Debug)]
+pub(crate) struct When {
+    expr: Expr,
+}
+
+#[derive(Debug)]
+enum Expr {
+    Or(Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    /// A lone operand: set, non-empty and not `false` or `0`.
+    Truthy(Operand),
+    Compare(Operand, CompareOp, Operand),
+    Regex(Operand, Regex),
+    Glob(Operand, glob::Pattern),
+    In(Operand, Operand),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug)]
+enum Operand {
+    Field(Field),
+    Literal(Value),
+}
+
+#[derive(Debug)]
+enum Field {
+    EventType,
+    ToolName,
+    FilePath,
+    Command,
+    ExitCode,
+    IdeSource,
+    SessionId,
+    UserPrompt,
+    Branch,
+    /// A JSON pointer into the session's state; empty for all of it.
+    Session(String),
+    /// A JSON pointer into the IDE's original payload; empty for all of it.
+    Payload(String),
+}
+
+/// What a `when` expression is evaluated against. The git branch is looked
+/// up at most once, and only if the expression asks for it.
+pub(crate) struct Subject<'a> {
+    event: &'a ProjectLintEvent,
+    command: Option<String>,
+    project_root: &'a Path,
+    session: &'a Value,
+    branch: OnceCell<Option<String>>,
+}
+
+impl<'a> Subject<'a> {
+    /// `command` is the shell command the event runs, wherever the IDE put it.
+    pub fn new(
+        event: &'a ProjectLintEvent,
+        command: Option<String>,
+        project_root: &'a Path,
+        session: &'a Value,
+    ) -> Self {
+        Self {
+            event,
+            command,
+            project_root,
+            session,
+            branch: OnceCell::new(),
+        }
+    }
+
+    fn field(&self, field: &Field) -> Value {
+        let context = &self.event.context;
+        let string = |s: Option<&str>| s.map_or(Value::Null, |s| Value::String(s.to_string()));
+        match field {
+            Field::EventType => match &self.event.event_type {
+                EventType::Unknown(name) => Value::String(name.clone()),
+                event_type => serde_json::to_value(event_type).unwrap_or(Value::Null),
+            },
+            Field::ToolName => string(context.tool_name.as_deref()),
+            Field::FilePath => string(
+                context
+                    .file_path
+                    .as_ref()
+                    .map(|p| p.to_string_lossy())
+                    .as_deref(),
+            ),
+            Field::Command => string(self.command.as_deref()),
+            Field::ExitCode => context.exit_code.map_or(Value::Null, Value::from),
+            Field::IdeSource => Value::String(context.ide_source.clone()),
+            Field::SessionId => string(self.event.session_id.as_deref()),
+            Field::UserPrompt => string(context.user_prompt.as_deref()),
+            Field::Branch => string(
+                self.branch
+                    .get_or_init(|| {
+                        let repo = git2::Repository::discover(self.project_root).ok()?;
+                        let head = repo.head().ok()?;
+                        head.shorthand().map(str::to_string)
+                    })
+                    .as_deref(),
+            ),
+            Field::Session(pointer) => self
+                .session
+                .pointer(pointer)
+                .cloned()
+                .unwrap_or(Value::Null),
+            Field::Payload(pointer) => context
+                .original_payload
+                .as_ref()
+                .and_then(|payload| payload.pointer(pointer))
+                .cloned()
+                .unwrap_or(Value::Null),
+        }
+    }
+}
+
+impl When {
+    /// The parsed expression for `source`, cached for the process.
+    pub fn parse_cached(source: &str) -> Result<Arc<When>> {
+        static CACHE: CompiledCache<std::result::Result<Arc<When>, String>> = CompiledCache::new();
+        CACHE
+            .get_or_compile(source, || {
+                Self::parse(source).map(Arc::new).map_err(|e| e.to_string())
+            })
+            .map_err(|e| anyhow!("{}", e))
+    }
+
+    pub fn parse(source: &str) -> Result<When> {
+        let tokens = tokenize(source).map_err(|e| anyhow!("invalid when '{}': {}", source, e))?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser
+            .expression()
+            .and_then(|expr| match parser.peek() {
+                None => Ok(expr),
+                Some(token) => bail!("unexpected {} at column {}", token.kind, token.column),
+            })
+            .map_err(|e| anyhow!("invalid when '{}': {}", source, e))?;
+        Ok(When { expr })
+    }
+
+    pub fn holds(&self, subject: &Subject) -> bool {
+        self.expr.holds(subject)
+    }
+}
+
+impl Expr {
+    fn holds(&self, subject: &Subject) -> bool {
+        match self {
+            Expr::Or(a, b) => a.holds(subject) || b.holds(subject),
+            Expr::And(a, b) => a.holds(subject) && b.holds(subject),
+            Expr::Not(a) => !a.holds(subject),
+            Expr::Truthy(a) => truthy(&a.value(subject)),
+            Expr::Compare(a, op, b) => compare(&a.value(subject), *op, &b.value(subject)),
+            Expr::Regex(a, regex) => text(&a.value(subject)).is_some_and(|t| regex.is_match(&t)),
+            Expr::Glob(a, pattern) => text(&a.value(subject)).is_some_and(|t| pattern.matches(&t)),
+            Expr::In(a, b) => {
+                let needle = a.value(subject);
+                match b.value(subject) {
+                    Value::Array(items) => items.iter().any(|item| equal(&needle, item)),
+                    Value::String(haystack) => {
+                        text(&needle).is_some_and(|t| haystack.contains(t.as_str()))
+                    }
+                    Value::Object(fields) => {
+                        needle.as_str().is_some_and(|k| fields.contains_key(k))
+                    }
+                    _ => false,
+                }
+            }
+        }
+    }
+}
+
+impl Operand {
+    fn value(&self, subject: &Subject) -> Value {
+        match self {
+            Operand::Field(field) => subject.field(field),
+            Operand::Literal(value) => value.clone(),
+        }
+    }
+}
+
+fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64() != Some(0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(_) => true,
+    }
+}
+
+/// Strings as they are, numbers and booleans in their JSON form.
+fn text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(_) | Value::Bool(_) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+/// Numbers compare by value, so `exit_code == 1` holds for `1.0` too.
+fn equal(a: &Value, b: &Value) -> bool {
+    match (a.as_f64(), b.as_f64()) {
+        (Some(a), Some(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+fn compare(a: &Value, op: CompareOp, b: &Value) -> bool {
+    let ordering = match (a, b) {
+        (Value::Number(_), Value::Number(_)) => a.as_f64().partial_cmp(&b.as_f64()),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    };
+    match op {
+        CompareOp::Eq => equal(a, b),
+        CompareOp::Ne => !equal(a, b),
+        CompareOp::Lt => ordering.is_some_and(|o| o.is_lt()),
+        CompareOp::Le => ordering.is_some_and(|o| o.is_le()),
+        CompareOp::Gt => ordering.is_some_and(|o| o.is_gt()),
+        CompareOp::Ge => ordering.is_some_and(|o| o.is_ge()),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Kind {
+    Ident(String),
+    Str(String),
+    Num(serde_json::Number),
+    Punct(&'static str),
+}
+
+impl std::fmt::Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Kind::Ident(name) => write!(f, "'{}'", name),
+            Kind::Str(s) => write!(f, "\"{}\"", s),
+            Kind::Num(n) => write!(f, "{}", n),
+            Kind::Punct(p) => write!(f, "'{}'", p),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: Kind,
+    /// 1-based column (in characters).
+    column: usize,
+}
+
+const PUNCTUATION: &[&str] = &[
+    "&&", "||", "==", "!=", "=~", "!~", "<=", ">=", "<", ">", "!", "(", ")", "[", "]", ",",
+];
+
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let column = i + 1;
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '"' || c == '\'' {
+            // Only the quote and backslash are escapes, so regexes keep `\d`
+            let mut value = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    None => bail!("unterminated string at column {}", column),
+                    Some(&q) if q == c => break,
+                    Some('\\') if chars.get(i + 1).is_some_and(|&n| n == c || n == '\\') => {
+                        value.push(chars[i + 1]);
+                        i += 2;
+                        continue;
+                    }
+                    Some(&other) => value.push(other),
+                }
+                i += 1;
+            }
+            i += 1;
+            tokens.push(Token {
+                kind: Kind::Str(value),
+                column,
+            });
+        } else if c.is_ascii_digit()
+            || (c == '-' && chars.get(i + 1).is_some_and(char::is_ascii_digit))
+        {
+            let start = i;
+            i += 1;
+            while chars
+                .get(i)
+                .is_some_and(|c| c.is_ascii_digit() || *c == '.')
+            {
+                i += 1;
+            }
+            let literal: String = chars[start..i].iter().collect();
+            let number = serde_json::from_str::<serde_json::Number>(&literal)
+                .map_err(|_| anyhow!("invalid number '{}' at column {}", literal, column))?;
+            tokens.push(Token {
+                kind: Kind::Num(number),
+                column,
+            });
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while chars
+                .get(i)
+                .is_some_and(|c| c.is_alphanumeric() || *c == '_')
+            {
+                i += 1;
+            }
+            tokens.push(Token {
+                kind: Kind::Ident(chars[start..i].iter().collect()),
+                column,
+            });
+        } else {
+            let rest: String = chars[i..chars.len().min(i + 2)].iter().collect();
+            let Some(punct) = PUNCTUATION.iter().find(|p| rest.starts_with(**p)) else {
+                bail!("unexpected '{}' at column {}", c, column);
+            };
+            i += punct.chars().count();
+            tokens.push(Token {
+                kind: Kind::Punct(punct),
+                column,
+            });
+        }
+    }
+    Ok(tokens)
+}
+
+/// Recursive descent, loosest first: `||`, `&&`, `!`, comparisons.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Result<Token> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| anyhow!("unexpected end of expression"))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn eat(&mut self, kind: &Kind) -> bool {
+        if self.peek().is_some_and(|t| &t.kind == kind) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_punct(&mut self, punct: &'static str) -> bool {
+        self.eat(&Kind::Punct(punct))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        self.eat(&Kind::Ident(keyword.to_string()))
+    }
+
+    fn expect_punct(&mut self, punct: &'static str) -> Result<()> {
+        let token = self.next()?;
+        if token.kind != Kind::Punct(punct) {
+            bail!(
+                "expected '{}' but found {} at column {}",
+                punct,
+                token.kind,
+                token.column
+            );
+        }
+        Ok(())
+    }
+
+    fn expression(&mut self) -> Result<Expr> {
+        let mut expr = self.conjunction()?;
+        while self.eat_punct("||") {
+            expr = Expr::Or(Box::new(expr), Box::new(self.conjunction()?));
+        }
+        Ok(expr)
+    }
+
+    fn conjunction(&mut self) -> Result<Expr> {
+        let mut expr = self.negation()?;
+        while self.eat_punct("&&") {
+            expr = Expr::And(Box::new(expr), Box::new(self.negation()?));
+        }
+        Ok(expr)
+    }
+
+    fn negation(&mut self) -> Result<Expr> {
+        if self.eat_punct("!") {
+            return Ok(Expr::Not(Box::new(self.negation()?)));
+        }
+        if self.eat_punct("(") {
+            let expr = self.expression()?;
+            self.expect_punct(")")?;
+            return Ok(expr);
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Expr> {
+        let left = self.operand()?;
+        let op = match self.peek().map(|t| t.kind.clone()) {
+            Some(Kind::Punct(op)) => op,
+            Some(Kind::Ident(keyword)) => match keyword.as_str() {
+                "in" => {
+                    self.pos += 1;
+                    return Ok(Expr::In(left, self.operand()?));
+                }
+                "not" => {
+                    self.pos += 1;
+                    if !self.eat_keyword("in") {
+                        bail!("expected 'in' after 'not'");
+                    }
+                    return Ok(Expr::Not(Box::new(Expr::In(left, self.operand()?))));
+                }
+                "matches" => {
+                    self.pos += 1;
+                    let source = self.string_literal("matches")?;
+                    let pattern = glob::Pattern::new(&source)
+                        .map_err(|e| anyhow!("invalid glob '{}': {}", source, e))?;
+                    return Ok(Expr::Glob(left, pattern));
+                }
+                _ => return Ok(Expr::Truthy(left)),
+            },
+            _ => return Ok(Expr::Truthy(left)),
+        };
+        let compare = match op {
+            "==" => CompareOp::Eq,
+            "!=" => CompareOp::Ne,
+            "<" => CompareOp::Lt,
+            "<=" => CompareOp::Le,
+            ">" => CompareOp::Gt,
+            ">=" => CompareOp::Ge,
+            "=~" | "!~" => {
+                self.pos += 1;
+                let source = self.string_literal(op)?;
+                let regex = Regex::new(&source)
+                    .map_err(|e| anyhow!("invalid regex '{}': {}", source, e))?;
+                let expr = Expr::Regex(left, regex);
+                return Ok(match op {
+                    "!~" => Expr::Not(Box::new(expr)),
+                    _ => expr,
+                });
+            }
+            _ => return Ok(Expr::Truthy(left)),
+        };
+        self.pos += 1;
+        Ok(Expr::Compare(left, compare, self.operand()?))
+    }
+
+    fn string_literal(&mut self, after: &str) -> Result<String> {
+        let token = self.next()?;
+        match token.kind {
+            Kind::Str(s) => Ok(s),
+            other => bail!(
+                "expected a string after '{}' but found {} at column {}",
+                after,
+                other,
+                token.column
+            ),
+        }
+    }
+
+    /// An optional `["/json/pointer"]` after a field.
+    fn pointer(&mut self) -> Result<String> {
+        if !self.eat_punct("[") {
+            return Ok(String::new());
+        }
+        let pointer = self.string_literal("[")?;
+        self.expect_punct("]")?;
+        if !pointer.is_empty() && !pointer.starts_with('/') {
+            bail!("JSON pointer '{}' must start with '/'", pointer);
+        }
+        Ok(pointer)
+    }
+
+    fn operand(&mut self) -> Result<Operand> {
+        let token = self.next()?;
+        let literal = |value: Value| Ok(Operand::Literal(value));
+        match token.kind {
+            Kind::Str(s) => literal(Value::String(s)),
+            Kind::Num(n) => literal(Value::Number(n)),
+            Kind::Punct("[") => {
+                let mut items = Vec::new();
+                while !self.eat_punct("]") {
+                    match self.operand()? {
+                        Operand::Literal(value) => items.push(value),
+                        Operand::Field(_) => {
+                            bail!("lists hold literals only (column {})", token.column)
+                        }
+                    }
+                    if !self.eat_punct(",") {
+                        self.expect_punct("]")?;
+                        break;
+                    }
+                }
+                literal(Value::Array(items))
+            }
+            Kind::Ident(name) => {
+                let field = match name.as_str() {
+                    "true" => return literal(Value::Bool(true)),
+                    "false" => return literal(Value::Bool(false)),
+                    "null" => return literal(Value::Null),
+                    "event_type" => Field::EventType,
+                    "tool_name" => Field::ToolName,
+                    "file_path" => Field::FilePath,
+                    "command" => Field::Command,
+                    "exit_code" => Field::ExitCode,
+                    "ide_source" => Field::IdeSource,
+                    "session_id" => Field::SessionId,
+                    "user_prompt" => Field::UserPrompt,
+                    "branch" => Field::Branch,
+                    "session" => Field::Session(self.pointer()?),
+                    "payload" | "original_payload" => Field::Payload(self.pointer()?),
+                    other => bail!("unknown field '{}' at column {}", other, token.column),
+                };
+                Ok(Operand::Field(field))
+            }
+            other => bail!("unexpected {} at column {}", other, token.column),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hooks::EventContext;
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    fn holds(source: &str, event: &ProjectLintEvent) -> bool {
+        let session = json!({"untested_writes": 3, "files_read": ["src/lib.rs"]});
+        let subject = Subject::new(
+            event,
+            event.context.command.clone(),
+            Path::new("."),
+            &session,
+        );
+        When::parse(source).unwrap().holds(&subject)
+    }
+
+    #[test]
+    fn evaluates_event_fields() {
+        let event = ProjectLintEvent {
+            event_type: EventType::PreToolUse,
+            session_id: Some("s-1".to_string()),
+            timestamp: None,
+            cwd: None,
+            context: EventContext {
+                tool_name: Some("Bash".to_string()),
+                command: Some("git push --force origin main".to_string()),
+                file_path: Some(PathBuf::from("src/lib.rs")),
+                exit_code: Some(1),
+                ide_source: "claude".to_string(),
+                original_payload: Some(json!({"tool_input": {"timeout": 600, "flags": ["a"]}})),
+                ..Default::default()
+            },
+        };
+
+        assert!(holds(
+            r#"tool_name == "Bash" && command =~ "^git push\s" && !(ide_source == 'kiro')"#,
+            &event
+        ));
+        assert!(holds(
+            r#"event_type in ["pre_tool_use", "pre_run_command"]"#,
+            &event
+        ));
+        assert!(holds(r#"file_path matches "src/**/*.rs""#, &event));
+        assert!(holds(
+            r#"exit_code != 0 && exit_code >= 1 && exit_code < 2"#,
+            &event
+        ));
+        assert!(holds(r#"payload["/tool_input/timeout"] > 300"#, &event));
+        assert!(holds(r#""a" in payload["/tool_input/flags"]"#, &event));
+        assert!(holds(r#""--force" in command"#, &event));
+        assert!(holds(r#"session_id && tool_name not in ["Edit"]"#, &event));
+        assert!(holds(r#"user_prompt == null || false"#, &event));
+        assert!(holds(
+            r#"session["/untested_writes"] > 2 && file_path in session["/files_read"]"#,
+            &event
+        ));
+        assert!(!holds(
+            r#"payload["/missing"] || command !~ "push""#,
+            &event
+        ));
+        // `&&` binds tighter than `||`
+        assert!(holds(r#"true || false && false"#, &event));
+        assert!(!holds(r#"(true || false) && false"#, &event));
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        for source in [
+            r#"tool_name == "Bash" &&"#,
+            r#"tool == "Bash""#,
+            r#"command =~ "(""#,
+            r#"command =~ tool_name"#,
+            r#"(tool_name == "Bash""#,
+            r#"tool_name == "Bash" extra"#,
+            r#"payload["tool_input"]"#,
+            r#"command == "open"#,
+        ] {
+            assert!(When::parse(source).is_err(), "{}", source);
+        }
+        let err = When::parse(r#"tool_nme == "Bash""#)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("unknown field 'tool_nme' at column 1"),
+            "{}",
+            err
+        );
+    }
+}
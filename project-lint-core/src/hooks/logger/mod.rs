@@ -5,6 +5,8 @@ use serde::{Deserialize, Serialize};
 use std::fs::{File, OpenOptions};
 use std::io::Write;
 use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime};
 use tracing::{debug, error, info, warn};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -125,6 +127,61 @@ impl HookLogger {
         Ok(entries)
     }
 
+    /// Files the session wrote, in the order first written, from the logs of
+    /// this log directory. Denied writes did not happen and are left out;
+    /// logs older than `max_age` are not read.
+    pub fn session_files_written(
+        &self,
+        session_id: &str,
+        max_age: Duration,
+    ) -> Result<Vec<String>> {
+        let Some(log_dir) = self.log_file.parent() else {
+            return Ok(Vec::new());
+        };
+        let now = SystemTime::now();
+        let mut logs: Vec<PathBuf> = std::fs::read_dir(log_dir)?
+            .flatten()
+            .filter(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let recent = entry
+                    .metadata()
+                    .and_then(|m| m.modified())
+                    .is_ok_and(|modified| {
+                        now.duration_since(modified).unwrap_or_default() <= max_age
+                    });
+                name.starts_with("hook-log-") && name.ends_with(".jsonl") && recent
+            })
+            .map(|entry| entry.path())
+            .collect();
+        // Dated names sort chronologically
+        logs.sort();
+
+        let mut files: Vec<String> = Vec::new();
+        for log in logs {
+            let content = std::fs::read_to_string(&log)?;
+            for entry in content
+                .lines()
+                .filter_map(|line| serde_json::from_str::<HookLogEntry>(line).ok())
+            {
+                if entry.session_id.as_deref() != Some(session_id) || entry.decision == "Deny" {
+                    continue;
+                }
+                let is_write =
+                    matches!(entry.event_type.as_str(), "PreWriteCode" | "PostWriteCode")
+                        || matches!(
+                            entry.tool_name.as_deref(),
+                            Some("Write" | "Edit" | "MultiEdit" | "NotebookEdit")
+                        );
+                if let Some(file) = entry.file_path.filter(|_| is_write) {
+                    if !files.contains(&file) {
+                        files.push(file);
+                    }
+                }
+            }
+        }
+        Ok(files)
+    }
+
     pub fn get_stats(&self) -> Result<HookStats> {
         let entries = self.get_recent_logs(None)?;
 
@@ -189,56 +246,40 @@ impl HookStats {
     }
 }
 
-// Global logger instance (using lazy_static or once_cell would be better in production)
-static mut GLOBAL_LOGGER: Option<HookLogger> = None;
-static LOGGER_INITIALIZED: std::sync::atomic::AtomicBool =
-    std::sync::atomic::AtomicBool::new(false);
+/// Logger of the process, set by [`initialize_global_logger`].
+static GLOBAL_LOGGER: OnceLock<HookLogger> = OnceLock::new();
 
 pub fn initialize_global_logger(log_dir: Option<PathBuf>) -> Result<()> {
-    if LOGGER_INITIALIZED
-        .compare_exchange(
-            false,
-            true,
-            std::sync::atomic::Ordering::Relaxed,
-            std::sync::atomic::Ordering::Relaxed,
-        )
-        .is_ok()
-    {
-        unsafe {
-            GLOBAL_LOGGER = Some(HookLogger::new(log_dir)?);
-        }
+    if GLOBAL_LOGGER.get().is_none() && GLOBAL_LOGGER.set(HookLogger::new(log_dir)?).is_ok() {
         info!("Global hook logger initialized");
     }
     Ok(())
 }
 
+/// Run `f` with the global logger, or a temporary one on the default log
+/// directory when it was never initialized.
+fn with_global_logger<T>(f: impl FnOnce(&HookLogger) -> Result<T>) -> Result<T> {
+    match GLOBAL_LOGGER.get() {
+        Some(logger) => f(logger),
+        None => f(&HookLogger::new(None)?),
+    }
+}
+
 pub fn log_hook_event(
     event: &ProjectLintEvent,
     decision: &str,
     message: Option<&str>,
     duration_ms: Option<u64>,
 ) -> Result<()> {
-    unsafe {
-        if let Some(logger) = &GLOBAL_LOGGER {
-            logger.log_event(event, decision, message, duration_ms)
-        } else {
-            // Fallback: create a temporary logger
-            let logger = HookLogger::new(None)?;
-            logger.log_event(event, decision, message, duration_ms)
-        }
-    }
+    with_global_logger(|logger| logger.log_event(event, decision, message, duration_ms))
+}
+
+pub fn session_files_written(session_id: &str, max_age: Duration) -> Result<Vec<String>> {
+    with_global_logger(|logger| logger.session_files_written(session_id, max_age))
 }
 
 pub fn get_hook_stats() -> Result<HookStats> {
-    unsafe {
-        if let Some(logger) = &GLOBAL_LOGGER {
-            logger.get_stats()
-        } else {
-            // Fallback: create a temporary logger
-            let logger = HookLogger::new(None)?;
-            logger.get_stats()
-        }
-    }
+    with_global_logger(|logger| logger.get_stats())
 }
 
 #[cfg(test)]
@@ -331,6 +372,40 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_session_files_written() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let logger = HookLogger::new(Some(temp_dir.path().to_path_buf()))?;
+
+        let event = |session_id: &str, tool_name: &str, file: &str| ProjectLintEvent {
+            event_type: EventType::PreToolUse,
+            session_id: Some(session_id.to_string()),
+            timestamp: None,
+            cwd: None,
+            context: EventContext {
+                tool_name: Some(tool_name.to_string()),
+                file_path: Some(PathBuf::from(file)),
+                ide_source: "claude".to_string(),
+                ..Default::default()
+            },
+        };
+        logger.log_event(&event("s-1", "Edit", "src/b.rs"), "Warn", None, None)?;
+        logger.log_event(&event("s-1", "Read", "src/r.rs"), "Allow", None, None)?;
+        logger.log_event(&event("s-1", "Write", "src/a.rs"), "Allow", None, None)?;
+        logger.log_event(&event("s-1", "Edit", "src/b.rs"), "Allow", None, None)?;
+        logger.log_event(&event("s-1", "Edit", "src/denied.rs"), "Deny", None, None)?;
+        logger.log_event(&event("s-2", "Edit", "src/other.rs"), "Allow", None, None)?;
+
+        let day = std::time::Duration::from_secs(24 * 60 * 60);
+        assert_eq!(
+            logger.session_files_written("s-1", day)?,
+            vec!["src/b.rs".to_string(), "src/a.rs".to_string()]
+        );
+        assert!(logger.session_files_written("s-3", day)?.is_empty());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_get_stats() -> Result<()> {
         let temp_dir = TempDir::new()?;
@@ -5,3 +5,35 @@ pub mod windsurf;
 pub use claude::ClaudeMapper;
 pub use kiro::KiroMapper;
 pub use windsurf::WindsurfMapper;
+
+use crate::hooks::{EventMapper, ProjectLintEvent};
+use serde_json::Value;
+
+/// Modified tool input for a rewritten command, shaped for the IDE the event
+/// came from.
+pub fn command_input(event: &ProjectLintEvent, command: &str) -> Option<Value> {
+    mapper_for_event(event)?.command_input(event, command)
+}
+
+/// Modified input carrying the event's (modified) prompt, tool input or
+/// command, shaped for the IDE the event came from.
+pub fn modified_input(event: &ProjectLintEvent) -> Option<Value> {
+    mapper_for_event(event)?.modified_input(event)
+}
+
+fn mapper_for_event(event: &ProjectLintEvent) -> Option<&'static dyn EventMapper> {
+    match event.context.ide_source.as_str() {
+        "windsurf" => Some(&WindsurfMapper),
+        "claude" => Some(&ClaudeMapper),
+        "kiro" => Some(&KiroMapper),
+        _ => None,
+    }
+}
+
+/// `input` with the first string field among `fields` set to `command`.
+fn with_command(input: &Value, fields: &[&str], command: &str) -> Option<Value> {
+    let field = fields.iter().find(|f| input[**f].is_string())?;
+    let mut modified = input.clone();
+    modified[*field] = Value::String(command.to_string());
+    Some(modified)
+}
@@ -91,6 +91,33 @@ impl EventMapper for WindsurfMapper {
         })
     }
 
+    fn command_input(&self, event: &ProjectLintEvent, command: &str) -> Option<Value> {
+        let payload = event.context.original_payload.as_ref()?;
+        match event.event_type {
+            EventType::PreRunCommand => {
+                super::with_command(&payload["tool_info"], &["command_line"], command)
+            }
+            _ => None,
+        }
+    }
+
+    fn modified_input(&self, event: &ProjectLintEvent) -> Option<Value> {
+        let payload = event.context.original_payload.as_ref()?;
+        let mut tool_info = payload["tool_info"].clone();
+        match event.event_type {
+            EventType::PreRunCommand => {
+                tool_info["command_line"] = Value::String(event.context.command.clone()?);
+                Some(tool_info)
+            }
+            EventType::PreToolUse => event.context.tool_input.clone(),
+            EventType::PreUserPrompt => {
+                tool_info["user_prompt"] = Value::String(event.context.user_prompt.clone()?);
+                Some(tool_info)
+            }
+            _ => None,
+        }
+    }
+
     fn format_response(&self, result: HookResult) -> Result<String> {
         // Windsurf can accept JSON responses for tool input modification
         let mut response = serde_json::Map::new();
@@ -115,11 +142,6 @@ impl EventMapper for WindsurfMapper {
                     "decision".to_string(),
                     serde_json::Value::String("warn".to_string()),
                 );
-
-                // If there's a modified input, include it for command rewriting
-                if let Some(modified_input) = result.modified_input {
-                    response.insert("modified_input".to_string(), modified_input);
-                }
             }
             Decision::Ask => {
                 // Request user confirmation
@@ -139,6 +161,14 @@ impl EventMapper for WindsurfMapper {
             }
         }
 
+        // A masked or rewritten input replaces the original whatever the
+        // decision, so an approved action never runs the original
+        if result.decision != Decision::Deny {
+            if let Some(modified_input) = result.modified_input {
+                response.insert("modified_input".to_string(), modified_input);
+            }
+        }
+
         // Return JSON response if we have meaningful content
         if !response.is_empty() {
             Ok(serde_json::to_string(&response)?)
@@ -154,6 +184,21 @@ mod tests {
     use crate::hooks::Decision;
     use serde_json::json;
 
+    #[test]
+    fn test_command_input_replaces_command_line() -> Result<()> {
+        let input = json!({
+            "agent_action_name": "pre_run_command",
+            "tool_info": { "command_line": "npm i x", "cwd": "/repo" }
+        })
+        .to_string();
+        let event = WindsurfMapper.map_event(&input)?;
+        assert_eq!(
+            WindsurfMapper.command_input(&event, "pnpm add x"),
+            Some(json!({ "command_line": "pnpm add x", "cwd": "/repo" }))
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_map_pre_write_code_extracts_file_and_edits() -> Result<()> {
         let input = json!({
@@ -237,6 +282,23 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_format_response_ask_keeps_modified_input() -> Result<()> {
+        let result = HookResult {
+            decision: Decision::Ask,
+            message: Some("confirm".to_string()),
+            modified_input: Some(json!({ "command_line": "curl [REDACTED aws_key]" })),
+        };
+        let out = WindsurfMapper.format_response(result)?;
+        let v: Value = serde_json::from_str(&out)?;
+        assert_eq!(v["decision"], json!("ask"));
+        assert_eq!(
+            v["modified_input"]["command_line"],
+            json!("curl [REDACTED aws_key]")
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_map_invalid_json_returns_error() {
         let result = WindsurfMapper.map_event("{ broken");
@@ -111,11 +111,42 @@ impl Default for HookResult {
 pub trait EventMapper {
     fn map_event(&self, input: &str) -> crate::utils::Result<ProjectLintEvent>;
     fn format_response(&self, result: HookResult) -> crate::utils::Result<String>;
+
+    /// The tool input that runs `command` instead of the event's command, in
+    /// this IDE's shape. `None` when the IDE cannot take a modified command.
+    fn command_input(
+        &self,
+        _event: &ProjectLintEvent,
+        _command: &str,
+    ) -> Option<serde_json::Value> {
+        None
+    }
+
+    /// The input the IDE should use instead of the event's, built from the
+    /// event's (modified) prompt, tool input or command. `None` when the IDE
+    /// cannot take a modified input for this event.
+    fn modified_input(&self, _event: &ProjectLintEvent) -> Option<serde_json::Value> {
+        None
+    }
+
+    /// The response to a `Stop`/`SubagentStop` event, which carries a denied
+    /// stop back to the agent. `None` when the IDE has no such response: the
+    /// event is answered like any other and a denied stop exits with code 2.
+    fn format_stop_response(&self, _result: HookResult) -> Option<crate::utils::Result<String>> {
+        None
+    }
 }
 
+pub mod daemon;
+pub mod dispatch;
 pub mod engine;
 pub mod logger;
 pub mod mappers;
+pub mod session;
+pub mod shell;
 
 pub use engine::RuleEngine;
-pub use logger::{get_hook_stats, initialize_global_logger, log_hook_event, HookLogger, HookStats};
+pub use logger::{
+    get_hook_stats, initialize_global_logger, log_hook_event, session_files_written, HookLogger,
+    HookStats,
+};
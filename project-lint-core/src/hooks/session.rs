@@ -0,0 +1,399 @@
+//! Per-session store. It keeps the state of each agent session (files
+//! read and written, denials, test runs) for rules to read through
+//! `session[...]` in `when` conditions, and queues findings of `LocalAsync`
+//! rules: a detached evaluation appends its findings to the session's queue;
+//! the next hook event of that session takes them and reports them as
+//! warnings. Both are dropped when the session ends.
+
+use crate::config::RuleSeverity;
+use crate::hooks::engine::DetectedIssue;
+use crate::hooks::shell::parse_command_line;
+use crate::hooks::{Decision, EventType, HookResult, ProjectLintEvent};
+use crate::utils::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{ErrorKind, Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// What a session has done so far.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionState {
+    /// Hook events seen.
+    pub events: u64,
+    /// Events denied, and the file writes among them.
+    pub denied: u64,
+    pub denied_writes: u64,
+    pub files_read: BTreeSet<String>,
+    pub files_written: BTreeSet<String>,
+    /// Files written since tests last ran.
+    pub untested_files: BTreeSet<String>,
+    pub test_runs: u64,
+}
+
+impl SessionState {
+    /// Account for an event and the decision it got. `test_commands` are
+    /// globs matched against each command of a command line. Reads, writes
+    /// and test runs are taken from pre events, where the decision is made,
+    /// so a tool call seen by both a pre and a post hook counts once.
+    pub fn record(
+        &mut self,
+        event: &ProjectLintEvent,
+        decision: &Decision,
+        test_commands: &[String],
+    ) {
+        let context = &event.context;
+        self.events += 1;
+        let file = context
+            .file_path
+            .as_ref()
+            .map(|p| p.to_string_lossy().to_string());
+        let is_write = file.is_some()
+            && match event.event_type {
+                EventType::PreToolUse => context.edits.is_some(),
+                EventType::PreWriteCode => true,
+                _ => false,
+            };
+        let is_read = file.is_some()
+            && match event.event_type {
+                EventType::PreToolUse => context.tool_name.as_deref() == Some("Read"),
+                EventType::PreReadCode => true,
+                _ => false,
+            };
+
+        if *decision == Decision::Deny {
+            self.denied += 1;
+            if is_write {
+                self.denied_writes += 1;
+            }
+            return;
+        }
+        if let Some(file) = file {
+            if is_write {
+                self.files_written.insert(file.clone());
+                self.untested_files.insert(file);
+            } else if is_read {
+                self.files_read.insert(file);
+            }
+        }
+        let starts_command = matches!(
+            event.event_type,
+            EventType::PreToolUse | EventType::PreRunCommand
+        );
+        let runs_tests = starts_command
+            && context.command.as_deref().is_some_and(|line| {
+                parse_command_line(line).iter().any(|command| {
+                    let text = std::iter::once(command.program.as_str())
+                        .chain(command.args.iter().map(String::as_str))
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    test_commands
+                        .iter()
+                        .any(|pattern| glob::Pattern::new(pattern).is_ok_and(|p| p.matches(&text)))
+                })
+            });
+        if runs_tests {
+            self.test_runs += 1;
+            self.untested_files.clear();
+        }
+    }
+
+    /// The state as `when` conditions see it, with `untested_writes`, the
+    /// number of files written since tests last ran.
+    pub fn to_value(&self) -> Value {
+        let mut value = serde_json::to_value(self).unwrap_or(Value::Null);
+        if let Value::Object(fields) = &mut value {
+            fields.insert(
+                "untested_writes".to_string(),
+                self.untested_files.len().into(),
+            );
+        }
+        value
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingFinding {
+    pub name: String,
+    pub message: String,
+    pub severity: RuleSeverity,
+}
+
+impl From<DetectedIssue> for PendingFinding {
+    fn from(issue: DetectedIssue) -> Self {
+        Self {
+            name: issue.name,
+            message: issue.message,
+            severity: issue.severity,
+        }
+    }
+}
+
+pub struct SessionStore {
+    dir: PathBuf,
+}
+
+impl SessionStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// `~/.cache/project-lint/sessions` (or the platform equivalent).
+    pub fn default_dir() -> PathBuf {
+        dirs::cache_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("project-lint")
+            .join("sessions")
+    }
+
+    /// Queue findings for the session's next event.
+    pub fn push(&self, session_id: &str, findings: &[PendingFinding]) -> Result<()> {
+        if findings.is_empty() {
+            return Ok(());
+        }
+        fs::create_dir_all(&self.dir)?;
+        let mut lines = String::new();
+        for finding in findings {
+            lines.push_str(&serde_json::to_string(finding)?);
+            lines.push('\n');
+        }
+        // One append per batch so concurrent writers do not interleave lines
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.queue_path(session_id))?
+            .write_all(lines.as_bytes())?;
+        Ok(())
+    }
+
+    /// Remove and return everything queued for the session.
+    pub fn take(&self, session_id: &str) -> Result<Vec<PendingFinding>> {
+        let queue = self.queue_path(session_id);
+        // Move the queue aside first; findings pushed meanwhile start a new one
+        let claimed = queue.with_extension(format!("taken-{}", std::process::id()));
+        if fs::rename(&queue, &claimed).is_err() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(&claimed)?;
+        fs::remove_file(&claimed)?;
+        Ok(content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+
+    /// The session's state; empty for a session not seen yet.
+    pub fn state(&self, session_id: &str) -> Result<SessionState> {
+        let mut file = match File::open(self.state_path(session_id)) {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(SessionState::default()),
+            Err(e) => return Err(e.into()),
+        };
+        file.lock_shared()?;
+        let mut content = String::new();
+        file.read_to_string(&mut content)?;
+        Ok(serde_json::from_str(&content).unwrap_or_default())
+    }
+
+    /// Read, change and write back the session's state. The file stays
+    /// locked throughout, so concurrent hooks of a session do not lose
+    /// each other's updates.
+    pub fn update_state<T>(
+        &self,
+        session_id: &str,
+        update: impl FnOnce(&mut SessionState) -> T,
+    ) -> Result<T> {
+        fs::create_dir_all(&self.dir)?;
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(self.state_path(session_id))?;
+        file.lock()?;
+        let mut content = String::new();
+        file.read_to_string(&mut content)?;
+        let mut state: SessionState = serde_json::from_str(&content).unwrap_or_default();
+        let output = update(&mut state);
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(serde_json::to_string(&state)?.as_bytes())?;
+        Ok(output)
+    }
+
+    /// Drop the session's state and queued findings.
+    pub fn end(&self, session_id: &str) -> Result<()> {
+        for path in [self.state_path(session_id), self.queue_path(session_id)] {
+            match fs::remove_file(path) {
+                Err(e) if e.kind() != ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Drop sessions that ended without a `SessionEnd` event: those not
+    /// updated for `max_age`.
+    pub fn prune(&self, max_age: Duration) -> Result<()> {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return Ok(());
+        };
+        let now = SystemTime::now();
+        for entry in entries.flatten() {
+            let stale = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .is_ok_and(|modified| now.duration_since(modified).unwrap_or_default() > max_age);
+            if stale {
+                fs::remove_file(entry.path())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn queue_path(&self, session_id: &str) -> PathBuf {
+        self.path(session_id, "pending.jsonl")
+    }
+
+    fn state_path(&self, session_id: &str) -> PathBuf {
+        self.path(session_id, "state.json")
+    }
+
+    fn path(&self, session_id: &str, extension: &str) -> PathBuf {
+        let safe: String = session_id
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' | '.' => c,
+                _ => '_',
+            })
+            .collect();
+        self.dir.join(format!("{}.{}", safe, extension))
+    }
+}
+
+/// Add findings of earlier async evaluations to a hook result as warnings.
+/// They never block: an `Allow` becomes `Warn`, other decisions stay.
+pub fn report_pending(result: &mut HookResult, findings: &[PendingFinding]) {
+    if findings.is_empty() {
+        return;
+    }
+    let mut message = result
+        .message
+        .take()
+        .unwrap_or_else(|| String::from("Project Lint violations detected:\n"));
+    for finding in findings {
+        message.push_str(&format!(
+            "⚠️ {} (async): {}\n",
+            finding.name, finding.message
+        ));
+    }
+    result.message = Some(message);
+    if result.decision == Decision::Allow {
+        result.decision = Decision::Warn;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn queues_and_drains_findings_per_session() -> Result<()> {
+        let dir = TempDir::new()?;
+        let store = SessionStore::new(dir.path().to_path_buf());
+        let finding = PendingFinding {
+            name: "slow-scan".to_string(),
+            message: "found something".to_string(),
+            severity: RuleSeverity::Error,
+        };
+        store.push("s/1", std::slice::from_ref(&finding))?;
+        store.push("s/1", std::slice::from_ref(&finding))?;
+        store.push("other", std::slice::from_ref(&finding))?;
+
+        assert_eq!(store.take("s/1")?.len(), 2);
+        assert!(store.take("s/1")?.is_empty());
+
+        let mut result = HookResult::default();
+        report_pending(&mut result, &store.take("other")?);
+        assert_eq!(result.decision, Decision::Warn);
+        assert!(result
+            .message
+            .unwrap()
+            .contains("⚠️ slow-scan (async): found something"));
+        Ok(())
+    }
+
+    #[test]
+    fn tracks_session_state_until_the_session_ends() -> Result<()> {
+        use crate::config::SessionConfig;
+        use crate::hooks::{EventContext, FileEdit};
+
+        let dir = TempDir::new()?;
+        let store = SessionStore::new(dir.path().to_path_buf());
+        let tests = SessionConfig::default().test_commands;
+        let event = |tool_name: &str, file: Option<&str>, command: Option<&str>| ProjectLintEvent {
+            event_type: EventType::PreToolUse,
+            session_id: Some("s-1".to_string()),
+            timestamp: None,
+            cwd: None,
+            context: EventContext {
+                tool_name: Some(tool_name.to_string()),
+                file_path: file.map(PathBuf::from),
+                edits: (tool_name == "Edit").then(|| {
+                    vec![FileEdit {
+                        old_string: None,
+                        new_string: "x".to_string(),
+                        start_line: None,
+                        end_line: None,
+                    }]
+                }),
+                command: command.map(str::to_string),
+                ide_source: "claude".to_string(),
+                ..Default::default()
+            },
+        };
+        let record = |event: &ProjectLintEvent, decision: Decision| {
+            store.update_state("s-1", |state| state.record(event, &decision, &tests))
+        };
+
+        record(&event("Read", Some("a.rs"), None), Decision::Allow)?;
+        record(&event("Edit", Some("a.rs"), None), Decision::Allow)?;
+        record(&event("Edit", Some("b.rs"), None), Decision::Allow)?;
+        record(&event("Edit", Some("c.rs"), None), Decision::Deny)?;
+        // The post hook of an answered write does not count it again
+        let mut post_edit = event("Edit", Some("d.rs"), None);
+        post_edit.event_type = EventType::PostToolUse;
+        record(&post_edit, Decision::Allow)?;
+        let state = store.state("s-1")?;
+        assert_eq!(state.events, 5);
+        assert_eq!((state.denied, state.denied_writes), (1, 1));
+        assert_eq!(state.files_read.len(), 1);
+        assert_eq!(state.to_value()["untested_writes"], 2);
+
+        record(
+            &event("Bash", None, Some("cd app && cargo test --all")),
+            Decision::Allow,
+        )?;
+        let state = store.state("s-1")?;
+        assert_eq!(state.test_runs, 1);
+        assert!(state.untested_files.is_empty());
+        assert_eq!(state.files_written.len(), 2);
+
+        store.push(
+            "s-1",
+            &[PendingFinding {
+                name: "slow-scan".to_string(),
+                message: "found something".to_string(),
+                severity: RuleSeverity::Warning,
+            }],
+        )?;
+        store.end("s-1")?;
+        assert_eq!(store.state("s-1")?, SessionState::default());
+        assert!(store.take("s-1")?.is_empty());
+        Ok(())
+    }
+}
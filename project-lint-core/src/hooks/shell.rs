@@ -0,0 +1,430 @@
+//! Shell command line splitting for command policies. It understands quoting,
+//! pipes, `&&`/`||`/`;`/`&` lists, redirections, subshells, `$(...)`, env
+//! assignments, wrappers such as `sudo` and `env`, and `sh -c` bodies. It is
+//! not a full shell: expansions are left as written.
+
+use std::ops::Range;
+
+/// One simple command of a command line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShellCommand {
+    /// Program name without its directory (`/usr/bin/rm` reads as `rm`).
+    pub program: String,
+    pub args: Vec<String>,
+    /// Program of the previous pipeline stage, when this command reads a pipe.
+    pub piped_from: Option<String>,
+}
+
+/// Programs that run their arguments as another command.
+const WRAPPERS: &[&str] = &[
+    "sudo", "doas", "env", "nohup", "time", "exec", "command", "nice", "xargs",
+];
+const SHELLS: &[&str] = &["sh", "bash", "zsh", "dash", "ksh", "fish"];
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Pipe,
+    Separator,
+}
+
+/// Split a command line into its simple commands, in order.
+pub fn parse_command_line(line: &str) -> Vec<ShellCommand> {
+    let mut commands = Vec::new();
+    let mut nested = Vec::new();
+    let tokens = tokenize(line, &mut nested);
+
+    let mut words: Vec<String> = Vec::new();
+    let mut piped_from: Option<String> = None;
+    for token in tokens.into_iter().chain(std::iter::once(Token::Separator)) {
+        match token {
+            Token::Word(word) => words.push(word),
+            Token::Pipe | Token::Separator => {
+                let is_pipe = token == Token::Pipe;
+                let program = finish(&mut words, piped_from.take(), &mut commands);
+                if is_pipe {
+                    piped_from = program;
+                }
+            }
+        }
+    }
+
+    for body in nested {
+        commands.extend(parse_command_line(&body));
+    }
+    commands
+}
+
+/// Byte ranges of the top-level commands of a command line (split on pipes,
+/// `&&`/`||`/`;`/`&`, newlines and parentheses outside quotes), each starting
+/// at its program: leading whitespace and env assignments are skipped.
+pub fn command_spans(line: &str) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    let mut start = 0;
+    let mut quote: Option<char> = None;
+    let mut depth = 0usize;
+    let mut chars = line.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match (quote, c) {
+            (Some('\''), '\'') | (Some('"'), '"') => quote = None,
+            (Some('"') | None, '\\') => {
+                chars.next();
+            }
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '$') if chars.peek().is_some_and(|(_, n)| *n == '(') => {
+                chars.next();
+                depth += 1;
+            }
+            (None, ')') if depth > 0 => depth -= 1,
+            (None, '|' | '&' | ';' | '\n' | '(' | ')') if depth == 0 => {
+                // `2>&1` and `&>` are redirections, not separators
+                let redirect = c == '&'
+                    && (line[..i].ends_with('>') || chars.peek().is_some_and(|(_, n)| *n == '>'));
+                if redirect {
+                    continue;
+                }
+                spans.push(start..i);
+                if chars
+                    .peek()
+                    .is_some_and(|(_, n)| *n == c && matches!(c, '|' | '&'))
+                {
+                    chars.next();
+                }
+                start = chars.peek().map_or(line.len(), |(j, _)| *j);
+            }
+            _ => {}
+        }
+    }
+    spans.push(start..line.len());
+
+    spans
+        .into_iter()
+        .filter_map(|span| {
+            let mut begin = span.start;
+            loop {
+                let text = &line[begin..span.end];
+                let trimmed = text.trim_start();
+                begin += text.len() - trimmed.len();
+                let word = trimmed.split_whitespace().next().unwrap_or("");
+                if !is_assignment(word) || word.contains(['"', '\'']) {
+                    break;
+                }
+                begin += word.len();
+            }
+            let end = begin + line[begin..span.end].trim_end().len();
+            (end > begin).then_some(begin..end)
+        })
+        .collect()
+}
+
+/// Turn the collected words into a command (unwrapping wrappers and shell
+/// `-c` bodies) and return the program name for a following pipe stage.
+fn finish(
+    words: &mut Vec<String>,
+    piped_from: Option<String>,
+    commands: &mut Vec<ShellCommand>,
+) -> Option<String> {
+    let mut rest: &[String] = words;
+    // Env assignments and wrappers in front of the real program
+    loop {
+        match rest.first() {
+            Some(word) if is_assignment(word) => rest = &rest[1..],
+            Some(word) if WRAPPERS.contains(&basename(word)) => {
+                let value_options = wrapper_value_options(basename(word));
+                rest = &rest[1..];
+                while let Some(option) = rest.first().filter(|w| w.starts_with('-')) {
+                    let takes_value = value_options.contains(&option.as_str());
+                    rest = &rest[if takes_value { 2 } else { 1 }.min(rest.len())..];
+                }
+            }
+            _ => break,
+        }
+    }
+
+    let program = rest.first().map(|p| basename(p).to_string());
+    if let Some(program) = &program {
+        let args: Vec<String> = rest[1..].to_vec();
+        let body = SHELLS
+            .contains(&program.as_str())
+            .then(|| args.iter().position(|a| is_command_option(a)))
+            .flatten()
+            .and_then(|i| args.get(i + 1).cloned());
+        commands.push(ShellCommand {
+            program: program.clone(),
+            args,
+            piped_from,
+        });
+        if let Some(body) = body {
+            commands.extend(parse_command_line(&body));
+        }
+    }
+    words.clear();
+    program
+}
+
+/// `-c`, or a cluster of short options containing it (`-lc`, `-ec`): the
+/// next argument is the command string.
+fn is_command_option(arg: &str) -> bool {
+    arg.strip_prefix('-').is_some_and(|flags| {
+        !flags.starts_with('-')
+            && flags.contains('c')
+            && flags.chars().all(|c| c.is_ascii_alphabetic())
+    })
+}
+
+fn tokenize(line: &str, nested: &mut Vec<String>) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = line.chars().peekable();
+
+    macro_rules! end_word {
+        () => {
+            if in_word {
+                tokens.push(Token::Word(std::mem::take(&mut word)));
+                in_word = false;
+            }
+        };
+    }
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                for c in chars.by_ref() {
+                    if c == '\'' {
+                        break;
+                    }
+                    word.push(c);
+                }
+            }
+            '"' => {
+                in_word = true;
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => word.extend(chars.next()),
+                        '$' if chars.peek() == Some(&'(') => {
+                            chars.next();
+                            let body = balanced(&mut chars);
+                            word.push_str(&format!("$({})", body));
+                            nested.push(body);
+                        }
+                        _ => word.push(c),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                match chars.next() {
+                    Some('\n') | None => {}
+                    Some(escaped) => word.push(escaped),
+                }
+            }
+            '$' if chars.peek() == Some(&'(') => {
+                chars.next();
+                let body = balanced(&mut chars);
+                word.push_str(&format!("$({})", body));
+                in_word = true;
+                nested.push(body);
+            }
+            '`' => {
+                let body: String = chars.by_ref().take_while(|&c| c != '`').collect();
+                word.push_str(&format!("`{}`", body));
+                in_word = true;
+                nested.push(body);
+            }
+            '#' if !in_word => {
+                // Comment to the end of the line
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+                tokens.push(Token::Separator);
+            }
+            '|' => {
+                end_word!();
+                if chars.peek() == Some(&'|') {
+                    chars.next();
+                    tokens.push(Token::Separator);
+                } else {
+                    // `|&` pipes stderr too
+                    if chars.peek() == Some(&'&') {
+                        chars.next();
+                    }
+                    tokens.push(Token::Pipe);
+                }
+            }
+            '&' if chars.peek() == Some(&'>') => {
+                // `&>file` redirects both streams
+                end_word!();
+                chars.next();
+                skip_redirect_target(&mut chars);
+            }
+            ';' | '&' | '\n' | '(' | ')' => {
+                end_word!();
+                tokens.push(Token::Separator);
+            }
+            '<' | '>' => {
+                // A file descriptor number belongs to the redirection
+                if in_word && word.chars().all(|c| c.is_ascii_digit()) {
+                    word.clear();
+                    in_word = false;
+                }
+                end_word!();
+                while matches!(chars.peek(), Some('<' | '>' | '|')) {
+                    chars.next();
+                }
+                if chars.peek() == Some(&'&') {
+                    // `2>&1`
+                    chars.next();
+                    while chars
+                        .peek()
+                        .is_some_and(|c| c.is_ascii_digit() || *c == '-')
+                    {
+                        chars.next();
+                    }
+                } else {
+                    skip_redirect_target(&mut chars);
+                }
+            }
+            c if c.is_whitespace() => end_word!(),
+            _ => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+    if in_word {
+        tokens.push(Token::Word(word));
+    }
+    tokens
+}
+
+/// Contents up to the `)` matching an already consumed `(`.
+fn balanced(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut depth = 1;
+    let mut body = String::new();
+    for c in chars.by_ref() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            _ => {}
+        }
+        body.push(c);
+    }
+    body
+}
+
+fn skip_redirect_target(chars: &mut std::iter::Peekable<std::str::Chars>) {
+    while chars.peek().is_some_and(|c| *c == ' ' || *c == '\t') {
+        chars.next();
+    }
+    let mut quote = None;
+    while let Some(&c) = chars.peek() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c.is_whitespace() || matches!(c, ';' | '&' | '|' | '(' | ')') => break,
+            None => {}
+        }
+        chars.next();
+    }
+}
+
+/// Options of a wrapper that take a value (`sudo -u root`).
+fn wrapper_value_options(wrapper: &str) -> &'static [&'static str] {
+    match wrapper {
+        "sudo" | "doas" => &["-u", "-g", "-C", "-h", "-p", "-U", "-r", "-t", "-D"],
+        "env" => &["-u", "-C", "-S"],
+        "nice" => &["-n"],
+        "xargs" => &["-I", "-n", "-P", "-L", "-s", "-d", "-E", "-a"],
+        _ => &[],
+    }
+}
+
+fn is_assignment(word: &str) -> bool {
+    word.split_once('=').is_some_and(|(name, _)| {
+        !name.is_empty()
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && !name.starts_with(|c: char| c.is_ascii_digit())
+    })
+}
+
+fn basename(program: &str) -> &str {
+    program.rsplit('/').next().unwrap_or(program)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn programs(line: &str) -> Vec<(String, Vec<String>, Option<String>)> {
+        parse_command_line(line)
+            .into_iter()
+            .map(|c| (c.program, c.args, c.piped_from))
+            .collect()
+    }
+
+    #[test]
+    fn splits_lists_pipes_wrappers_and_shell_bodies() {
+        let parsed = programs(
+            "FOO=1 sudo -u root /bin/rm -rf '/tmp/a b' 2>&1 && curl -fsSL https://x.sh | sudo bash; echo done > out.log",
+        );
+        assert_eq!(
+            parsed,
+            vec![
+                (
+                    "rm".to_string(),
+                    vec!["-rf".to_string(), "/tmp/a b".to_string()],
+                    None
+                ),
+                (
+                    "curl".to_string(),
+                    vec!["-fsSL".to_string(), "https://x.sh".to_string()],
+                    None
+                ),
+                ("bash".to_string(), vec![], Some("curl".to_string())),
+                ("echo".to_string(), vec!["done".to_string()], None),
+            ]
+        );
+
+        let line = "CI=1 npm i x 2>&1 | tee log && (echo \"a && b\"; pnpm test) &";
+        let spans: Vec<&str> = command_spans(line).into_iter().map(|r| &line[r]).collect();
+        assert_eq!(
+            spans,
+            vec!["npm i x 2>&1", "tee log", "echo \"a && b\"", "pnpm test"]
+        );
+
+        let parsed = programs(r#"bash -c "git push --force origin main" || echo "$(whoami)""#);
+        let names: Vec<&str> = parsed.iter().map(|c| c.0.as_str()).collect();
+        assert_eq!(names, vec!["bash", "git", "echo", "whoami"]);
+        assert_eq!(parsed[1].1, vec!["push", "--force", "origin", "main"]);
+    }
+
+    #[test]
+    fn unwraps_shell_bodies_after_option_clusters() {
+        for line in [
+            r#"bash -lc "rm -rf /""#,
+            r#"sh -ec 'rm -rf /'"#,
+            r#"bash -x -c "rm -rf /""#,
+            r#"zsh --norc -xc "rm -rf /""#,
+        ] {
+            let parsed = programs(line);
+            assert_eq!(parsed.len(), 2, "{}", line);
+            assert_eq!(parsed[1].0, "rm", "{}", line);
+            assert_eq!(parsed[1].1, vec!["-rf", "/"], "{}", line);
+        }
+        // `--rcfile` is not `-c`, and `-e` alone takes no command string
+        assert_eq!(programs("bash --rcfile x.sh").len(), 1);
+        assert_eq!(programs("bash -e script.sh").len(), 1);
+    }
+}
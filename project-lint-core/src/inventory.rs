@@ -0,0 +1,762 @@
+//! Project inventory for `project-lint init`: which languages, package
+//! managers, container, CI and dev-environment tooling a repository uses, and
+//! the starter configuration (checks, profiles, scanner sections) that fits.
+
+use crate::config::{
+    DevEnvironmentFilesConfig, DevOpsSpecificConfig, DockerfileSecurityConfig,
+    PackageManagerEnforcementConfig, Profile, ProfileActivation, ProfileChecks, ProfileEnable,
+    ProfileMetadata, RulesConfig, RulesMode, RustFileNamingConfig, RustSecurityConfig,
+    ScannerConfig, TerraformConfig, TypescriptMonorepoConfig, CURRENT_CONFIG_VERSION,
+};
+use crate::scanners::compose::{is_compose_file, COMPOSE_GLOBS};
+use crate::utils::Result;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// How deep below the project root marker files are looked for.
+const INVENTORY_MAX_DEPTH: usize = 4;
+
+/// Directories never descended into while taking the inventory.
+const INVENTORY_SKIP_DIRS: &[&str] = &[
+    ".git",
+    "node_modules",
+    "target",
+    "dist",
+    "build",
+    ".venv",
+    "vendor",
+];
+
+/// JavaScript package managers and the lockfile each one owns.
+const JS_PACKAGE_MANAGERS: &[(&str, &str)] = &[
+    ("npm", "package-lock.json"),
+    ("pnpm", "pnpm-lock.yaml"),
+    ("yarn", "yarn.lock"),
+    ("bun", "bun.lock"),
+];
+
+/// Names accepted by `project-lint init --preset`.
+pub const PRESETS: &[&str] = &[
+    "rust",
+    "typescript",
+    "pnpm-monorepo",
+    "python",
+    "go",
+    "terraform",
+    "container",
+    "minimal",
+];
+
+/// Checks every generated config enables regardless of stack.
+const BASE_CHECKS: &[&str] = &["file_naming", "security_analysis", "custom_rules"];
+
+/// What `init` found in a repository.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectInventory {
+    /// `rust`, `typescript`, `javascript`, `python`, `go`, `terraform`.
+    pub languages: BTreeSet<String>,
+    /// `cargo`, `npm`, `pnpm`, `yarn`, `bun`, `pip`, `poetry`, `uv`, `go`.
+    pub package_managers: BTreeSet<String>,
+    /// Dockerfiles, relative to the project root.
+    pub dockerfiles: Vec<String>,
+    /// docker-compose manifests, relative to the project root.
+    pub compose_files: Vec<String>,
+    /// `github-actions`, `gitlab-ci`, `circleci`, `jenkins`, `azure-pipelines`.
+    pub ci_systems: BTreeSet<String>,
+    /// Root-level dev tooling: `just`, `make`, `devbox`, `nix`, `direnv`.
+    pub dev_tools: BTreeSet<String>,
+    /// Cargo/pnpm workspace, turbo, nx or lerna markers were found.
+    pub monorepo: bool,
+}
+
+/// A group of checks, a profile and scanner sections generated together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stack {
+    Rust,
+    TypeScript,
+    Python,
+    Go,
+    Terraform,
+    Containers,
+    Ci,
+    DevEnvironment,
+}
+
+impl Stack {
+    /// Profile name written to `rules/profiles/<name>.toml`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Stack::Rust => "rust",
+            Stack::TypeScript => "typescript",
+            Stack::Python => "python",
+            Stack::Go => "go",
+            Stack::Terraform => "terraform",
+            Stack::Containers => "containers",
+            Stack::Ci => "ci",
+            Stack::DevEnvironment => "dev-environment",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Stack::Rust => "Rust conventions and AST checks when Cargo.toml is present.",
+            Stack::TypeScript => "TypeScript/JavaScript checks when package.json is present.",
+            Stack::Python => "Python AST checks when Python project files are present.",
+            Stack::Go => "Secret sourcing checks for Go sources when go.mod is present.",
+            Stack::Terraform => "Terraform validation and fmt checks when .tf files are present.",
+            Stack::Containers => "Dockerfile lint when Dockerfiles or compose files are present.",
+            Stack::Ci => "CI/CD parity checks when CI workflows are present.",
+            Stack::DevEnvironment => "Developer tooling checks (justfile, devbox, direnv).",
+        }
+    }
+}
+
+/// Generated configuration: the `config.toml` contents plus one profile per
+/// selected [`Stack`].
+#[derive(Debug, Clone)]
+pub struct StarterConfig {
+    pub summary: Vec<String>,
+    pub rules: RulesConfig,
+    pub scanner_config: ScannerConfig,
+    pub profiles: Vec<Profile>,
+}
+
+impl ProjectInventory {
+    /// Walk `root` (a few levels deep, skipping build and dependency
+    /// directories) and record the marker files found.
+    pub fn detect(root: &Path) -> Self {
+        let mut inventory = Self::default();
+
+        let walker = WalkDir::new(root)
+            .max_depth(INVENTORY_MAX_DEPTH)
+            .into_iter()
+            .filter_entry(|e| {
+                e.depth() == 0
+                    || !INVENTORY_SKIP_DIRS.contains(&e.file_name().to_string_lossy().as_ref())
+            });
+        for entry in walker
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            let rel = path
+                .strip_prefix(root)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            let name = entry.file_name().to_string_lossy();
+            inventory.record(path, &rel, &name, entry.depth() == 1);
+        }
+
+        // A package.json without a lockfile is an npm project
+        let has_js = inventory.languages.contains("javascript")
+            || inventory.languages.contains("typescript");
+        if has_js && inventory.js_package_managers().is_empty() {
+            inventory.package_managers.insert("npm".to_string());
+        }
+
+        inventory.dockerfiles.sort();
+        inventory.compose_files.sort();
+        inventory
+    }
+
+    /// A synthetic inventory for `--preset`, or `None` for an unknown name.
+    pub fn preset(name: &str) -> Option<Self> {
+        let mut inventory = Self::default();
+        match name {
+            "rust" => inventory.add("rust", "cargo"),
+            "typescript" => inventory.add("typescript", "npm"),
+            "pnpm-monorepo" => {
+                inventory.add("typescript", "pnpm");
+                inventory.monorepo = true;
+            }
+            "python" => inventory.add("python", "pip"),
+            "go" => inventory.add("go", "go"),
+            "terraform" => {
+                inventory.languages.insert("terraform".to_string());
+            }
+            "container" => {
+                inventory.dockerfiles.push("Dockerfile".to_string());
+                inventory
+                    .compose_files
+                    .push("docker-compose.yml".to_string());
+            }
+            "minimal" => {}
+            _ => return None,
+        }
+        Some(inventory)
+    }
+
+    /// One human-readable line per non-empty category.
+    pub fn summary(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        let mut push = |label: &str, items: Vec<String>| {
+            if !items.is_empty() {
+                lines.push(format!("{}: {}", label, items.join(", ")));
+            }
+        };
+        push("languages", self.languages.iter().cloned().collect());
+        push(
+            "package managers",
+            self.package_managers.iter().cloned().collect(),
+        );
+        push("dockerfiles", self.dockerfiles.clone());
+        push("compose files", self.compose_files.clone());
+        push("ci", self.ci_systems.iter().cloned().collect());
+        push("dev tooling", self.dev_tools.iter().cloned().collect());
+        if self.monorepo {
+            lines.push("layout: monorepo".to_string());
+        }
+        lines
+    }
+
+    /// Stacks this inventory calls for, in a stable order.
+    pub fn stacks(&self) -> Vec<Stack> {
+        let mut stacks = Vec::new();
+        if self.languages.contains("rust") {
+            stacks.push(Stack::Rust);
+        }
+        if self.languages.contains("typescript") || self.languages.contains("javascript") {
+            stacks.push(Stack::TypeScript);
+        }
+        if self.languages.contains("python") {
+            stacks.push(Stack::Python);
+        }
+        if self.languages.contains("go") {
+            stacks.push(Stack::Go);
+        }
+        if self.languages.contains("terraform") {
+            stacks.push(Stack::Terraform);
+        }
+        if !self.dockerfiles.is_empty() || !self.compose_files.is_empty() {
+            stacks.push(Stack::Containers);
+        }
+        if !self.ci_systems.is_empty() {
+            stacks.push(Stack::Ci);
+        }
+        if ["just", "devbox", "nix", "direnv"]
+            .iter()
+            .any(|t| self.dev_tools.contains(*t))
+        {
+            stacks.push(Stack::DevEnvironment);
+        }
+        stacks
+    }
+
+    /// Build the starter configuration for the selected `stacks`.
+    pub fn starter_config(&self, stacks: &[Stack]) -> StarterConfig {
+        let rules = RulesConfig {
+            mode: RulesMode::Allowlist,
+            enabled_checks: BASE_CHECKS.iter().map(|c| c.to_string()).collect(),
+            ..RulesConfig::default()
+        };
+        let mut scanner_config = ScannerConfig::default();
+        let mut profiles = Vec::new();
+
+        for stack in stacks {
+            let mut activation = empty_activation();
+            let mut checks = Vec::new();
+            let mut devops_specific = None;
+            match stack {
+                Stack::Rust => {
+                    activation.indicators = vec!["Cargo.toml".to_string()];
+                    checks.extend(["rust_conventions", "ast_analysis"]);
+                    scanner_config.rust_file_naming = Some(RustFileNamingConfig {
+                        required_files: vec!["Cargo.toml".to_string()],
+                        ..RustFileNamingConfig::default()
+                    });
+                    scanner_config.rust_security = Some(RustSecurityConfig {
+                        ban_unwrap_in_lib: true,
+                        ban_unsafe_blocks: true,
+                        forbidden_crates: Vec::new(),
+                    });
+                }
+                Stack::TypeScript => {
+                    activation.indicators =
+                        vec!["package.json".to_string(), "tsconfig.json".to_string()];
+                    checks.extend(["typescript_analysis", "ast_analysis"]);
+                    if self.languages.contains("typescript") {
+                        if self.monorepo {
+                            checks.push("typescript_monorepo");
+                        }
+                        scanner_config.typescript_monorepo = Some(TypescriptMonorepoConfig {
+                            catalog_mode: self.monorepo && self.package_managers.contains("pnpm"),
+                            path_aliases: Vec::new(),
+                            allowed_extensions: vec![".ts".to_string(), ".tsx".to_string()],
+                        });
+                    }
+                    if let [manager] = self.js_package_managers().as_slice() {
+                        scanner_config.package_manager_enforcement =
+                            Some(package_manager_enforcement(manager));
+                    }
+                }
+                Stack::Python => {
+                    activation.indicators = vec![
+                        "pyproject.toml".to_string(),
+                        "requirements.txt".to_string(),
+                        "setup.py".to_string(),
+                    ];
+                    checks.push("ast_analysis");
+                }
+                Stack::Go => {
+                    activation.indicators = vec!["go.mod".to_string()];
+                    checks.push("vault_security");
+                }
+                Stack::Terraform => {
+                    activation.globs = vec!["**/*.tf".to_string()];
+                    checks.push("terraform");
+                    // The terraform check also needs the profile's toggle
+                    devops_specific = Some(DevOpsSpecificConfig {
+                        check_secrets: false,
+                        validate_yaml: false,
+                        check_docker_best_practices: false,
+                        validate_terraform: true,
+                        check_kubernetes_manifests: false,
+                        scan_for_hardcoded_secrets: false,
+                        check_ssl_certificates: false,
+                    });
+                    scanner_config.terraform = Some(TerraformConfig::default());
+                }
+                Stack::Containers => {
+                    activation.indicators = self
+                        .dockerfiles
+                        .iter()
+                        .chain(&self.compose_files)
+                        .filter(|f| !f.contains('/'))
+                        .cloned()
+                        .collect();
+                    activation.globs = std::iter::once("**/Dockerfile*")
+                        .chain(COMPOSE_GLOBS.iter().copied())
+                        .map(str::to_string)
+                        .collect();
+                    if !self.dockerfiles.is_empty() {
+                        checks.push("dockerfile_lint");
+                    }
+                    if !self.compose_files.is_empty() {
+                        checks.push("compose_lint");
+                    }
+                    scanner_config.dockerfile_security = Some(DockerfileSecurityConfig {
+                        require_pinned_digests: true,
+                        require_non_root_user: true,
+                        forbid_copy_dot: true,
+                        require_no_new_privileges: true,
+                        require_healthcheck: true,
+                    });
+                }
+                Stack::Ci => {
+                    activation.paths = self
+                        .ci_systems
+                        .iter()
+                        .filter_map(|ci| ci_marker(ci))
+                        .map(|p| p.to_string())
+                        .collect();
+                    if self.ci_systems.contains("github-actions") {
+                        checks.push("ci_cd_parity");
+                    }
+                }
+                Stack::DevEnvironment => {
+                    let present: Vec<String> = [
+                        ("devbox", "devbox.json"),
+                        ("direnv", ".envrc"),
+                        ("just", "justfile"),
+                    ]
+                    .iter()
+                    .filter(|(tool, _)| self.dev_tools.contains(*tool))
+                    .map(|(_, file)| file.to_string())
+                    .collect();
+                    activation.indicators = present.clone();
+                    checks.push("dev_environment");
+                    let forbidden =
+                        if self.dev_tools.contains("just") && !self.dev_tools.contains("make") {
+                            vec!["Makefile".to_string()]
+                        } else {
+                            Vec::new()
+                        };
+                    scanner_config.dev_environment_files = Some(DevEnvironmentFilesConfig {
+                        required_files: present,
+                        forbidden_files: forbidden,
+                    });
+                }
+            }
+            let mut profile = stack_profile(*stack, activation, &checks);
+            profile.devops_specific = devops_specific;
+            profiles.push(profile);
+        }
+
+        StarterConfig {
+            summary: self.summary(),
+            rules,
+            scanner_config,
+            profiles,
+        }
+    }
+
+    fn record(&mut self, path: &Path, rel: &str, name: &str, at_root: bool) {
+        match name {
+            "Cargo.toml" => {
+                self.add("rust", "cargo");
+                if at_root && read_contains(path, "[workspace]") {
+                    self.monorepo = true;
+                }
+            }
+            "package.json" => {
+                self.languages.insert("javascript".to_string());
+            }
+            "tsconfig.json" => {
+                self.languages.insert("typescript".to_string());
+            }
+            "pnpm-workspace.yaml" => {
+                self.package_managers.insert("pnpm".to_string());
+                self.monorepo = true;
+            }
+            "turbo.json" | "nx.json" | "lerna.json" => self.monorepo = true,
+            "bun.lockb" => {
+                self.package_managers.insert("bun".to_string());
+            }
+            "pyproject.toml" | "setup.py" => {
+                self.languages.insert("python".to_string());
+            }
+            "requirements.txt" => self.add("python", "pip"),
+            "poetry.lock" => self.add("python", "poetry"),
+            "uv.lock" => self.add("python", "uv"),
+            "go.mod" => self.add("go", "go"),
+            ".gitlab-ci.yml" => self.add_ci("gitlab-ci"),
+            "Jenkinsfile" => self.add_ci("jenkins"),
+            "azure-pipelines.yml" => self.add_ci("azure-pipelines"),
+            "justfile" | "Justfile" | ".justfile" if at_root => self.add_tool("just"),
+            "Makefile" if at_root => self.add_tool("make"),
+            "devbox.json" if at_root => self.add_tool("devbox"),
+            "flake.nix" | "shell.nix" | "default.nix" if at_root => self.add_tool("nix"),
+            ".envrc" if at_root => self.add_tool("direnv"),
+            _ => {}
+        }
+
+        if let Some((manager, _)) = JS_PACKAGE_MANAGERS.iter().find(|(_, lock)| *lock == name) {
+            self.package_managers.insert(manager.to_string());
+        }
+        if name.ends_with(".ts") || name.ends_with(".tsx") {
+            self.languages.insert("typescript".to_string());
+        }
+        if name.ends_with(".tf") {
+            self.languages.insert("terraform".to_string());
+        }
+        if name == "Dockerfile" || name.starts_with("Dockerfile.") || name.ends_with(".dockerfile")
+        {
+            self.dockerfiles.push(rel.to_string());
+        }
+        if is_compose_file(name) {
+            self.compose_files.push(rel.to_string());
+        }
+        if rel.starts_with(".github/workflows/")
+            && (rel.ends_with(".yml") || rel.ends_with(".yaml"))
+        {
+            self.add_ci("github-actions");
+        }
+        if rel == ".circleci/config.yml" {
+            self.add_ci("circleci");
+        }
+    }
+
+    fn add(&mut self, language: &str, package_manager: &str) {
+        self.languages.insert(language.to_string());
+        self.package_managers.insert(package_manager.to_string());
+    }
+
+    fn add_ci(&mut self, ci: &str) {
+        self.ci_systems.insert(ci.to_string());
+    }
+
+    fn add_tool(&mut self, tool: &str) {
+        self.dev_tools.insert(tool.to_string());
+    }
+
+    fn js_package_managers(&self) -> Vec<&str> {
+        JS_PACKAGE_MANAGERS
+            .iter()
+            .map(|(m, _)| *m)
+            .filter(|m| self.package_managers.contains(*m))
+            .collect()
+    }
+}
+
+impl StarterConfig {
+    /// Render `config.toml`: a header listing what was detected followed by
+    /// `version`, `[rules]` and any `[scanner_config.*]` sections.
+    pub fn config_toml(&self) -> Result<String> {
+        let mut table = toml::value::Table::new();
+        table.insert(
+            "version".to_string(),
+            toml::Value::Integer(CURRENT_CONFIG_VERSION as i64),
+        );
+        table.insert("rules".to_string(), toml::Value::try_from(&self.rules)?);
+        let scanner_config = toml::Value::try_from(&self.scanner_config)?;
+        if scanner_config.as_table().is_some_and(|t| !t.is_empty()) {
+            table.insert("scanner_config".to_string(), scanner_config);
+        }
+
+        let mut header = String::from("# Generated by `project-lint init`\n");
+        if self.summary.is_empty() {
+            header.push_str("# No languages or tooling detected\n");
+        } else {
+            header.push_str("# Detected:\n");
+            for line in &self.summary {
+                header.push_str(&format!("#   {}\n", line));
+            }
+        }
+        Ok(format!(
+            "{}\n{}",
+            header,
+            toml::to_string_pretty(&toml::Value::Table(table))?
+        ))
+    }
+
+    /// Write `config.toml` and `rules/profiles/<name>.toml` under
+    /// `config_dir`, returning the paths written.
+    pub fn write_to(&self, config_dir: &Path) -> Result<Vec<PathBuf>> {
+        let profiles_dir = config_dir.join("rules").join("profiles");
+        std::fs::create_dir_all(&profiles_dir)?;
+        std::fs::create_dir_all(config_dir.join("rules").join("active"))?;
+
+        let config_file = config_dir.join("config.toml");
+        std::fs::write(&config_file, self.config_toml()?)?;
+        let mut written = vec![config_file];
+
+        for profile in &self.profiles {
+            let path = profiles_dir.join(format!("{}.toml", profile.metadata.name));
+            let content = format!(
+                "# Generated by `project-lint init`\n\n{}",
+                toml::to_string_pretty(&toml::Value::try_from(profile)?)?
+            );
+            std::fs::write(&path, content)?;
+            written.push(path);
+        }
+
+        Ok(written)
+    }
+}
+
+fn empty_activation() -> ProfileActivation {
+    ProfileActivation {
+        paths: Vec::new(),
+        extensions: Vec::new(),
+        branches: Vec::new(),
+        indicators: Vec::new(),
+        globs: Vec::new(),
+        content: Vec::new(),
+        events: Vec::new(),
+        when: None,
+    }
+}
+
+fn stack_profile(stack: Stack, activation: ProfileActivation, checks: &[&str]) -> Profile {
+    let scope = match stack {
+        Stack::Rust | Stack::TypeScript | Stack::Python | Stack::Go => "language",
+        Stack::Terraform | Stack::Containers | Stack::Ci => "devops",
+        Stack::DevEnvironment => "general",
+    };
+    Profile {
+        metadata: ProfileMetadata {
+            name: stack.name().to_string(),
+            version: "1.0.0".to_string(),
+            scope: scope.to_string(),
+            updated: chrono::Local::now().format("%Y-%m-%d").to_string(),
+            description: stack.description().to_string(),
+            priority: 0,
+            conflicts_with: Vec::new(),
+            requires: Vec::new(),
+        },
+        activation,
+        enable: ProfileEnable {
+            domains: Vec::new(),
+            plugins: Vec::new(),
+        },
+        checks: Some(ProfileChecks {
+            enable: checks.iter().map(|c| c.to_string()).collect(),
+            disable: Vec::new(),
+            slices: None,
+        }),
+        web_specific: None,
+        devops_specific: None,
+        structure: None,
+        extensions: None,
+        commands: None,
+    }
+}
+
+fn package_manager_enforcement(manager: &str) -> PackageManagerEnforcementConfig {
+    PackageManagerEnforcementConfig {
+        allowed: vec![manager.to_string()],
+        forbidden: JS_PACKAGE_MANAGERS
+            .iter()
+            .map(|(m, _)| m.to_string())
+            .filter(|m| m != manager)
+            .collect(),
+        required_lockfile: JS_PACKAGE_MANAGERS
+            .iter()
+            .find(|(m, _)| *m == manager)
+            .map(|(_, lock)| lock.to_string()),
+    }
+}
+
+/// Path whose presence marks a CI system, used for profile activation.
+fn ci_marker(ci: &str) -> Option<&'static str> {
+    match ci {
+        "github-actions" => Some(".github/workflows"),
+        "gitlab-ci" => Some(".gitlab-ci.yml"),
+        "circleci" => Some(".circleci"),
+        "jenkins" => Some("Jenkinsfile"),
+        "azure-pipelines" => Some("azure-pipelines.yml"),
+        _ => None,
+    }
+}
+
+fn read_contains(path: &Path, needle: &str) -> bool {
+    std::fs::read_to_string(path)
+        .map(|c| c.contains(needle))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::profiles::get_active_profiles;
+    use tempfile::TempDir;
+
+    fn touch(root: &Path, rel: &str, content: &str) -> Result<()> {
+        let path = root.join(rel);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    #[test]
+    fn detects_rust_workspace_with_docker_and_ci() -> Result<()> {
+        let dir = TempDir::new()?;
+        let root = dir.path();
+        touch(root, "Cargo.toml", "[workspace]\nmembers = [\"core\"]\n")?;
+        touch(root, "core/Cargo.toml", "[package]\nname = \"core\"\n")?;
+        touch(root, "docker/Dockerfile", "FROM rust\n")?;
+        touch(root, ".github/workflows/ci.yml", "on: push\n")?;
+        touch(root, "justfile", "default:\n")?;
+        touch(root, "target/debug/package.json", "{}")?;
+
+        let inventory = ProjectInventory::detect(root);
+        assert_eq!(inventory.languages.iter().collect::<Vec<_>>(), vec!["rust"]);
+        assert!(inventory.package_managers.contains("cargo"));
+        assert!(inventory.monorepo);
+        assert_eq!(inventory.dockerfiles, vec!["docker/Dockerfile"]);
+        assert!(inventory.ci_systems.contains("github-actions"));
+        assert!(inventory.dev_tools.contains("just"));
+        assert_eq!(
+            inventory.stacks(),
+            vec![
+                Stack::Rust,
+                Stack::Containers,
+                Stack::Ci,
+                Stack::DevEnvironment
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn detects_pnpm_typescript_monorepo() -> Result<()> {
+        let dir = TempDir::new()?;
+        let root = dir.path();
+        touch(root, "package.json", "{}")?;
+        touch(root, "pnpm-lock.yaml", "")?;
+        touch(root, "pnpm-workspace.yaml", "packages: []\n")?;
+        touch(root, "packages/app/src/index.ts", "export {}\n")?;
+
+        let inventory = ProjectInventory::detect(root);
+        assert!(inventory.languages.contains("typescript"));
+        assert_eq!(inventory.js_package_managers(), vec!["pnpm"]);
+        assert!(inventory.monorepo);
+
+        let starter = inventory.starter_config(&inventory.stacks());
+        let ts = starter.scanner_config.typescript_monorepo.expect("ts");
+        assert!(ts.catalog_mode);
+        let pm = starter
+            .scanner_config
+            .package_manager_enforcement
+            .expect("pm");
+        assert_eq!(pm.allowed, vec!["pnpm"]);
+        assert_eq!(pm.required_lockfile.as_deref(), Some("pnpm-lock.yaml"));
+        let checks = &starter.profiles[0].checks.as_ref().expect("checks").enable;
+        assert!(checks.contains(&"typescript_monorepo".to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn rust_preset_starter_has_no_legacy_defaults() -> Result<()> {
+        let inventory = ProjectInventory::preset("rust").expect("preset");
+        let starter = inventory.starter_config(&inventory.stacks());
+        let dir = TempDir::new()?;
+        let written = starter.write_to(dir.path())?;
+        assert_eq!(written.len(), 2);
+
+        let config = Config::load_from_file(&dir.path().join("config.toml"))?;
+        assert_eq!(config.version, CURRENT_CONFIG_VERSION);
+        assert!(config.modular_rules.is_empty());
+        assert!(config.scanner_config.rust_file_naming.is_some());
+        assert!(config.scanner_config.typescript_monorepo.is_none());
+        assert_eq!(config.active_profiles.len(), 1);
+        assert!(config.is_check_enabled("file_naming"));
+        assert!(!config.is_check_enabled("typescript_analysis"));
+        Ok(())
+    }
+
+    #[test]
+    fn detects_go_and_terraform_stacks() -> Result<()> {
+        let dir = TempDir::new()?;
+        let root = dir.path();
+        touch(root, "go.mod", "module example.com/app\n")?;
+        touch(root, "infra/main.tf", "terraform {}\n")?;
+
+        let inventory = ProjectInventory::detect(root);
+        assert_eq!(inventory.stacks(), vec![Stack::Go, Stack::Terraform]);
+
+        let starter = inventory.starter_config(&inventory.stacks());
+        let written = starter.write_to(root.join(".config/project-lint").as_path())?;
+        assert_eq!(written.len(), 3);
+        assert!(starter.scanner_config.terraform.is_some());
+
+        let loaded = Config::load_from_file(&root.join(".config/project-lint/config.toml"))?;
+        let active = get_active_profiles(root, &loaded.active_profiles, None)?;
+        let config = loaded.with_active_profiles(active)?;
+        let names: Vec<&str> = config
+            .active_profiles
+            .iter()
+            .map(|p| p.metadata.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["go", "terraform"]);
+        assert!(config.is_check_enabled("vault_security"));
+        assert!(config.is_check_enabled("terraform"));
+        let terraform = config
+            .active_profiles
+            .iter()
+            .find_map(|p| p.devops_specific.as_ref())
+            .expect("terraform profile turns on its toggle");
+        assert!(terraform.validate_terraform);
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_preset_is_rejected() {
+        assert!(ProjectInventory::preset("cobol").is_none());
+        for name in PRESETS {
+            let inventory = ProjectInventory::preset(name).expect(name);
+            // Every preset but `minimal` yields at least one profile
+            assert_eq!(
+                inventory.stacks().is_empty(),
+                *name == "minimal",
+                "{}",
+                name
+            );
+        }
+    }
+}
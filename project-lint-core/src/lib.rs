@@ -1,16 +1,19 @@
 pub mod config;
+pub mod content_match;
 pub mod dependency_checker;
 pub mod hooks;
+pub mod inventory;
+pub mod migrate;
 pub mod profiles;
 pub mod scanners;
 pub mod utils;
 
 // Re-export main types for easier access
 pub use config::{
-    Config, CustomRule, DevEnvironmentFilesConfig, DirectoriesConfig, DockerfileSecurityConfig,
-    ExecutionMode, FilesConfig, GitConfig, GitRuleConfig, ModularRule,
-    PackageManagerEnforcementConfig, RuleConditions, RuleSeverity, RulesConfig,
-    RustFileNamingConfig, RustSecurityConfig, ScannerConfig, ScriptRuleConfig,
+    CommandAction, CommandRule, Config, ContentMatchMode, CustomRule, DevEnvironmentFilesConfig,
+    DirectoriesConfig, DockerfileSecurityConfig, ExecutionMode, FilesConfig, GitConfig,
+    GitRuleConfig, ModularRule, PackageManagerEnforcementConfig, RuleConditions, RuleSeverity,
+    RulesConfig, RustFileNamingConfig, RustSecurityConfig, ScannerConfig, ScriptRuleConfig,
     TypescriptMonorepoConfig, VaultSecurityConfig,
 };
 pub use hooks::{
@@ -20,6 +23,7 @@ pub use hooks::{
 pub use scanners::{
     ast::{ASTAnalyzer, ASTIssue},
     ci_cd_parity::CiCdParityScanner,
+    compose::ComposeScanner,
     dependency_version_checker::{DependencyIssue, DependencyVersionChecker, Severity},
     detection::{
         DetectionIssue, FunctionCallDetector, FunctionCallRule, PatternDetector, PatternRule,
@@ -28,10 +32,14 @@ pub use scanners::{
     dockerfile_lint::DockerfileLintScanner,
     file_naming::{FileNamingScanner, NamingIssue},
     git::{check_branch_allowed, get_git_info, GitInfo},
+    kubernetes::KubernetesScanner,
+    profile_structure::ProfileStructureScanner,
     rust_conventions::RustConventionsScanner,
     security::{SecurityRuleSet, SecurityScanner},
+    terraform::TerraformScanner,
     typescript::{TypeScriptRuleSet, TypeScriptScanner},
     typescript_monorepo::TypeScriptMonorepoScanner,
     vault_security::VaultSecurityScanner,
+    web::WebScanner,
     ScannerIssue,
 };
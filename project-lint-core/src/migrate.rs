@@ -0,0 +1,273 @@
+//! Config migrations. Version 1 configs carry legacy `[git]`, `[files]` and
+//! `[directories]` sections; migrating to version 2 writes equivalent modular
+//! rules under `rules/active/` and drops the sections from `config.toml`.
+
+use crate::config::{Config, ModularRule, CURRENT_CONFIG_VERSION};
+use crate::utils::Result;
+use std::path::{Path, PathBuf};
+
+/// Sections removed from `config.toml` by the version 2 migration.
+const LEGACY_SECTIONS: &[&str] = &["git", "files", "directories"];
+
+/// One file the migration creates or rewrites.
+#[derive(Debug, Clone)]
+pub struct PlannedFile {
+    pub path: PathBuf,
+    /// Current content, or `None` when the file is created.
+    pub original: Option<String>,
+    pub updated: String,
+}
+
+impl PlannedFile {
+    /// Unified-style line diff between the current and migrated content.
+    pub fn diff(&self) -> String {
+        let display = self.path.display();
+        let old = self.original.as_deref().unwrap_or("");
+        let mut out = match self.original {
+            Some(_) => format!("--- {}\n+++ {}\n", display, display),
+            None => format!("--- /dev/null\n+++ {}\n", display),
+        };
+        for line in line_diff(old, &self.updated) {
+            out.push_str(&line);
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// The files a migration would write. Empty when the config is current.
+#[derive(Debug, Clone, Default)]
+pub struct MigrationPlan {
+    pub from_version: u32,
+    pub files: Vec<PlannedFile>,
+    /// Legacy rules not written because a rule file of the same name exists.
+    pub skipped_rules: Vec<String>,
+}
+
+impl MigrationPlan {
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Write every planned file, creating directories as needed.
+    pub fn apply(&self) -> Result<()> {
+        for file in &self.files {
+            if let Some(parent) = file.path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&file.path, &file.updated)?;
+        }
+        Ok(())
+    }
+}
+
+/// Plan the migration of `config_dir/config.toml` to the current version.
+pub fn plan_migration(config_dir: &Path) -> Result<MigrationPlan> {
+    let config_file = config_dir.join("config.toml");
+    let original = std::fs::read_to_string(&config_file)
+        .map_err(|e| anyhow::anyhow!("Cannot read {}: {}", config_file.display(), e))?;
+    let config: Config = toml::from_str(&original)?;
+
+    let mut plan = MigrationPlan {
+        from_version: config.version,
+        ..Default::default()
+    };
+    if config.version >= CURRENT_CONFIG_VERSION {
+        return Ok(plan);
+    }
+
+    let rules_dir = config_dir.join("rules").join("active");
+    let existing = Config::load_modular_rules(&config_dir.to_path_buf())?;
+    for rule in config.legacy_rules() {
+        let path = rules_dir.join(format!("{}.toml", rule.name));
+        // A same-named rule already superseded the legacy section at runtime.
+        if path.exists() || existing.iter().any(|r| r.name == rule.name) {
+            plan.skipped_rules.push(rule.name.clone());
+            continue;
+        }
+        plan.files.push(PlannedFile {
+            path,
+            original: None,
+            updated: render_rule(&rule)?,
+        });
+    }
+
+    plan.files.push(PlannedFile {
+        path: config_file,
+        updated: migrate_config_toml(&original)?,
+        original: Some(original),
+    });
+
+    Ok(plan)
+}
+
+/// Drop the legacy sections and stamp the current version, keeping comments
+/// and formatting of everything else.
+fn migrate_config_toml(content: &str) -> Result<String> {
+    // Leading comments attach to the first table and would vanish with it.
+    let header: String = content
+        .lines()
+        .take_while(|l| l.trim_start().starts_with('#'))
+        .map(|l| format!("{}\n", l))
+        .collect();
+
+    let mut doc: toml_edit::DocumentMut = content.parse()?;
+    for section in LEGACY_SECTIONS {
+        doc.remove(section);
+    }
+    doc.remove("version");
+    let rest = doc.to_string();
+    let rest = rest.strip_prefix(header.as_str()).unwrap_or(&rest);
+
+    let mut out = header;
+    out.push_str(&format!("version = {}\n", CURRENT_CONFIG_VERSION));
+    if !rest.trim().is_empty() {
+        out.push('\n');
+        out.push_str(rest.trim_start_matches('\n'));
+    }
+    Ok(out)
+}
+
+fn render_rule(rule: &ModularRule) -> Result<String> {
+    let value = toml::Value::try_from(rule)?;
+    Ok(format!(
+        "# Migrated from legacy config sections by `project-lint config migrate`\n\n{}",
+        toml::to_string_pretty(&value)?
+    ))
+}
+
+/// Minimal LCS line diff: unchanged lines are prefixed with a space, removed
+/// lines with `-` and added lines with `+`.
+fn line_diff(old: &str, new: &str) -> Vec<String> {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            out.push(format!(" {}", a[i]));
+            i += 1;
+            j += 1;
+        } else if j < b.len() && (i == a.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            out.push(format!("+{}", b[j]));
+            j += 1;
+        } else {
+            out.push(format!("-{}", a[i]));
+            i += 1;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const LEGACY: &str = r#"# Project config
+[git]
+warn_wrong_branch = true
+allowed_branches = ["main"]
+forbidden_branches = ["develop"]
+
+[files]
+auto_move = true
+ignored_patterns = ["target/"]
+
+[files.type_mappings]
+"*.sh" = "bin/"
+
+[directories]
+warn_scripts_location = false
+scripts_directory = "bin"
+
+[rules]
+enabled_checks = ["git_branch"]
+"#;
+
+    #[test]
+    fn test_plan_migration_writes_rules_and_strips_sections() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("config.toml"), LEGACY).unwrap();
+
+        let plan = plan_migration(dir.path()).unwrap();
+        assert_eq!(plan.from_version, 1);
+        assert_eq!(plan.files.len(), 4);
+        plan.apply().unwrap();
+
+        let migrated = Config::load_from_file(&dir.path().join("config.toml")).unwrap();
+        assert_eq!(migrated.version, CURRENT_CONFIG_VERSION);
+        assert_eq!(migrated.rules.enabled_checks, vec!["git_branch"]);
+
+        let names: Vec<&str> = migrated
+            .modular_rules
+            .iter()
+            .map(|r| r.name.as_str())
+            .collect();
+        assert!(names.contains(&"git-branch-rules"));
+        assert!(names.contains(&"file-organization"));
+        // `warn_scripts_location = false` migrates to a disabled rule.
+        assert!(!names.contains(&"script-location"));
+        assert!(dir
+            .path()
+            .join("rules/active/script-location.toml")
+            .exists());
+
+        let file_rule = migrated
+            .modular_rules
+            .iter()
+            .find(|r| r.name == "file-organization")
+            .unwrap();
+        assert_eq!(
+            file_rule.file_mappings.as_ref().unwrap().get("*.sh"),
+            Some(&"bin/".to_string())
+        );
+
+        let content = std::fs::read_to_string(dir.path().join("config.toml")).unwrap();
+        assert!(content.starts_with("# Project config\nversion = 2\n"));
+        assert!(!content.contains("[git]"));
+        assert!(plan_migration(dir.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_plan_migration_keeps_existing_rule_files() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("config.toml"), LEGACY).unwrap();
+        let active = dir.path().join("rules/active");
+        std::fs::create_dir_all(&active).unwrap();
+        std::fs::write(
+            active.join("branches.toml"),
+            "name = \"git-branch-rules\"\ndescription = \"d\"\nenabled = true\nseverity = \"warning\"\n",
+        )
+        .unwrap();
+
+        let plan = plan_migration(dir.path()).unwrap();
+        assert_eq!(plan.skipped_rules, vec!["git-branch-rules"]);
+        assert!(plan
+            .files
+            .iter()
+            .all(|f| !f.path.ends_with("git-branch-rules.toml")));
+    }
+
+    #[test]
+    fn test_diff_marks_removed_and_added_lines() {
+        let file = PlannedFile {
+            path: PathBuf::from("config.toml"),
+            original: Some("a\nb\nc\n".to_string()),
+            updated: "a\nc\nd\n".to_string(),
+        };
+        let diff = file.diff();
+        assert!(diff.contains(" a\n-b\n c\n+d\n"));
+    }
+}
@@ -1,140 +1,361 @@
-use crate::config::{MatchPosition, Profile, ProfileActivation};
+use crate::config::{ActivationExpr, ContentTrigger, MatchPosition, Profile, ProfileActivation};
 use crate::hooks::ProjectLintEvent;
 use crate::utils::Result;
 use glob::glob;
+use serde::{Deserialize, Serialize};
+use std::cell::OnceCell;
 use std::fs::File;
 use std::io::{Read, Seek, SeekFrom};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tracing::{debug, warn};
+use walkdir::WalkDir;
 
 const HEADER_SIZE: usize = 1024;
 
+/// What activation criteria are evaluated against. The git branch is looked
+/// up at most once, and only if some profile asks for it.
+struct ActivationContext<'a> {
+    project_path: &'a Path,
+    event: Option<&'a ProjectLintEvent>,
+    branch: OnceCell<Option<String>>,
+}
+
+impl<'a> ActivationContext<'a> {
+    fn new(project_path: &'a Path, event: Option<&'a ProjectLintEvent>) -> Self {
+        Self {
+            project_path,
+            event,
+            branch: OnceCell::new(),
+        }
+    }
+
+    fn branch(&self) -> Option<&str> {
+        self.branch
+            .get_or_init(|| {
+                // HEAD only; `get_git_info` also computes the (slow) status
+                let repo = git2::Repository::open(self.project_path).ok()?;
+                let head = repo.head().ok()?;
+                head.shorthand().map(str::to_string)
+            })
+            .as_deref()
+    }
+}
+
+/// Why a profile is or is not active, as shown by `profiles explain`.
+#[derive(Debug, Clone)]
+pub struct ActivationReport {
+    pub profile: String,
+    pub active: bool,
+    /// Flat activation criteria that matched, e.g. `indicator Dockerfile`.
+    pub matched: Vec<String>,
+    /// The `when` expression with each leaf's result, if the profile has one.
+    pub expression: Option<String>,
+}
+
 pub fn is_profile_active(
     project_path: &Path,
     profile: &Profile,
     event: Option<&ProjectLintEvent>,
 ) -> Result<bool> {
+    is_active_in(&ActivationContext::new(project_path, event), profile)
+}
+
+/// The flat activation lists form one implicit `any`; a `when` expression,
+/// if present, must hold as well. A profile without criteria is inactive.
+fn is_active_in(ctx: &ActivationContext, profile: &Profile) -> Result<bool> {
     let activation = &profile.activation;
+    let name = &profile.metadata.name;
 
-    // Check event-based activation
-    if let Some(event) = event {
-        for event_trigger in &activation.events {
-            // Match against unified event type (string representation)
-            let event_type_str = serde_json::to_string(&event.event_type)?
-                .trim_matches('"')
-                .to_string();
-
-            if event_trigger == &event_type_str || event_trigger == "all" {
-                debug!(
-                    "Profile '{}' activated by event: {}",
-                    profile.metadata.name, event_type_str
-                );
-                return Ok(true);
-            }
+    let flat = if has_flat_criteria(activation) {
+        let matched = flat_matches(ctx, activation, false)?;
+        if let Some(first) = matched.first() {
+            debug!("Profile '{}' activated by {}", name, first);
+        }
+        !matched.is_empty()
+    } else {
+        activation.when.is_some()
+    };
+    if !flat {
+        return Ok(false);
+    }
 
-            // Also check for IDE-specific events if specified
-            if let Some(original_payload) = &event.context.original_payload {
-                // Windsurf specific
-                if let Some(action_name) = original_payload["agent_action_name"].as_str() {
-                    if event_trigger == action_name {
-                        debug!(
-                            "Profile '{}' activated by Windsurf event: {}",
-                            profile.metadata.name, action_name
-                        );
-                        return Ok(true);
-                    }
-                }
-                // Claude specific
-                if let Some(hook_event_name) = original_payload["hook_event_name"].as_str() {
-                    if event_trigger == hook_event_name {
-                        debug!(
-                            "Profile '{}' activated by Claude event: {}",
-                            profile.metadata.name, hook_event_name
-                        );
-                        return Ok(true);
-                    }
-                }
+    match &activation.when {
+        Some(expr) => {
+            let result = evaluate(ctx, expr)?;
+            debug!("Profile '{}' activation expression: {}", name, result);
+            Ok(result)
+        }
+        None => Ok(true),
+    }
+}
+
+/// Evaluate every criterion of every profile, without short-circuiting, and
+/// report which ones matched.
+pub fn explain_profiles(
+    project_path: &Path,
+    profiles: &[Profile],
+    event: Option<&ProjectLintEvent>,
+) -> Result<Vec<ActivationReport>> {
+    let ctx = ActivationContext::new(project_path, event);
+    let mut reports = Vec::new();
+
+    for profile in profiles {
+        let activation = &profile.activation;
+        let matched = flat_matches(&ctx, activation, true)?;
+        let expression = match &activation.when {
+            Some(expr) => Some(describe(&ctx, expr)?),
+            None => None,
+        };
+        let flat = if has_flat_criteria(activation) {
+            !matched.is_empty()
+        } else {
+            expression.is_some()
+        };
+        let active = flat && expression.as_ref().is_none_or(|(result, _)| *result);
+        reports.push(ActivationReport {
+            profile: profile.metadata.name.clone(),
+            active,
+            matched,
+            expression: expression.map(|(_, text)| text),
+        });
+    }
+
+    Ok(reports)
+}
+
+fn has_flat_criteria(activation: &ProfileActivation) -> bool {
+    !(activation.events.is_empty()
+        && activation.indicators.is_empty()
+        && activation.paths.is_empty()
+        && activation.globs.is_empty()
+        && activation.branches.is_empty()
+        && activation.content.is_empty())
+}
+
+/// Descriptions of the flat criteria that match. Unless `exhaustive`, stops
+/// at the first match.
+fn flat_matches(
+    ctx: &ActivationContext,
+    activation: &ProfileActivation,
+    exhaustive: bool,
+) -> Result<Vec<String>> {
+    let mut matched = Vec::new();
+    macro_rules! found {
+        ($($arg:tt)*) => {{
+            matched.push(format!($($arg)*));
+            if !exhaustive {
+                return Ok(matched);
             }
+        }};
+    }
+
+    // Check event-based activation
+    for event_trigger in &activation.events {
+        if event_matches(ctx.event, event_trigger)? {
+            found!("event {}", event_trigger);
         }
     }
 
     // Check indicators (files that must exist)
     for indicator in &activation.indicators {
-        if project_path.join(indicator).exists() {
-            debug!(
-                "Profile '{}' activated by indicator: {}",
-                profile.metadata.name, indicator
-            );
-            return Ok(true);
+        if ctx.project_path.join(indicator).exists() {
+            found!("indicator {}", indicator);
         }
     }
 
     // Check paths (directories that must exist)
     for path in &activation.paths {
-        if project_path.join(path).exists() {
-            debug!(
-                "Profile '{}' activated by path: {}",
-                profile.metadata.name, path
-            );
-            return Ok(true);
+        if ctx.project_path.join(path).exists() {
+            found!("path {}", path);
         }
     }
 
     // Check globs (file patterns)
     for pattern in &activation.globs {
-        let full_pattern = project_path.join(pattern);
-        let pattern_str = full_pattern.to_string_lossy();
+        if glob_matches(ctx.project_path, pattern) {
+            found!("glob {}", pattern);
+        }
+    }
 
-        match glob(&pattern_str) {
-            Ok(paths) => {
-                // If we find at least one match, the profile is active
-                if paths.count() > 0 {
-                    debug!(
-                        "Profile '{}' activated by glob: {}",
-                        profile.metadata.name, pattern
-                    );
-                    return Ok(true);
-                }
-            }
-            Err(e) => {
-                warn!("Invalid glob pattern '{}': {}", pattern, e);
-            }
+    // Check branch patterns
+    for pattern in &activation.branches {
+        if branch_matches(ctx, pattern) {
+            found!("branch {} ({})", pattern, ctx.branch().unwrap_or_default());
         }
     }
 
     // Check content triggers
     for trigger in &activation.content {
-        let patterns_to_check = if trigger.globs.is_empty() {
-            vec!["**/*".to_string()]
-        } else {
-            trigger.globs.clone()
-        };
+        if let Some(path) = content_match(ctx.project_path, trigger) {
+            let rel = path.strip_prefix(ctx.project_path).unwrap_or(&path);
+            found!(
+                "content {:?} in {}",
+                trigger.matches.join("|"),
+                rel.display()
+            );
+        }
+    }
 
-        for glob_pattern in patterns_to_check {
-            let full_pattern = project_path.join(&glob_pattern);
-            let pattern_str = full_pattern.to_string_lossy();
-
-            if let Ok(paths) = glob(&pattern_str) {
-                for path_result in paths {
-                    if let Ok(path) = path_result {
-                        if path.is_file() {
-                            if check_file_content(&path, &trigger.matches, &trigger.position) {
-                                debug!(
-                                    "Profile '{}' activated by content match in: {:?}",
-                                    profile.metadata.name, path
-                                );
-                                return Ok(true);
-                            }
-                        }
-                    }
+    Ok(matched)
+}
+
+fn evaluate(ctx: &ActivationContext, expr: &ActivationExpr) -> Result<bool> {
+    match expr {
+        ActivationExpr::All(exprs) => {
+            for e in exprs {
+                if !evaluate(ctx, e)? {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        }
+        ActivationExpr::Any(exprs) => {
+            for e in exprs {
+                if evaluate(ctx, e)? {
+                    return Ok(true);
                 }
             }
+            Ok(false)
+        }
+        ActivationExpr::Not(e) => Ok(!evaluate(ctx, e)?),
+        leaf => evaluate_leaf(ctx, leaf),
+    }
+}
+
+/// Evaluate `expr` fully and render it with each node's result, e.g.
+/// `all(indicator tsconfig.json ✓, not(glob next.config.* ✗) ✓) ✓`.
+fn describe(ctx: &ActivationContext, expr: &ActivationExpr) -> Result<(bool, String)> {
+    let mark = |result: bool| if result { "✓" } else { "✗" };
+    let group = |name: &str, exprs: &[ActivationExpr], all: bool| -> Result<(bool, String)> {
+        let mut parts = Vec::new();
+        let mut result = all;
+        for e in exprs {
+            let (r, text) = describe(ctx, e)?;
+            result = if all { result && r } else { result || r };
+            parts.push(text);
+        }
+        Ok((
+            result,
+            format!("{}({}) {}", name, parts.join(", "), mark(result)),
+        ))
+    };
+
+    match expr {
+        ActivationExpr::All(exprs) => group("all", exprs, true),
+        ActivationExpr::Any(exprs) => group("any", exprs, false),
+        ActivationExpr::Not(e) => {
+            let (r, text) = describe(ctx, e)?;
+            Ok((!r, format!("not({}) {}", text, mark(!r))))
+        }
+        leaf => {
+            let r = evaluate_leaf(ctx, leaf)?;
+            let text = match leaf {
+                ActivationExpr::Indicator(v) => format!("indicator {}", v),
+                ActivationExpr::Path(v) => format!("path {}", v),
+                ActivationExpr::Glob(v) => format!("glob {}", v),
+                ActivationExpr::Branch(v) => format!("branch {}", v),
+                ActivationExpr::Event(v) => format!("event {}", v),
+                ActivationExpr::Content(t) => format!("content {:?}", t.matches.join("|")),
+                _ => unreachable!("combinators handled above"),
+            };
+            Ok((r, format!("{} {}", text, mark(r))))
+        }
+    }
+}
+
+fn evaluate_leaf(ctx: &ActivationContext, leaf: &ActivationExpr) -> Result<bool> {
+    Ok(match leaf {
+        ActivationExpr::Indicator(p) | ActivationExpr::Path(p) => ctx.project_path.join(p).exists(),
+        ActivationExpr::Glob(pattern) => glob_matches(ctx.project_path, pattern),
+        ActivationExpr::Branch(pattern) => branch_matches(ctx, pattern),
+        ActivationExpr::Event(trigger) => event_matches(ctx.event, trigger)?,
+        ActivationExpr::Content(trigger) => content_match(ctx.project_path, trigger).is_some(),
+        ActivationExpr::All(_) | ActivationExpr::Any(_) | ActivationExpr::Not(_) => {
+            evaluate(ctx, leaf)?
+        }
+    })
+}
+
+fn event_matches(event: Option<&ProjectLintEvent>, event_trigger: &str) -> Result<bool> {
+    let Some(event) = event else {
+        return Ok(false);
+    };
+
+    // Match against unified event type (string representation)
+    let event_type_str = serde_json::to_string(&event.event_type)?
+        .trim_matches('"')
+        .to_string();
+    if event_trigger == event_type_str || event_trigger == "all" {
+        return Ok(true);
+    }
+
+    // Also check for IDE-specific events if specified
+    if let Some(original_payload) = &event.context.original_payload {
+        // Windsurf specific
+        if original_payload["agent_action_name"].as_str() == Some(event_trigger) {
+            return Ok(true);
+        }
+        // Claude specific
+        if original_payload["hook_event_name"].as_str() == Some(event_trigger) {
+            return Ok(true);
         }
     }
 
     Ok(false)
 }
 
+fn glob_matches(project_path: &Path, pattern: &str) -> bool {
+    let full_pattern = project_path.join(pattern);
+    let pattern_str = full_pattern.to_string_lossy();
+
+    match glob(&pattern_str) {
+        // If we find at least one match, the criterion holds
+        Ok(mut paths) => paths.next().is_some(),
+        Err(e) => {
+            warn!("Invalid glob pattern '{}': {}", pattern, e);
+            false
+        }
+    }
+}
+
+fn branch_matches(ctx: &ActivationContext, pattern: &str) -> bool {
+    let Some(branch) = ctx.branch() else {
+        return false;
+    };
+    match glob::Pattern::new(pattern) {
+        Ok(p) => p.matches(branch),
+        Err(e) => {
+            warn!("Invalid branch pattern '{}': {}", pattern, e);
+            false
+        }
+    }
+}
+
+/// First file matched by a content trigger.
+fn content_match(project_path: &Path, trigger: &ContentTrigger) -> Option<PathBuf> {
+    let patterns_to_check = if trigger.globs.is_empty() {
+        vec!["**/*".to_string()]
+    } else {
+        trigger.globs.clone()
+    };
+
+    for glob_pattern in patterns_to_check {
+        let full_pattern = project_path.join(&glob_pattern);
+        let pattern_str = full_pattern.to_string_lossy();
+
+        if let Ok(paths) = glob(&pattern_str) {
+            for path in paths.flatten() {
+                if path.is_file() && check_file_content(&path, &trigger.matches, &trigger.position)
+                {
+                    return Some(path);
+                }
+            }
+        }
+    }
+
+    None
+}
+
 fn check_file_content(path: &Path, matches: &[String], position: &MatchPosition) -> bool {
     match File::open(path) {
         Ok(mut file) => {
@@ -175,13 +396,388 @@ pub fn get_active_profiles(
     available_profiles: &[Profile],
     event: Option<&ProjectLintEvent>,
 ) -> Result<Vec<Profile>> {
+    let ctx = ActivationContext::new(project_path, event);
     let mut active_profiles = Vec::new();
 
     for profile in available_profiles {
-        if is_profile_active(project_path, profile, event)? {
+        if is_active_in(&ctx, profile)? {
             active_profiles.push(profile.clone());
         }
     }
 
     Ok(active_profiles)
 }
+
+/// Directories whose contents do not feed the activation cache key (their
+/// own mtime still does).
+const ACTIVATION_CACHE_SKIP_DIRS: &[&str] = &[".git", "node_modules", "target", "dist", "build"];
+
+/// Activation results kept per project.
+const ACTIVATION_CACHE_ENTRIES: usize = 16;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ActivationCacheFile {
+    entries: Vec<ActivationCacheEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ActivationCacheEntry {
+    key: String,
+    /// Indices into the profile list the key was computed from.
+    active: Vec<usize>,
+}
+
+/// On-disk cache of [`get_active_profiles`] results, keyed on the project
+/// tree's mtimes, the `.git/HEAD` contents, the profile definitions and the
+/// triggering event. A hit costs a metadata walk instead of reading file
+/// headers for every content trigger.
+pub struct ActivationCache {
+    dir: PathBuf,
+}
+
+impl ActivationCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// `~/.cache/project-lint/activation` (or the platform equivalent).
+    pub fn default_dir() -> PathBuf {
+        dirs::cache_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("project-lint")
+            .join("activation")
+    }
+
+    pub fn active_profiles(
+        &self,
+        project_path: &Path,
+        available_profiles: &[Profile],
+        event: Option<&ProjectLintEvent>,
+    ) -> Result<Vec<Profile>> {
+        let key = activation_key(project_path, available_profiles, event)?;
+        let cache_file = self.cache_file(project_path);
+        let mut cached: ActivationCacheFile = std::fs::read_to_string(&cache_file)
+            .ok()
+            .and_then(|c| serde_json::from_str(&c).ok())
+            .unwrap_or_default();
+
+        if let Some(entry) = cached.entries.iter().find(|e| e.key == key) {
+            debug!("Profile activation cache hit for {:?}", project_path);
+            return Ok(entry
+                .active
+                .iter()
+                .filter_map(|&i| available_profiles.get(i).cloned())
+                .collect());
+        }
+
+        let ctx = ActivationContext::new(project_path, event);
+        let mut active = Vec::new();
+        for (i, profile) in available_profiles.iter().enumerate() {
+            if is_active_in(&ctx, profile)? {
+                active.push(i);
+            }
+        }
+
+        let profiles = active
+            .iter()
+            .map(|&i| available_profiles[i].clone())
+            .collect();
+        cached
+            .entries
+            .insert(0, ActivationCacheEntry { key, active });
+        cached.entries.truncate(ACTIVATION_CACHE_ENTRIES);
+        // Concurrent hooks must never read a half-written file
+        let temp_file = cache_file.with_extension(format!("json.{}.tmp", std::process::id()));
+        let written = std::fs::create_dir_all(&self.dir)
+            .map_err(anyhow::Error::from)
+            .and_then(|_| Ok(serde_json::to_string(&cached)?))
+            .and_then(|content| Ok(std::fs::write(&temp_file, content)?))
+            .and_then(|_| Ok(std::fs::rename(&temp_file, &cache_file)?));
+        if written.is_err() {
+            let _ = std::fs::remove_file(&temp_file);
+        }
+        if let Err(e) = written {
+            debug!("Could not write activation cache {:?}: {}", cache_file, e);
+        }
+
+        Ok(profiles)
+    }
+
+    fn cache_file(&self, project_path: &Path) -> PathBuf {
+        let canonical = project_path
+            .canonicalize()
+            .unwrap_or_else(|_| project_path.to_path_buf());
+        let mut hasher = StableHasher::new();
+        hasher.field(canonical.as_os_str().as_encoded_bytes());
+        self.dir.join(format!("{:016x}.json", hasher.finish()))
+    }
+}
+
+/// 64-bit FNV-1a over explicitly fed bytes. Cache keys outlive the binary
+/// that wrote them, so unlike `DefaultHasher` (and std's `Hash` impls) the
+/// output must not change between toolchains.
+struct StableHasher(u64);
+
+impl StableHasher {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    /// Length-prefixed, so adjacent fields cannot run into each other.
+    fn field(&mut self, bytes: &[u8]) {
+        self.write(&(bytes.len() as u64).to_le_bytes());
+        self.write(bytes);
+    }
+
+    fn optional(&mut self, bytes: Option<&[u8]>) {
+        match bytes {
+            Some(bytes) => {
+                self.write(&[1]);
+                self.field(bytes);
+            }
+            None => self.write(&[0]),
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+fn activation_key(
+    project_path: &Path,
+    profiles: &[Profile],
+    event: Option<&ProjectLintEvent>,
+) -> Result<String> {
+    let mut hasher = StableHasher::new();
+
+    for profile in profiles {
+        hasher.field(serde_json::to_string(profile)?.as_bytes());
+    }
+
+    if let Some(event) = event {
+        hasher.field(serde_json::to_string(&event.event_type)?.as_bytes());
+        if let Some(payload) = &event.context.original_payload {
+            hasher.optional(payload["agent_action_name"].as_str().map(str::as_bytes));
+            hasher.optional(payload["hook_event_name"].as_str().map(str::as_bytes));
+        }
+    }
+
+    // Branch criteria depend on HEAD, which lives in a skipped directory
+    hasher.optional(
+        std::fs::read(project_path.join(".git").join("HEAD"))
+            .ok()
+            .as_deref(),
+    );
+
+    let mut walker = WalkDir::new(project_path).into_iter();
+    while let Some(entry) = walker.next() {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        hasher.field(entry.path().as_os_str().as_encoded_bytes());
+        let modified = entry
+            .metadata()
+            .ok()
+            .and_then(|m| m.modified().ok())
+            .and_then(|m| m.duration_since(std::time::UNIX_EPOCH).ok());
+        hasher.optional(
+            modified
+                .map(|m| {
+                    [
+                        m.as_secs().to_le_bytes(),
+                        u64::from(m.subsec_nanos()).to_le_bytes(),
+                    ]
+                    .concat()
+                })
+                .as_deref(),
+        );
+        if entry.depth() > 0
+            && entry.file_type().is_dir()
+            && ACTIVATION_CACHE_SKIP_DIRS.contains(&entry.file_name().to_string_lossy().as_ref())
+        {
+            walker.skip_current_dir();
+        }
+    }
+
+    Ok(format!("{:016x}", hasher.finish()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn profile(activation: &str) -> Profile {
+        toml::from_str(&format!(
+            r#"
+[metadata]
+name = "test"
+version = "1.0.0"
+updated = "2025-01-01"
+description = "test"
+
+[enable]
+domains = []
+plugins = []
+
+[activation]
+{}
+"#,
+            activation
+        ))
+        .expect("profile")
+    }
+
+    fn commit_on_branch(dir: &Path, branch: &str) -> Result<()> {
+        let repo = git2::Repository::init(dir)?;
+        repo.set_head(&format!("refs/heads/{}", branch))?;
+        let sig = git2::Signature::now("test", "test@example.com")?;
+        let tree_id = repo.index()?.write_tree()?;
+        let tree = repo.find_tree(tree_id)?;
+        repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])?;
+        Ok(())
+    }
+
+    #[test]
+    fn when_expression_combines_with_all_and_not() -> Result<()> {
+        let dir = TempDir::new()?;
+        std::fs::write(dir.path().join("tsconfig.json"), "{}")?;
+        let p = profile(
+            r#"when = { all = [{ indicator = "tsconfig.json" }, { not = { glob = "next.config.*" } }] }"#,
+        );
+        assert!(is_profile_active(dir.path(), &p, None)?);
+
+        std::fs::write(dir.path().join("next.config.mjs"), "")?;
+        assert!(!is_profile_active(dir.path(), &p, None)?);
+        Ok(())
+    }
+
+    #[test]
+    fn flat_lists_and_when_must_both_hold() -> Result<()> {
+        let dir = TempDir::new()?;
+        std::fs::write(dir.path().join("Dockerfile"), "FROM scratch\n")?;
+        let p = profile(
+            r#"indicators = ["Dockerfile"]
+when = { any = [{ path = "deploy" }, { indicator = "compose.yml" }] }"#,
+        );
+        assert!(!is_profile_active(dir.path(), &p, None)?);
+
+        std::fs::create_dir(dir.path().join("deploy"))?;
+        assert!(is_profile_active(dir.path(), &p, None)?);
+
+        std::fs::remove_file(dir.path().join("Dockerfile"))?;
+        assert!(!is_profile_active(dir.path(), &p, None)?);
+        Ok(())
+    }
+
+    #[test]
+    fn branches_are_matched_as_globs() -> Result<()> {
+        let dir = TempDir::new()?;
+        commit_on_branch(dir.path(), "release/1.2")?;
+        std::fs::write(dir.path().join("Dockerfile"), "FROM scratch\n")?;
+
+        assert!(is_profile_active(
+            dir.path(),
+            &profile(r#"branches = ["release/*"]"#),
+            None
+        )?);
+        assert!(!is_profile_active(
+            dir.path(),
+            &profile(r#"branches = ["feature/*"]"#),
+            None
+        )?);
+        let expr =
+            profile(r#"when = { all = [{ indicator = "Dockerfile" }, { branch = "release/*" }] }"#);
+        assert!(is_profile_active(dir.path(), &expr, None)?);
+        Ok(())
+    }
+
+    #[test]
+    fn profile_without_criteria_is_inactive() -> Result<()> {
+        let dir = TempDir::new()?;
+        assert!(!is_profile_active(dir.path(), &profile(""), None)?);
+        Ok(())
+    }
+
+    #[test]
+    fn explain_reports_every_matching_criterion() -> Result<()> {
+        let dir = TempDir::new()?;
+        std::fs::write(dir.path().join("Dockerfile"), "FROM scratch\n")?;
+        std::fs::write(dir.path().join("package.json"), "{\"typescript\": 1}")?;
+        let profiles = vec![
+            profile(
+                r#"indicators = ["Dockerfile", "compose.yml"]
+globs = ["Docker*"]
+[[activation.content]]
+matches = ["typescript"]
+globs = ["package.json"]"#,
+            ),
+            profile(r#"when = { not = { indicator = "Dockerfile" } }"#),
+        ];
+
+        let reports = explain_profiles(dir.path(), &profiles, None)?;
+        assert!(reports[0].active);
+        assert_eq!(
+            reports[0].matched,
+            vec![
+                "indicator Dockerfile",
+                "glob Docker*",
+                "content \"typescript\" in package.json"
+            ]
+        );
+        assert!(!reports[1].active);
+        assert_eq!(
+            reports[1].expression.as_deref(),
+            Some("not(indicator Dockerfile ✓) ✗")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn activation_cache_is_invalidated_by_tree_changes() -> Result<()> {
+        let project = TempDir::new()?;
+        let cache_dir = TempDir::new()?;
+        let cache = ActivationCache::new(cache_dir.path().to_path_buf());
+        let profiles = vec![profile(r#"indicators = ["Dockerfile"]"#)];
+
+        assert!(cache
+            .active_profiles(project.path(), &profiles, None)?
+            .is_empty());
+        assert_eq!(std::fs::read_dir(cache_dir.path())?.count(), 1);
+
+        std::fs::write(project.path().join("Dockerfile"), "FROM scratch\n")?;
+        assert_eq!(
+            cache
+                .active_profiles(project.path(), &profiles, None)?
+                .len(),
+            1
+        );
+        // Served from the cache on the unchanged tree
+        assert_eq!(
+            cache
+                .active_profiles(project.path(), &profiles, None)?
+                .len(),
+            1
+        );
+        // Written through a temp file that is renamed into place
+        assert_eq!(std::fs::read_dir(cache_dir.path())?.count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn activation_cache_keys_use_a_fixed_hash() {
+        // FNV-1a test vectors; a different value would orphan every cache
+        let mut hasher = StableHasher::new();
+        assert_eq!(hasher.finish(), 0xcbf2_9ce4_8422_2325);
+        hasher.write(b"a");
+        assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+    }
+}
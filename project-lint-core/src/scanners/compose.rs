@@ -0,0 +1,415 @@
+//! Compose scanner — container hardening for `docker-compose*.yml` and
+//! `compose.*.yaml` services: no privileged containers or docker.sock mounts
+//! (dockerproxy services excepted), `no-new-privileges`, tagged images,
+//! healthchecks, and no ports published on `0.0.0.0` without a gateway
+//! annotation.
+
+use crate::scanners::kubernetes::is_unpinned_image;
+use crate::scanners::yaml::{parse_documents, YamlNode};
+use crate::scanners::ScannerIssue;
+use crate::utils::Result;
+use std::path::Path;
+use walkdir::WalkDir;
+
+const DOCKER_SOCK: &str = "/var/run/docker.sock";
+const SKIP_DIRS: &[&str] = &[".git", "node_modules", "target", "dist", "build"];
+
+pub struct ComposeScanner {
+    require_no_new_privileges: bool,
+    require_healthcheck: bool,
+}
+
+impl ComposeScanner {
+    pub fn new() -> Self {
+        Self {
+            require_no_new_privileges: true,
+            require_healthcheck: true,
+        }
+    }
+
+    pub fn with_config(require_no_new_privileges: bool, require_healthcheck: bool) -> Self {
+        Self {
+            require_no_new_privileges,
+            require_healthcheck,
+        }
+    }
+
+    /// Scan a project for compose files and lint each service.
+    pub fn scan(&self, project_path: &str) -> Result<Vec<ScannerIssue>> {
+        let root = Path::new(project_path);
+        let mut issues = Vec::new();
+
+        for entry in WalkDir::new(root)
+            .into_iter()
+            .filter_entry(|e| {
+                !(e.depth() > 0
+                    && e.file_type().is_dir()
+                    && SKIP_DIRS.contains(&e.file_name().to_string_lossy().as_ref()))
+            })
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            if !is_compose_file(&name) {
+                continue;
+            }
+            let Ok(content) = std::fs::read_to_string(path) else {
+                continue;
+            };
+            let rel = path
+                .strip_prefix(root)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .to_string();
+            for document in parse_documents(&content)? {
+                let Some(services) = document.get("services") else {
+                    continue;
+                };
+                for (service, definition) in services.entries() {
+                    issues.extend(self.check_service(service, definition, &rel));
+                }
+            }
+        }
+
+        Ok(issues)
+    }
+
+    fn check_service(&self, service: &str, definition: &YamlNode, rel: &str) -> Vec<ScannerIssue> {
+        let mut issues = Vec::new();
+        let proxy_role = label(definition, "com.dockerproxy.role")
+            .filter(|role| matches!(role.as_str(), "rw" | "ro"));
+
+        if let Some(privileged) = definition.get("privileged").filter(|v| v.is_true()) {
+            issues.push(
+                ScannerIssue::new(
+                    "privileged",
+                    "error",
+                    rel,
+                    format!("service '{}' runs privileged", service),
+                )
+                .at_line(privileged.line),
+            );
+        }
+
+        if let Some(volumes) = definition.get("volumes") {
+            for volume in volumes.items() {
+                let source = match volume.as_str() {
+                    Some(short) => short.split(':').next().unwrap_or(short),
+                    None => volume.get("source").and_then(|s| s.as_str()).unwrap_or(""),
+                };
+                if source != DOCKER_SOCK {
+                    continue;
+                }
+                let issue = match &proxy_role {
+                    Some(role) => ScannerIssue::new(
+                        "docker-sock",
+                        "info",
+                        rel,
+                        format!(
+                            "service '{}' mounts {} as a dockerproxy ({})",
+                            service, DOCKER_SOCK, role
+                        ),
+                    ),
+                    None => ScannerIssue::new(
+                        "docker-sock",
+                        "error",
+                        rel,
+                        format!(
+                            "service '{}' mounts {}; route access through a dockerproxy service (label com.dockerproxy.role=rw|ro)",
+                            service, DOCKER_SOCK
+                        ),
+                    ),
+                };
+                issues.push(issue.at_line(volume.line));
+            }
+        }
+
+        if self.require_no_new_privileges {
+            let hardened = definition
+                .get("security_opt")
+                .map(|opts| opts.items())
+                .unwrap_or(&[])
+                .iter()
+                .filter_map(|o| o.as_str())
+                .any(|o| {
+                    matches!(
+                        o.replace('=', ":").as_str(),
+                        "no-new-privileges" | "no-new-privileges:true"
+                    )
+                });
+            if !hardened {
+                issues.push(
+                    ScannerIssue::new(
+                        "no-new-privileges",
+                        "warning",
+                        rel,
+                        format!(
+                            "service '{}' does not set security_opt: [\"no-new-privileges:true\"]",
+                            service
+                        ),
+                    )
+                    .at_line(definition.line),
+                );
+            }
+        }
+
+        if let Some(image) = definition.get("image") {
+            if let Some(reference) = image.as_str().filter(|i| is_unpinned_image(i)) {
+                issues.push(
+                    ScannerIssue::new(
+                        "latest-image",
+                        "warning",
+                        rel,
+                        format!(
+                            "service '{}' uses image '{}' without a fixed tag",
+                            service, reference
+                        ),
+                    )
+                    .at_line(image.line),
+                );
+            }
+        }
+
+        let has_gateway = label(definition, "gateway").is_some_and(|g| g == "transparent");
+        if let Some(ports) = definition.get("ports").filter(|_| !has_gateway) {
+            for port in ports.items() {
+                // Without a host IP Docker binds every interface
+                let host_ip = match port.as_str() {
+                    Some(short) => short_port_host_ip(short),
+                    None => port.get("host_ip").and_then(|h| h.as_str()),
+                };
+                if host_ip.is_some_and(is_loopback) {
+                    continue;
+                }
+                issues.push(
+                    ScannerIssue::new(
+                        "public-port",
+                        "warning",
+                        rel,
+                        format!(
+                            "service '{}' publishes a port on {} without a 'gateway: transparent' label",
+                            service,
+                            host_ip.unwrap_or("all interfaces")
+                        ),
+                    )
+                    .at_line(port.line),
+                );
+            }
+        }
+
+        if self.require_healthcheck {
+            let disabled = definition
+                .path(&["healthcheck", "disable"])
+                .is_some_and(|d| d.is_true());
+            if definition.get("healthcheck").is_none() || disabled {
+                issues.push(
+                    ScannerIssue::new(
+                        "missing-healthcheck",
+                        "warning",
+                        rel,
+                        format!("service '{}' has no healthcheck", service),
+                    )
+                    .at_line(definition.line),
+                );
+            }
+        }
+
+        issues
+    }
+}
+
+impl Default for ComposeScanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Host IP of a short-form port (`[HOST:]HOST_PORT:CONTAINER_PORT[/PROTO]`,
+/// IPv6 hosts in brackets), if it names one.
+fn short_port_host_ip(port: &str) -> Option<&str> {
+    if let Some(bracketed) = port.strip_prefix('[') {
+        return bracketed.split_once(']').map(|(ip, _)| ip);
+    }
+    let mapping = port.split('/').next().unwrap_or(port);
+    let parts: Vec<&str> = mapping.split(':').collect();
+    (parts.len() == 3)
+        .then(|| parts[0])
+        .filter(|ip| !ip.is_empty())
+}
+
+fn is_loopback(host_ip: &str) -> bool {
+    host_ip == "localhost"
+        || host_ip
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Globs matching the files [`is_compose_file`] accepts, anywhere in a tree.
+pub const COMPOSE_GLOBS: &[&str] = &[
+    "**/docker-compose*.yml",
+    "**/docker-compose*.yaml",
+    "**/compose.yml",
+    "**/compose.yaml",
+    "**/compose.*.yml",
+    "**/compose.*.yaml",
+];
+
+/// `docker-compose*.yml`, `compose.yaml`, `compose.override.yml` and the
+/// like, by file name.
+pub fn is_compose_file(name: &str) -> bool {
+    let is_yaml = name.ends_with(".yml") || name.ends_with(".yaml");
+    is_yaml && (name.starts_with("docker-compose") || name.starts_with("compose."))
+}
+
+/// A service label, from either the mapping or the `KEY=VALUE` list form.
+fn label(definition: &YamlNode, key: &str) -> Option<String> {
+    let labels = definition.get("labels")?;
+    if let Some(value) = labels.get(key) {
+        return value.as_str().map(str::to_string);
+    }
+    labels
+        .items()
+        .iter()
+        .filter_map(|l| l.as_str())
+        .find_map(|l| {
+            l.split_once('=')
+                .filter(|(k, _)| *k == key)
+                .map(|(_, v)| v.to_string())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn flags_service_hardening_gaps() -> Result<()> {
+        let dir = TempDir::new()?;
+        std::fs::write(
+            dir.path().join("docker-compose.yml"),
+            r#"services:
+  app:
+    image: nginx
+    privileged: true
+    ports:
+      - "0.0.0.0:8080:80"
+      - "127.0.0.1:9090:90"
+    volumes:
+      - /var/run/docker.sock:/var/run/docker.sock
+  proxy:
+    image: tecnativa/docker-socket-proxy:0.2@sha256:abc
+    labels:
+      - com.dockerproxy.role=ro
+      - gateway=transparent
+    security_opt: ["no-new-privileges:true"]
+    ports:
+      - target: 2375
+        host_ip: 0.0.0.0
+    volumes:
+      - type: bind
+        source: /var/run/docker.sock
+        target: /var/run/docker.sock
+    healthcheck:
+      test: ["CMD", "true"]
+  web:
+    image: nginx:1.27
+    security_opt: ["no-new-privileges:true"]
+    healthcheck:
+      test: ["CMD", "true"]
+    ports:
+      - "8080:80"
+      - 3000
+      - "[::1]:8443:443/tcp"
+      - target: 80
+        published: 8081
+      - target: 443
+        host_ip: 127.0.0.1
+"#,
+        )?;
+        std::fs::write(
+            dir.path().join("unrelated.yml"),
+            "services: {x: {privileged: true}}\n",
+        )?;
+
+        let issues = ComposeScanner::new().scan(&dir.path().to_string_lossy())?;
+        let mut found: Vec<(&str, &str, usize)> = issues
+            .iter()
+            .map(|i| (i.rule.as_str(), i.severity.as_str(), i.line))
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                ("docker-sock", "error", 9),
+                ("docker-sock", "info", 20),
+                ("latest-image", "warning", 3),
+                ("missing-healthcheck", "warning", 3),
+                ("no-new-privileges", "warning", 3),
+                ("privileged", "error", 4),
+                ("public-port", "warning", 6),
+                ("public-port", "warning", 31),
+                ("public-port", "warning", 32),
+                ("public-port", "warning", 34),
+            ]
+        );
+        let messages: Vec<&str> = issues
+            .iter()
+            .filter(|i| i.rule == "public-port")
+            .map(|i| i.message.as_str())
+            .collect();
+        assert!(messages[0].contains("on 0.0.0.0"));
+        assert!(messages[1..]
+            .iter()
+            .all(|m| m.contains("on all interfaces")));
+
+        let relaxed =
+            ComposeScanner::with_config(false, false).scan(&dir.path().to_string_lossy())?;
+        assert_eq!(relaxed.len(), 8);
+        Ok(())
+    }
+
+    #[test]
+    fn finds_compose_files_at_any_depth() -> Result<()> {
+        let dir = TempDir::new()?;
+        let deep = dir.path().join("apps/billing/deploy/local/stack");
+        std::fs::create_dir_all(&deep)?;
+        std::fs::write(
+            deep.join("compose.override.yaml"),
+            "services:\n  app:\n    privileged: true\n",
+        )?;
+        std::fs::write(
+            deep.join("composer.yml"),
+            "services:\n  app:\n    privileged: true\n",
+        )?;
+
+        let issues = ComposeScanner::new().scan(&dir.path().to_string_lossy())?;
+        assert!(issues.iter().any(|i| i.rule == "privileged"
+            && i.file == "apps/billing/deploy/local/stack/compose.override.yaml"));
+        assert!(issues.iter().all(|i| !i.file.ends_with("composer.yml")));
+        Ok(())
+    }
+
+    #[test]
+    fn compose_globs_match_compose_files() {
+        let names = [
+            ("docker-compose.yml", true),
+            ("docker-compose.prod.yaml", true),
+            ("compose.yml", true),
+            ("compose.yaml", true),
+            ("compose.override.yml", true),
+            ("compose.dev.yaml", true),
+            ("composer.yml", false),
+            ("compose.json", false),
+        ];
+        for (name, compose) in names {
+            let path = format!("svc/{}", name);
+            let globbed = COMPOSE_GLOBS
+                .iter()
+                .any(|g| glob::Pattern::new(g).unwrap().matches(&path));
+            assert_eq!(is_compose_file(name), compose, "{}", name);
+            assert_eq!(globbed, compose, "{}", name);
+        }
+    }
+}
@@ -0,0 +1,352 @@
+//! Structural HCL reader for the Terraform scanner. It splits a file into
+//! blocks (type, labels, body) and attributes, keeping each attribute's
+//! expression as raw text. Expressions are not evaluated; the checks that use
+//! this only look at literals.
+
+/// Attributes and nested blocks of a file or block body, in source order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HclBody {
+    pub attributes: Vec<HclAttribute>,
+    pub blocks: Vec<HclBlock>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HclAttribute {
+    pub name: String,
+    /// The expression as written, comments and surrounding space removed.
+    pub expr: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HclBlock {
+    pub kind: String,
+    pub labels: Vec<String>,
+    pub body: HclBody,
+    pub line: usize,
+}
+
+impl HclBody {
+    pub fn attribute(&self, name: &str) -> Option<&HclAttribute> {
+        self.attributes.iter().find(|a| a.name == name)
+    }
+
+    pub fn blocks_of<'a>(&'a self, kind: &'a str) -> impl Iterator<Item = &'a HclBlock> {
+        self.blocks.iter().filter(move |b| b.kind == kind)
+    }
+}
+
+impl HclAttribute {
+    /// The value when the expression is a plain string literal without
+    /// interpolation.
+    pub fn string_literal(&self) -> Option<&str> {
+        let inner = self.expr.strip_prefix('"')?.strip_suffix('"')?;
+        (!inner.contains("${") && !inner.contains('"')).then_some(inner)
+    }
+}
+
+/// Parse an HCL source. Malformed input yields whatever could be read up to
+/// the problem.
+pub fn parse(source: &str) -> HclBody {
+    let mut reader = Reader {
+        chars: source.chars().collect(),
+        pos: 0,
+        line: 1,
+    };
+    reader.body(false)
+}
+
+struct Reader {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+}
+
+impl Reader {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    /// Skip whitespace (including newlines) and comments.
+    fn skip_trivia(&mut self) {
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() {
+                self.bump();
+            } else if c == '#' || (c == '/' && self.peek_at(1) == Some('/')) {
+                self.skip_line();
+            } else if c == '/' && self.peek_at(1) == Some('*') {
+                self.skip_block_comment();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn skip_line(&mut self) {
+        while let Some(c) = self.peek() {
+            if c == '\n' {
+                break;
+            }
+            self.bump();
+        }
+    }
+
+    fn skip_block_comment(&mut self) {
+        self.bump();
+        self.bump();
+        while let Some(c) = self.bump() {
+            if c == '*' && self.peek() == Some('/') {
+                self.bump();
+                break;
+            }
+        }
+    }
+
+    fn identifier(&mut self) -> String {
+        let mut ident = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_alphanumeric() || c == '_' || c == '-' {
+                ident.push(c);
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        ident
+    }
+
+    fn quoted(&mut self) -> String {
+        let mut text = String::new();
+        self.bump();
+        while let Some(c) = self.bump() {
+            match c {
+                '\\' => {
+                    if let Some(escaped) = self.bump() {
+                        text.push(escaped);
+                    }
+                }
+                '"' => break,
+                _ => text.push(c),
+            }
+        }
+        text
+    }
+
+    fn body(&mut self, nested: bool) -> HclBody {
+        let mut body = HclBody::default();
+        loop {
+            self.skip_trivia();
+            match self.peek() {
+                None => break,
+                Some('}') if nested => {
+                    self.bump();
+                    break;
+                }
+                _ => {}
+            }
+
+            let line = self.line;
+            let name = self.identifier();
+            if name.is_empty() {
+                // Not something we understand; skip the character
+                self.bump();
+                continue;
+            }
+
+            let mut labels = Vec::new();
+            loop {
+                while self.peek().is_some_and(|c| c == ' ' || c == '\t') {
+                    self.bump();
+                }
+                match self.peek() {
+                    Some('=') => {
+                        self.bump();
+                        let expr = self.expression();
+                        body.attributes.push(HclAttribute { name, expr, line });
+                        break;
+                    }
+                    Some('{') => {
+                        self.bump();
+                        let inner = self.body(true);
+                        body.blocks.push(HclBlock {
+                            kind: name,
+                            labels,
+                            body: inner,
+                            line,
+                        });
+                        break;
+                    }
+                    Some('"') => labels.push(self.quoted()),
+                    Some(c) if c.is_alphanumeric() || c == '_' => labels.push(self.identifier()),
+                    _ => break,
+                }
+            }
+        }
+        body
+    }
+
+    /// Raw expression text up to the end of the line at nesting depth zero,
+    /// or up to the `}` closing a one-line block.
+    fn expression(&mut self) -> String {
+        let mut expr = String::new();
+        let mut depth = 0usize;
+        while let Some(c) = self.peek() {
+            match c {
+                '\n' if depth == 0 => break,
+                '}' if depth == 0 => break,
+                '#' => {
+                    self.skip_line();
+                    continue;
+                }
+                '/' if self.peek_at(1) == Some('/') => {
+                    self.skip_line();
+                    continue;
+                }
+                '/' if self.peek_at(1) == Some('*') => {
+                    self.skip_block_comment();
+                    continue;
+                }
+                '"' => {
+                    self.string_into(&mut expr);
+                    continue;
+                }
+                '<' if self.peek_at(1) == Some('<') && depth == 0 => {
+                    self.heredoc_into(&mut expr);
+                    continue;
+                }
+                '{' | '[' | '(' => depth += 1,
+                '}' | ']' | ')' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            expr.push(c);
+            self.bump();
+        }
+        expr.trim().to_string()
+    }
+
+    /// Copy a string literal, interpolations included, into `out`.
+    fn string_into(&mut self, out: &mut String) {
+        out.extend(self.bump());
+        let mut interpolation = 0usize;
+        while let Some(c) = self.bump() {
+            out.push(c);
+            match c {
+                '\\' => out.extend(self.bump()),
+                '$' if self.peek() == Some('{') => {
+                    out.extend(self.bump());
+                    interpolation += 1;
+                }
+                '}' if interpolation > 0 => interpolation -= 1,
+                '"' if interpolation == 0 => break,
+                '\n' => break,
+                _ => {}
+            }
+        }
+    }
+
+    /// Copy a `<<MARKER` / `<<-MARKER` heredoc into `out`.
+    fn heredoc_into(&mut self, out: &mut String) {
+        let mut opener = String::new();
+        while let Some(c) = self.peek() {
+            if c == '\n' {
+                break;
+            }
+            opener.push(c);
+            self.bump();
+        }
+        let marker = opener
+            .trim_start_matches('<')
+            .trim_start_matches('-')
+            .trim()
+            .to_string();
+        out.push_str(&opener);
+
+        // Stop before the newline that ends the marker line
+        let mut current = String::new();
+        while let Some(c) = self.peek() {
+            if c == '\n' {
+                if current.trim() == marker {
+                    break;
+                }
+                current.clear();
+            } else {
+                current.push(c);
+            }
+            out.push(c);
+            self.bump();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_blocks_attributes_and_expressions() {
+        let source = r#"
+# comment
+terraform {
+  required_version = ">= 1.5" // trailing
+  required_providers {
+    aws = {
+      source  = "hashicorp/aws"
+      version = "~> 5.0"
+    }
+  }
+}
+
+resource "aws_s3_bucket" "logs" {
+  bucket = "logs-${var.env}"
+  tags   = { Name = "logs" }
+  policy = <<-EOT
+    { "Version": "2012-10-17" }
+  EOT
+  lifecycle { prevent_destroy = true }
+}
+"#;
+        let body = parse(source);
+        let terraform = body.blocks_of("terraform").next().unwrap();
+        assert_eq!(terraform.line, 3);
+        let version = terraform.body.attribute("required_version").unwrap();
+        assert_eq!(version.string_literal(), Some(">= 1.5"));
+        let providers = terraform
+            .body
+            .blocks_of("required_providers")
+            .next()
+            .unwrap();
+        let aws = providers.body.attribute("aws").unwrap();
+        assert!(aws.expr.contains("version = \"~> 5.0\""));
+
+        let bucket = body.blocks_of("resource").next().unwrap();
+        assert_eq!(bucket.labels, vec!["aws_s3_bucket", "logs"]);
+        let name = bucket.body.attribute("bucket").unwrap();
+        assert_eq!(name.expr, "\"logs-${var.env}\"");
+        assert_eq!(name.string_literal(), None);
+        assert!(bucket
+            .body
+            .attribute("policy")
+            .unwrap()
+            .expr
+            .ends_with("EOT"));
+        let lifecycle = bucket.body.blocks_of("lifecycle").next().unwrap();
+        assert_eq!(
+            lifecycle.body.attribute("prevent_destroy").unwrap().expr,
+            "true"
+        );
+        assert_eq!(lifecycle.line, 19);
+    }
+}
@@ -0,0 +1,486 @@
+//! Kubernetes manifest scanner — finds Kubernetes objects in YAML files
+//! (including multi-document files) and checks workloads for resource
+//! requests/limits, privileged or host-level access, `runAsNonRoot`, `:latest`
+//! images and health probes, and Secrets committed in plaintext.
+//!
+//! `kustomization.yaml` files are followed too: every local `resources` /
+//! `bases` entry must exist, and patch files they list are only checked for
+//! what they set, since a patch is not expected to be a complete object.
+
+use crate::scanners::yaml::{parse_documents, YamlNode};
+use crate::scanners::ScannerIssue;
+use crate::utils::Result;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+const SKIP_DIRS: &[&str] = &[".git", "node_modules", "target", "dist", "build"];
+const KUSTOMIZATION_FILES: &[&str] = &["kustomization.yaml", "kustomization.yml", "Kustomization"];
+
+/// Kinds whose pod template lives at `spec.template.spec`.
+const TEMPLATED_WORKLOADS: &[&str] = &[
+    "Deployment",
+    "StatefulSet",
+    "DaemonSet",
+    "ReplicaSet",
+    "ReplicationController",
+    "Job",
+];
+
+pub struct KubernetesScanner {
+    require_probes: bool,
+    require_resources: bool,
+}
+
+impl KubernetesScanner {
+    pub fn new() -> Self {
+        Self {
+            require_probes: true,
+            require_resources: true,
+        }
+    }
+
+    pub fn with_config(require_probes: bool, require_resources: bool) -> Self {
+        Self {
+            require_probes,
+            require_resources,
+        }
+    }
+
+    /// Scan a project for Kubernetes manifests and kustomizations.
+    pub fn scan(&self, project_path: &str) -> Result<Vec<ScannerIssue>> {
+        let root = Path::new(project_path);
+        let mut issues = Vec::new();
+
+        let yaml_files: Vec<PathBuf> = WalkDir::new(root)
+            .into_iter()
+            .filter_entry(|e| {
+                !(e.depth() > 0
+                    && e.file_type().is_dir()
+                    && SKIP_DIRS.contains(&e.file_name().to_string_lossy().as_ref()))
+            })
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .map(|e| e.into_path())
+            .filter(|p| {
+                let name = p.file_name().unwrap_or_default().to_string_lossy();
+                name.ends_with(".yaml") || name.ends_with(".yml") || name == "Kustomization"
+            })
+            .collect();
+
+        // Kustomizations first, so the patches they list are known
+        let mut patches = HashSet::new();
+        let mut manifests = Vec::new();
+        for path in yaml_files {
+            let Ok(content) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            let Ok(documents) = parse_documents(&content) else {
+                continue;
+            };
+            let rel = relative(root, &path);
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            if KUSTOMIZATION_FILES.contains(&name.as_ref()) {
+                if let Some(kustomization) = documents.first() {
+                    issues.extend(check_kustomization(
+                        root,
+                        &path,
+                        &rel,
+                        kustomization,
+                        &mut patches,
+                    ));
+                }
+                continue;
+            }
+            manifests.push((path, rel, documents));
+        }
+
+        for (path, rel, documents) in manifests {
+            let is_patch = patches.contains(&normalize(&path));
+            for document in documents.iter().filter(|d| is_object(d)) {
+                issues.extend(self.check_object(document, &rel, is_patch));
+            }
+        }
+
+        Ok(issues)
+    }
+
+    fn check_object(&self, object: &YamlNode, rel: &str, is_patch: bool) -> Vec<ScannerIssue> {
+        let kind = object.get("kind").and_then(|k| k.as_str()).unwrap_or("");
+        let name = object
+            .path(&["metadata", "name"])
+            .and_then(|n| n.as_str())
+            .unwrap_or("<unnamed>");
+        let subject = format!("{} '{}'", kind, name);
+        let mut issues = Vec::new();
+
+        if kind == "Secret" {
+            let plaintext = ["data", "stringData"]
+                .iter()
+                .filter_map(|key| object.get(key))
+                .flat_map(|data| data.entries())
+                .find(|(_, value)| value.as_str().is_some_and(|v| !v.is_empty()));
+            if let Some((key, value)) = plaintext {
+                issues.push(
+                    ScannerIssue::new(
+                        "plaintext-secret",
+                        "error",
+                        rel,
+                        format!(
+                            "{} commits secret value '{}'; use a sealed or external secret",
+                            subject, key
+                        ),
+                    )
+                    .at_line(value.line),
+                );
+            }
+            return issues;
+        }
+
+        let pod_spec = if kind == "Pod" {
+            object.get("spec")
+        } else if kind == "CronJob" {
+            object.path(&["spec", "jobTemplate", "spec", "template", "spec"])
+        } else if TEMPLATED_WORKLOADS.contains(&kind) {
+            object.path(&["spec", "template", "spec"])
+        } else {
+            None
+        };
+        let Some(pod_spec) = pod_spec else {
+            return issues;
+        };
+        // Run-to-completion workloads are not probed
+        let long_running = !matches!(kind, "Job" | "CronJob");
+
+        if let Some(host_network) = pod_spec.get("hostNetwork").filter(|v| v.is_true()) {
+            issues.push(
+                ScannerIssue::new(
+                    "host-network",
+                    "error",
+                    rel,
+                    format!("{} uses the host network", subject),
+                )
+                .at_line(host_network.line),
+            );
+        }
+        if let Some(volumes) = pod_spec.get("volumes") {
+            for volume in volumes.items() {
+                if let Some(host_path) = volume.get("hostPath") {
+                    issues.push(
+                        ScannerIssue::new(
+                            "host-path",
+                            "error",
+                            rel,
+                            format!("{} mounts a hostPath volume", subject),
+                        )
+                        .at_line(host_path.line),
+                    );
+                }
+            }
+        }
+
+        let pod_non_root = pod_spec
+            .path(&["securityContext", "runAsNonRoot"])
+            .is_some_and(|v| v.is_true());
+        let containers = pod_spec.get("containers").map(|c| c.items()).unwrap_or(&[]);
+        let init_containers = pod_spec
+            .get("initContainers")
+            .map(|c| c.items())
+            .unwrap_or(&[]);
+
+        for (container, is_init) in containers
+            .iter()
+            .map(|c| (c, false))
+            .chain(init_containers.iter().map(|c| (c, true)))
+        {
+            let container_name = container
+                .get("name")
+                .and_then(|n| n.as_str())
+                .unwrap_or("<unnamed>");
+            let what = format!("container '{}' in {}", container_name, subject);
+
+            if let Some(privileged) = container
+                .path(&["securityContext", "privileged"])
+                .filter(|v| v.is_true())
+            {
+                issues.push(
+                    ScannerIssue::new(
+                        "privileged",
+                        "error",
+                        rel,
+                        format!("{} is privileged", what),
+                    )
+                    .at_line(privileged.line),
+                );
+            }
+            if let Some(image) = container.get("image") {
+                if image.as_str().is_some_and(is_unpinned_image) {
+                    issues.push(
+                        ScannerIssue::new(
+                            "latest-image",
+                            "warning",
+                            rel,
+                            format!(
+                                "{} uses image '{}' without a fixed tag",
+                                what,
+                                image.as_str().unwrap_or_default()
+                            ),
+                        )
+                        .at_line(image.line),
+                    );
+                }
+            }
+
+            // Patches only carry the fields they change
+            if is_patch {
+                continue;
+            }
+            let container_non_root = container
+                .path(&["securityContext", "runAsNonRoot"])
+                .map(|v| v.is_true());
+            if !container_non_root.unwrap_or(pod_non_root) {
+                issues.push(
+                    ScannerIssue::new(
+                        "run-as-non-root",
+                        "warning",
+                        rel,
+                        format!("{} does not set runAsNonRoot: true", what),
+                    )
+                    .at_line(container.line),
+                );
+            }
+            if is_init {
+                continue;
+            }
+            if self.require_resources {
+                let missing: Vec<&str> = ["requests", "limits"]
+                    .into_iter()
+                    .filter(|k| {
+                        container
+                            .path(&["resources", k])
+                            .is_none_or(|v| v.entries().is_empty())
+                    })
+                    .collect();
+                if !missing.is_empty() {
+                    issues.push(
+                        ScannerIssue::new(
+                            "missing-resources",
+                            "warning",
+                            rel,
+                            format!("{} has no resource {}", what, missing.join(" or ")),
+                        )
+                        .at_line(container.line),
+                    );
+                }
+            }
+            if self.require_probes && long_running {
+                let missing: Vec<&str> = ["livenessProbe", "readinessProbe"]
+                    .into_iter()
+                    .filter(|k| container.get(k).is_none())
+                    .collect();
+                if !missing.is_empty() {
+                    issues.push(
+                        ScannerIssue::new(
+                            "missing-probes",
+                            "warning",
+                            rel,
+                            format!("{} has no {}", what, missing.join(" or ")),
+                        )
+                        .at_line(container.line),
+                    );
+                }
+            }
+        }
+
+        issues
+    }
+}
+
+impl Default for KubernetesScanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A document is a Kubernetes object when it names both `apiVersion` and `kind`.
+fn is_object(document: &YamlNode) -> bool {
+    document
+        .get("apiVersion")
+        .and_then(|v| v.as_str())
+        .is_some()
+        && document.get("kind").and_then(|v| v.as_str()).is_some()
+}
+
+/// Images with no tag, or tagged `latest`, and no digest.
+pub fn is_unpinned_image(image: &str) -> bool {
+    if image.contains('@') {
+        return false;
+    }
+    let name = image.rsplit('/').next().unwrap_or(image);
+    match name.split_once(':') {
+        Some((_, tag)) => tag == "latest",
+        None => true,
+    }
+}
+
+/// Check a kustomization's local references and record its patch files.
+fn check_kustomization(
+    root: &Path,
+    path: &Path,
+    rel: &str,
+    kustomization: &YamlNode,
+    patches: &mut HashSet<PathBuf>,
+) -> Vec<ScannerIssue> {
+    let dir = path.parent().unwrap_or(root);
+    let mut issues = Vec::new();
+
+    for key in ["resources", "bases", "components"] {
+        let Some(entries) = kustomization.get(key) else {
+            continue;
+        };
+        for entry in entries.items() {
+            let Some(reference) = entry.as_str() else {
+                continue;
+            };
+            if is_remote(reference) {
+                continue;
+            }
+            if !dir.join(reference).exists() {
+                issues.push(
+                    ScannerIssue::new(
+                        "kustomize-missing-resource",
+                        "error",
+                        rel,
+                        format!("{} entry '{}' does not exist", key, reference),
+                    )
+                    .at_line(entry.line),
+                );
+            }
+        }
+    }
+
+    let strategic = kustomization
+        .get("patchesStrategicMerge")
+        .map(|p| p.items())
+        .unwrap_or(&[])
+        .iter()
+        .filter_map(|p| p.as_str());
+    let patch_paths = kustomization
+        .get("patches")
+        .map(|p| p.items())
+        .unwrap_or(&[])
+        .iter()
+        .filter_map(|p| p.get("path").and_then(|v| v.as_str()));
+    for patch in strategic.chain(patch_paths) {
+        patches.insert(normalize(&dir.join(patch)));
+    }
+
+    issues
+}
+
+fn is_remote(reference: &str) -> bool {
+    reference.contains("://") || reference.starts_with("github.com/") || reference.contains("?ref=")
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn relative(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const DEPLOYMENT: &str = r#"apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: web
+spec:
+  template:
+    spec:
+      hostNetwork: true
+      securityContext:
+        runAsNonRoot: true
+      containers:
+        - name: app
+          image: registry.local:5000/web
+          securityContext:
+            privileged: true
+          volumeMounts: []
+        - name: sidecar
+          image: envoy:v1.30@sha256:abc
+          resources:
+            requests: {cpu: 10m}
+            limits: {cpu: 100m}
+          livenessProbe: {tcpSocket: {port: 9901}}
+          readinessProbe: {tcpSocket: {port: 9901}}
+      volumes:
+        - name: docker
+          hostPath:
+            path: /var/run/docker.sock
+---
+apiVersion: v1
+kind: Secret
+metadata:
+  name: creds
+stringData:
+  password: hunter2
+"#;
+
+    #[test]
+    fn flags_workload_and_secret_problems() -> Result<()> {
+        let dir = TempDir::new()?;
+        std::fs::create_dir_all(dir.path().join("k8s"))?;
+        std::fs::write(dir.path().join("k8s/app.yaml"), DEPLOYMENT)?;
+        // Not a Kubernetes object
+        std::fs::write(dir.path().join("config.yml"), "kind: settings\n")?;
+
+        let issues = KubernetesScanner::new().scan(&dir.path().to_string_lossy())?;
+        let mut found: Vec<(&str, usize)> =
+            issues.iter().map(|i| (i.rule.as_str(), i.line)).collect();
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                ("host-network", 8),
+                ("host-path", 27),
+                ("latest-image", 13),
+                ("missing-probes", 12),
+                ("missing-resources", 12),
+                ("plaintext-secret", 34),
+                ("privileged", 15),
+            ]
+        );
+        assert!(issues.iter().all(|i| i.file == "k8s/app.yaml"));
+        Ok(())
+    }
+
+    #[test]
+    fn follows_kustomizations() -> Result<()> {
+        let dir = TempDir::new()?;
+        let overlay = dir.path().join("overlays/prod");
+        std::fs::create_dir_all(&overlay)?;
+        std::fs::create_dir_all(dir.path().join("base"))?;
+        std::fs::write(
+            overlay.join("kustomization.yaml"),
+            "resources:\n  - ../../base\n  - missing.yaml\n  - github.com/org/repo//deploy?ref=v1\npatches:\n  - path: replicas.yaml\n",
+        )?;
+        std::fs::write(
+            overlay.join("replicas.yaml"),
+            "apiVersion: apps/v1\nkind: Deployment\nmetadata:\n  name: web\nspec:\n  template:\n    spec:\n      containers:\n        - name: app\n          image: web:latest\n",
+        )?;
+
+        let issues = KubernetesScanner::new().scan(&dir.path().to_string_lossy())?;
+        let rules: Vec<&str> = issues.iter().map(|i| i.rule.as_str()).collect();
+        assert_eq!(rules, vec!["kustomize-missing-resource", "latest-image"]);
+        assert!(issues[0].message.contains("'missing.yaml'"));
+        Ok(())
+    }
+}
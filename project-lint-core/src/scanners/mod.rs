@@ -1,5 +1,6 @@
 pub mod ast;
 pub mod ci_cd_parity;
+pub mod compose;
 pub mod config_validation;
 pub mod dependency_version_checker;
 pub mod detection;
@@ -7,18 +8,25 @@ pub mod dev_environment;
 pub mod dockerfile_lint;
 pub mod file_naming;
 pub mod git;
+pub mod hcl;
+pub mod kubernetes;
 pub mod markdown_frontmatter;
 pub mod package_organization;
+pub mod profile_structure;
 pub mod runtime_guards;
 pub mod rust_conventions;
 pub mod security;
+pub mod terraform;
 pub mod typescript;
 pub mod typescript_monorepo;
 pub mod vault_security;
+pub mod web;
+pub mod yaml;
 
 /// Generic issue emitted by the project-level scanners (rust_conventions,
-/// dev_environment, ci_cd_parity, dockerfile_lint, typescript_monorepo,
-/// vault_security). Carries enough context for the lint command to format a
+/// dev_environment, ci_cd_parity, dockerfile_lint, compose_lint,
+/// typescript_monorepo, vault_security, profile_structure, web, kubernetes,
+/// terraform). Carries enough context for the lint command to format a
 /// human-readable line and for tests to assert on severity/file/message.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ScannerIssue {
@@ -0,0 +1,127 @@
+//! Profile structure scanner — enforces a profile's `[structure]` section:
+//! every `expected_dirs` entry must exist under the project root and no
+//! `forbidden_dirs` entry may. Missing expected directories can be scaffolded.
+
+use crate::scanners::ScannerIssue;
+use crate::utils::Result;
+use std::path::Path;
+use tracing::info;
+
+pub struct ProfileStructureScanner {
+    profile: String,
+    expected_dirs: Vec<String>,
+    forbidden_dirs: Vec<String>,
+}
+
+impl ProfileStructureScanner {
+    pub fn new(profile: &str, expected_dirs: Vec<String>, forbidden_dirs: Vec<String>) -> Self {
+        Self {
+            profile: profile.to_string(),
+            expected_dirs,
+            forbidden_dirs,
+        }
+    }
+
+    /// Scan a project root for missing expected and present forbidden
+    /// directories. Findings name the directory relative to the root.
+    pub fn scan(&self, project_path: &str) -> Result<Vec<ScannerIssue>> {
+        let root = Path::new(project_path);
+        let mut issues = Vec::new();
+
+        for dir in &self.expected_dirs {
+            if !root.join(dir.trim_end_matches('/')).is_dir() {
+                issues.push(ScannerIssue::new(
+                    "expected-dir",
+                    "warning",
+                    dir,
+                    format!(
+                        "profile '{}' expects directory '{}', which is missing",
+                        self.profile, dir
+                    ),
+                ));
+            }
+        }
+        for dir in &self.forbidden_dirs {
+            if root.join(dir.trim_end_matches('/')).exists() {
+                issues.push(ScannerIssue::new(
+                    "forbidden-dir",
+                    "error",
+                    dir,
+                    format!("profile '{}' forbids directory '{}'", self.profile, dir),
+                ));
+            }
+        }
+
+        Ok(issues)
+    }
+
+    /// Create the directories behind `expected-dir` findings. Returns how
+    /// many were (or, with `dry_run`, would be) created.
+    pub fn apply_fixes(
+        &self,
+        project_path: &str,
+        issues: &[ScannerIssue],
+        dry_run: bool,
+    ) -> Result<usize> {
+        let mut created = 0;
+        for issue in issues.iter().filter(|i| i.rule == "expected-dir") {
+            let dir = Path::new(project_path).join(issue.file.trim_end_matches('/'));
+            if dry_run {
+                info!("Would create directory {:?}", dir);
+            } else {
+                std::fs::create_dir_all(&dir)?;
+                info!("Created directory {:?}", dir);
+            }
+            created += 1;
+        }
+        Ok(created)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn scanner() -> ProfileStructureScanner {
+        ProfileStructureScanner::new(
+            "devops",
+            vec!["infra/".to_string(), "scripts/".to_string()],
+            vec!["secrets/".to_string()],
+        )
+    }
+
+    #[test]
+    fn flags_missing_expected_and_present_forbidden_dirs() -> Result<()> {
+        let dir = TempDir::new()?;
+        std::fs::create_dir_all(dir.path().join("scripts"))?;
+        std::fs::create_dir_all(dir.path().join("secrets"))?;
+        let issues = scanner().scan(&dir.path().to_string_lossy())?;
+        assert_eq!(issues.len(), 2);
+        assert!(issues
+            .iter()
+            .any(|i| i.rule == "expected-dir" && i.file == "infra/"));
+        let forbidden = issues
+            .iter()
+            .find(|i| i.rule == "forbidden-dir")
+            .expect("forbidden dir");
+        assert!(forbidden.message.contains("profile 'devops'"));
+        Ok(())
+    }
+
+    #[test]
+    fn scaffolds_missing_expected_dirs() -> Result<()> {
+        let dir = TempDir::new()?;
+        let root = dir.path().to_string_lossy().to_string();
+        let scanner = scanner();
+        let issues = scanner.scan(&root)?;
+
+        assert_eq!(scanner.apply_fixes(&root, &issues, true)?, 2);
+        assert!(!dir.path().join("infra").exists());
+
+        assert_eq!(scanner.apply_fixes(&root, &issues, false)?, 2);
+        assert!(dir.path().join("infra").is_dir());
+        assert!(scanner.scan(&root)?.is_empty());
+        Ok(())
+    }
+}
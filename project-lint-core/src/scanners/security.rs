@@ -250,6 +250,12 @@ impl SecurityScanner {
         Ok(all_issues)
     }
 
+    /// Scan in-memory content for hardcoded credentials only, for write
+    /// events where the content has not reached the disk yet.
+    pub fn scan_credentials(&self, content: &str, file_label: &str) -> Vec<DetectionIssue> {
+        self.credentials_detector.scan_str(content, file_label)
+    }
+
     /// Apply fixes to a file
     pub fn apply_fixes(
         &self,
@@ -0,0 +1,594 @@
+//! Terraform scanner — checks `.tf` files for unpinned providers and modules,
+//! hardcoded provider credentials, public S3 ACLs, security groups open to
+//! the internet, and `terraform fmt` layout. Root modules (directories that
+//! are not used as a local module source) must also declare a backend and a
+//! `required_version`.
+
+use crate::scanners::hcl::{self, HclAttribute, HclBlock, HclBody};
+use crate::scanners::ScannerIssue;
+use crate::utils::Result;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+const SKIP_DIRS: &[&str] = &[".git", ".terraform", "node_modules", "target"];
+
+/// Provider arguments that must come from variables or the environment.
+const CREDENTIAL_ATTRIBUTES: &[&str] = &[
+    "access_key",
+    "secret_key",
+    "token",
+    "password",
+    "client_secret",
+    "client_certificate_password",
+    "api_key",
+    "api_token",
+    "credentials",
+];
+
+const PUBLIC_ACLS: &[&str] = &["public-read", "public-read-write", "authenticated-read"];
+const OPEN_CIDRS: &[&str] = &["0.0.0.0/0", "::/0"];
+
+pub struct TerraformScanner {
+    check_fmt: bool,
+}
+
+impl TerraformScanner {
+    pub fn new() -> Self {
+        Self { check_fmt: true }
+    }
+
+    pub fn with_config(check_fmt: bool) -> Self {
+        Self { check_fmt }
+    }
+
+    /// Scan a project for Terraform modules and check each `.tf` file.
+    pub fn scan(&self, project_path: &str) -> Result<Vec<ScannerIssue>> {
+        let root = Path::new(project_path);
+        let mut issues = Vec::new();
+
+        // Files grouped by module directory
+        let mut modules: BTreeMap<PathBuf, Vec<(String, String, HclBody)>> = BTreeMap::new();
+        for entry in WalkDir::new(root)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|e| {
+                !(e.depth() > 0
+                    && e.file_type().is_dir()
+                    && SKIP_DIRS.contains(&e.file_name().to_string_lossy().as_ref()))
+            })
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "tf"))
+        {
+            let path = entry.path();
+            let Ok(content) = std::fs::read_to_string(path) else {
+                continue;
+            };
+            let rel = path
+                .strip_prefix(root)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .to_string();
+            let body = hcl::parse(&content);
+            let dir = path.parent().unwrap_or(root).to_path_buf();
+            modules.entry(dir).or_default().push((rel, content, body));
+        }
+
+        // Directories used as a local module source are child modules
+        let mut child_modules = HashSet::new();
+        for (dir, files) in &modules {
+            for (_, _, body) in files {
+                for module in body.blocks_of("module") {
+                    if let Some(source) = module
+                        .body
+                        .attribute("source")
+                        .and_then(|s| s.string_literal())
+                    {
+                        if source.starts_with("./") || source.starts_with("../") {
+                            child_modules.insert(normalize(&dir.join(source)));
+                        }
+                    }
+                }
+            }
+        }
+
+        for (dir, files) in &modules {
+            for (rel, content, body) in files {
+                issues.extend(self.check_file(rel, content, body));
+            }
+            if !child_modules.contains(&normalize(dir)) {
+                issues.extend(check_root_module(files));
+            }
+        }
+
+        Ok(issues)
+    }
+
+    fn check_file(&self, rel: &str, content: &str, body: &HclBody) -> Vec<ScannerIssue> {
+        let mut issues = Vec::new();
+
+        // Provider versions belong in required_providers
+        for terraform in body.blocks_of("terraform") {
+            for providers in terraform.body.blocks_of("required_providers") {
+                for provider in &providers.body.attributes {
+                    let pinned =
+                        provider.string_literal().is_some() || provider.expr.contains("version");
+                    if !pinned {
+                        issues.push(
+                            ScannerIssue::new(
+                                "unpinned-provider",
+                                "warning",
+                                rel,
+                                format!("provider '{}' has no version constraint", provider.name),
+                            )
+                            .at_line(provider.line),
+                        );
+                    }
+                }
+            }
+        }
+
+        for module in body.blocks_of("module") {
+            if let Some(issue) = check_module_pin(rel, module) {
+                issues.push(issue);
+            }
+        }
+
+        for provider in body.blocks_of("provider") {
+            let name = provider.labels.first().map(String::as_str).unwrap_or("");
+            for attribute in credential_literals(&provider.body) {
+                issues.push(
+                    ScannerIssue::new(
+                        "hardcoded-credentials",
+                        "error",
+                        rel,
+                        format!(
+                            "provider '{}' sets '{}' to a literal; use a variable or the environment",
+                            name, attribute.name
+                        ),
+                    )
+                    .at_line(attribute.line),
+                );
+            }
+        }
+
+        for resource in body.blocks_of("resource") {
+            issues.extend(check_resource(rel, resource));
+        }
+
+        if self.check_fmt {
+            let problems = fmt_problems(content);
+            if let Some((line, reason)) = problems.first() {
+                issues.push(
+                    ScannerIssue::new(
+                        "terraform-fmt",
+                        "info",
+                        rel,
+                        format!(
+                            "not formatted like `terraform fmt` ({} line(s), first: {})",
+                            problems.len(),
+                            reason
+                        ),
+                    )
+                    .at_line(*line),
+                );
+            }
+        }
+
+        issues
+    }
+}
+
+impl Default for TerraformScanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A root module needs a backend and a `required_version` in one of its files.
+fn check_root_module(files: &[(String, String, HclBody)]) -> Vec<ScannerIssue> {
+    let terraform_blocks: Vec<&HclBlock> = files
+        .iter()
+        .flat_map(|(_, _, body)| body.blocks_of("terraform"))
+        .collect();
+    let has_config = files.iter().any(|(_, _, body)| {
+        body.blocks
+            .iter()
+            .any(|b| matches!(b.kind.as_str(), "resource" | "module" | "provider"))
+    });
+    if !has_config {
+        return Vec::new();
+    }
+
+    // Report against the file holding the terraform block, else the first
+    let (rel, line) = files
+        .iter()
+        .find_map(|(rel, _, body)| body.blocks_of("terraform").next().map(|b| (rel, b.line)))
+        .unwrap_or((&files[0].0, 0));
+    let mut issues = Vec::new();
+
+    let has_backend = terraform_blocks.iter().any(|t| {
+        t.body
+            .blocks
+            .iter()
+            .any(|b| matches!(b.kind.as_str(), "backend" | "cloud"))
+    });
+    if !has_backend {
+        issues.push(
+            ScannerIssue::new(
+                "missing-backend",
+                "warning",
+                rel,
+                "root module has no backend block; state would be kept locally",
+            )
+            .at_line(line),
+        );
+    }
+    let has_required_version = terraform_blocks
+        .iter()
+        .any(|t| t.body.attribute("required_version").is_some());
+    if !has_required_version {
+        issues.push(
+            ScannerIssue::new(
+                "missing-required-version",
+                "warning",
+                rel,
+                "root module does not set terraform required_version",
+            )
+            .at_line(line),
+        );
+    }
+
+    issues
+}
+
+/// Registry modules need `version`; git modules need a `ref`.
+fn check_module_pin(rel: &str, module: &HclBlock) -> Option<ScannerIssue> {
+    let name = module.labels.first().map(String::as_str).unwrap_or("");
+    let source = module.body.attribute("source")?.string_literal()?;
+    if source.starts_with("./") || source.starts_with("../") {
+        return None;
+    }
+    let is_git = source.starts_with("git::")
+        || source.starts_with("github.com/")
+        || source.starts_with("git@")
+        || source.contains(".git");
+    let pinned = if is_git {
+        source.contains("ref=")
+    } else if source.contains("://") || source.contains("::") {
+        // Archives and buckets are addressed by exact URL
+        true
+    } else {
+        module.body.attribute("version").is_some()
+    };
+    (!pinned).then(|| {
+        ScannerIssue::new(
+            "unpinned-module",
+            "warning",
+            rel,
+            format!(
+                "module '{}' source '{}' is not pinned to a version",
+                name, source
+            ),
+        )
+        .at_line(module.line)
+    })
+}
+
+fn credential_literals(body: &HclBody) -> Vec<&HclAttribute> {
+    let mut found: Vec<&HclAttribute> = body
+        .attributes
+        .iter()
+        .filter(|a| CREDENTIAL_ATTRIBUTES.contains(&a.name.as_str()))
+        .filter(|a| a.string_literal().is_some_and(|v| !v.is_empty()))
+        .collect();
+    for block in &body.blocks {
+        found.extend(credential_literals(&block.body));
+    }
+    found
+}
+
+fn check_resource(rel: &str, resource: &HclBlock) -> Vec<ScannerIssue> {
+    let kind = resource.labels.first().map(String::as_str).unwrap_or("");
+    let name = resource.labels.get(1).map(String::as_str).unwrap_or("");
+    let subject = format!("{}.{}", kind, name);
+    let mut issues = Vec::new();
+
+    if matches!(kind, "aws_s3_bucket" | "aws_s3_bucket_acl") {
+        if let Some(acl) = resource.body.attribute("acl") {
+            if acl
+                .string_literal()
+                .is_some_and(|v| PUBLIC_ACLS.contains(&v))
+            {
+                issues.push(
+                    ScannerIssue::new(
+                        "public-s3-acl",
+                        "error",
+                        rel,
+                        format!("{} grants the {} ACL", subject, acl.expr),
+                    )
+                    .at_line(acl.line),
+                );
+            }
+        }
+    }
+
+    let open_ingress: Vec<usize> = match kind {
+        "aws_security_group" => resource
+            .body
+            .blocks_of("ingress")
+            .filter(|rule| opens_to_world(&rule.body))
+            .map(|rule| rule.line)
+            .collect(),
+        "aws_security_group_rule"
+            if resource
+                .body
+                .attribute("type")
+                .and_then(|t| t.string_literal())
+                == Some("ingress")
+                && opens_to_world(&resource.body) =>
+        {
+            vec![resource.line]
+        }
+        "aws_vpc_security_group_ingress_rule" if opens_to_world(&resource.body) => {
+            vec![resource.line]
+        }
+        _ => Vec::new(),
+    };
+    for line in open_ingress {
+        issues.push(
+            ScannerIssue::new(
+                "open-ingress",
+                "warning",
+                rel,
+                format!("{} allows ingress from anywhere", subject),
+            )
+            .at_line(line),
+        );
+    }
+
+    issues
+}
+
+fn opens_to_world(body: &HclBody) -> bool {
+    ["cidr_blocks", "ipv6_cidr_blocks", "cidr_ipv4", "cidr_ipv6"]
+        .iter()
+        .filter_map(|name| body.attribute(name))
+        .any(|a| {
+            OPEN_CIDRS
+                .iter()
+                .any(|cidr| a.expr.contains(&format!("\"{}\"", cidr)))
+        })
+}
+
+/// Lines that `terraform fmt` would change: tab or misnested indentation,
+/// trailing whitespace, and unaligned `=` in runs of consecutive attributes.
+fn fmt_problems(source: &str) -> Vec<(usize, String)> {
+    let mut problems = Vec::new();
+    let mut depth = 0usize;
+    let mut heredoc: Option<String> = None;
+    // Consecutive attribute lines at one depth: (line, indent, name length, `=` column)
+    let mut run: Vec<(usize, usize, usize, usize)> = Vec::new();
+
+    let flush = |run: &mut Vec<(usize, usize, usize, usize)>,
+                 problems: &mut Vec<(usize, String)>| {
+        if let Some(longest) = run.iter().map(|(_, _, len, _)| *len).max() {
+            for (line, indent, _, column) in run.iter() {
+                if *column != indent + longest + 1 {
+                    problems.push((
+                        *line,
+                        "'=' not aligned with neighbouring attributes".to_string(),
+                    ));
+                }
+            }
+        }
+        run.clear();
+    };
+
+    for (i, raw) in source.lines().enumerate() {
+        let line = i + 1;
+        if let Some(marker) = &heredoc {
+            if raw.trim() == marker {
+                heredoc = None;
+            }
+            continue;
+        }
+        if raw != raw.trim_end() {
+            problems.push((line, "trailing whitespace".to_string()));
+        }
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            flush(&mut run, &mut problems);
+            continue;
+        }
+
+        let code = strip_comment(trimmed);
+        let leading_close = code.starts_with(['}', ']', ')']);
+        let expected = 2 * depth.saturating_sub(usize::from(leading_close));
+        let indent_text = &raw[..raw.len() - raw.trim_start().len()];
+        if indent_text.contains('\t') {
+            problems.push((line, "tab indentation".to_string()));
+        } else if indent_text.len() != expected {
+            problems.push((
+                line,
+                format!(
+                    "indented {} spaces, expected {}",
+                    indent_text.len(),
+                    expected
+                ),
+            ));
+        }
+
+        // Attribute lines take part in `=` alignment
+        match attribute_name(code) {
+            Some(name) if !trimmed.starts_with('#') && !trimmed.starts_with("//") => {
+                let indent = indent_text.len();
+                if run.first().is_some_and(|(_, i, _, _)| *i != indent) {
+                    flush(&mut run, &mut problems);
+                }
+                let column = raw.find('=').unwrap_or(0);
+                run.push((line, indent, name.len(), column));
+            }
+            _ => flush(&mut run, &mut problems),
+        }
+
+        if let Some(at) = code.find("<<") {
+            let marker = code[at + 2..].trim_start_matches('-').trim();
+            if !marker.is_empty() {
+                heredoc = Some(marker.to_string());
+            }
+        }
+        let (opens, closes) = bracket_counts(code);
+        depth = (depth + opens).saturating_sub(closes);
+    }
+    flush(&mut run, &mut problems);
+
+    problems.sort();
+    problems.dedup_by_key(|(line, _)| *line);
+    problems
+}
+
+/// `name` for a line of the form `name = ...`.
+fn attribute_name(code: &str) -> Option<&str> {
+    let (name, rest) = code.split_once('=')?;
+    let name = name.trim_end();
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-');
+    (valid && !rest.starts_with('=')).then_some(name)
+}
+
+fn strip_comment(code: &str) -> &str {
+    let mut in_string = false;
+    let bytes = code.as_bytes();
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            b'"' if i == 0 || bytes[i - 1] != b'\\' => in_string = !in_string,
+            b'#' if !in_string => return code[..i].trim_end(),
+            b'/' if !in_string && bytes.get(i + 1) == Some(&b'/') => return code[..i].trim_end(),
+            _ => {}
+        }
+    }
+    code
+}
+
+fn bracket_counts(code: &str) -> (usize, usize) {
+    let mut in_string = false;
+    let mut opens = 0;
+    let mut closes = 0;
+    let mut previous = ' ';
+    for c in code.chars() {
+        match c {
+            '"' if previous != '\\' => in_string = !in_string,
+            '{' | '[' | '(' if !in_string => opens += 1,
+            '}' | ']' | ')' if !in_string => closes += 1,
+            _ => {}
+        }
+        previous = c;
+    }
+    (opens, closes)
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const MAIN_TF: &str = r#"terraform {
+  required_providers {
+    aws = {
+      source = "hashicorp/aws"
+    }
+    random = "~> 3.5"
+  }
+}
+
+provider "aws" {
+  region     = "eu-west-1"
+  access_key = "AKIAEXAMPLE"
+  secret_key = var.secret_key
+}
+
+module "vpc" {
+  source = "terraform-aws-modules/vpc/aws"
+}
+
+module "pinned" {
+  source = "git::https://example.com/net.git?ref=v1.2.0"
+}
+
+module "local" {
+  source = "./modules/app"
+}
+
+resource "aws_s3_bucket" "site" {
+  bucket = "site"
+  acl    = "public-read"
+}
+
+resource "aws_security_group" "web" {
+  ingress {
+    from_port   = 22
+    to_port     = 22
+    protocol    = "tcp"
+    cidr_blocks = ["0.0.0.0/0"]
+  }
+}
+"#;
+
+    #[test]
+    fn flags_terraform_problems() -> Result<()> {
+        let dir = TempDir::new()?;
+        std::fs::create_dir_all(dir.path().join("modules/app"))?;
+        std::fs::write(dir.path().join("main.tf"), MAIN_TF)?;
+        // A child module is exempt from the backend/required_version checks
+        std::fs::write(
+            dir.path().join("modules/app/main.tf"),
+            "resource \"aws_instance\" \"app\" {\n  ami = \"ami-123\"\n\tinstance_type = \"t3.micro\"\n}\n",
+        )?;
+
+        let issues = TerraformScanner::new().scan(&dir.path().to_string_lossy())?;
+        let mut found: Vec<(&str, &str, usize)> = issues
+            .iter()
+            .map(|i| (i.file.as_str(), i.rule.as_str(), i.line))
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                ("main.tf", "hardcoded-credentials", 12),
+                ("main.tf", "missing-backend", 1),
+                ("main.tf", "missing-required-version", 1),
+                ("main.tf", "open-ingress", 34),
+                ("main.tf", "public-s3-acl", 30),
+                ("main.tf", "unpinned-module", 16),
+                ("main.tf", "unpinned-provider", 3),
+                ("modules/app/main.tf", "terraform-fmt", 3),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn detects_fmt_layout_problems() {
+        assert!(fmt_problems(MAIN_TF).is_empty());
+        let messy = "resource \"a\" \"b\" {\n  name = \"x\"\n  count = 2 \n    tags = {}\n}\n";
+        assert_eq!(
+            fmt_problems(messy),
+            vec![
+                (
+                    2,
+                    "'=' not aligned with neighbouring attributes".to_string()
+                ),
+                (3, "trailing whitespace".to_string()),
+                (4, "indented 4 spaces, expected 2".to_string()),
+            ]
+        );
+    }
+}
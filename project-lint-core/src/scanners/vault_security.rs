@@ -48,25 +48,28 @@ impl VaultSecurityScanner {
             {
                 continue;
             }
-            let name = path.file_name().unwrap_or_default().to_string_lossy();
-            let is_source = name.ends_with(".rs")
-                || name.ends_with(".ts")
-                || name.ends_with(".js")
-                || name.ends_with(".py")
-                || name.ends_with(".go");
-            if !is_source {
+            if !Self::is_source(path) {
                 continue;
             }
-            issues.extend(self.scan_source_file(path, &rel));
+            if let Ok(content) = std::fs::read_to_string(path) {
+                issues.extend(self.scan_content(&content, &rel));
+            }
         }
 
         Ok(issues)
     }
 
-    fn scan_source_file(&self, path: &Path, rel: &str) -> Vec<ScannerIssue> {
-        let Ok(content) = std::fs::read_to_string(path) else {
-            return Vec::new();
-        };
+    /// Whether the scanner looks at `path` (Rust, TypeScript, JavaScript,
+    /// Python and Go sources).
+    pub fn is_source(path: &Path) -> bool {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        [".rs", ".ts", ".js", ".py", ".go"]
+            .iter()
+            .any(|ext| name.ends_with(ext))
+    }
+
+    /// Check in-memory source content, attributing findings to `rel`.
+    pub fn scan_content(&self, content: &str, rel: &str) -> Vec<ScannerIssue> {
         let mut issues = Vec::new();
 
         for (i, line) in content.lines().enumerate() {
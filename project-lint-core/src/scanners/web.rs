@@ -0,0 +1,1014 @@
+//! Web scanner — the checks behind a profile's `[web_specific]` toggles:
+//! accessibility (`alt` on images, `lang` on documents), HTML semantics
+//! (heading order), SEO meta on pages, unknown CSS properties, and oversized
+//! images. Markup (HTML, JSX/TSX, Vue, Svelte) is read with a small tag
+//! tokenizer rather than a full parser, which is enough for these checks.
+
+use crate::scanners::ScannerIssue;
+use crate::utils::Result;
+use std::io::Read;
+use std::path::Path;
+use walkdir::WalkDir;
+
+const MARKUP_EXTENSIONS: &[&str] = &["html", "htm", "jsx", "tsx", "vue", "svelte"];
+const STYLE_EXTENSIONS: &[&str] = &["css", "scss"];
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp"];
+const SKIP_DIRS: &[&str] = &[".git", "node_modules", "target", "dist", "build", ".next"];
+
+/// Only this much of an image is read to find its dimensions.
+const IMAGE_HEADER_BYTES: u64 = 256 * 1024;
+
+pub struct WebScanner {
+    check_html_semantics: bool,
+    check_accessibility: bool,
+    check_seo_meta: bool,
+    validate_css_properties: bool,
+    optimize_images: bool,
+    max_image_bytes: u64,
+    max_image_dimension: u32,
+}
+
+impl WebScanner {
+    pub fn new() -> Self {
+        Self {
+            check_html_semantics: true,
+            check_accessibility: true,
+            check_seo_meta: true,
+            validate_css_properties: true,
+            optimize_images: true,
+            max_image_bytes: 500_000,
+            max_image_dimension: 2560,
+        }
+    }
+
+    pub fn with_config(
+        check_html_semantics: bool,
+        check_accessibility: bool,
+        check_seo_meta: bool,
+        validate_css_properties: bool,
+        optimize_images: bool,
+    ) -> Self {
+        Self {
+            check_html_semantics,
+            check_accessibility,
+            check_seo_meta,
+            validate_css_properties,
+            optimize_images,
+            ..Self::new()
+        }
+    }
+
+    /// Images over `max_bytes`, or wider or taller than `max_dimension`
+    /// pixels, are reported when image checks are on.
+    pub fn with_image_limits(mut self, max_bytes: u64, max_dimension: u32) -> Self {
+        self.max_image_bytes = max_bytes;
+        self.max_image_dimension = max_dimension;
+        self
+    }
+
+    /// Scan a project for markup, stylesheets and images.
+    pub fn scan(&self, project_path: &str) -> Result<Vec<ScannerIssue>> {
+        let root = Path::new(project_path);
+        let mut issues = Vec::new();
+
+        let entries = WalkDir::new(root)
+            .into_iter()
+            .filter_entry(|e| {
+                !(e.depth() > 0
+                    && e.file_type().is_dir()
+                    && SKIP_DIRS.contains(&e.file_name().to_string_lossy().as_ref()))
+            })
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file());
+        for entry in entries {
+            let path = entry.path();
+            let ext = path
+                .extension()
+                .map(|e| e.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            let rel = path
+                .strip_prefix(root)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .to_string();
+
+            if MARKUP_EXTENSIONS.contains(&ext.as_str()) {
+                if let Ok(content) = std::fs::read_to_string(path) {
+                    let html = matches!(ext.as_str(), "html" | "htm");
+                    issues.extend(self.scan_markup(&content, &rel, html));
+                }
+            } else if STYLE_EXTENSIONS.contains(&ext.as_str()) {
+                if self.validate_css_properties {
+                    if let Ok(content) = std::fs::read_to_string(path) {
+                        issues.extend(scan_stylesheet(&content, &rel));
+                    }
+                }
+            } else if IMAGE_EXTENSIONS.contains(&ext.as_str()) && self.optimize_images {
+                issues.extend(self.scan_image(path, &rel)?);
+            }
+        }
+
+        Ok(issues)
+    }
+
+    /// `html` documents have case-insensitive tag names; elsewhere `<Img>`
+    /// is a component, not an `<img>`.
+    fn scan_markup(&self, content: &str, rel: &str, html: bool) -> Vec<ScannerIssue> {
+        let tags = tags(content, html);
+        let mut issues = Vec::new();
+
+        if self.check_accessibility {
+            for tag in &tags {
+                if tag.name == "img" && !tag.has_attr("alt") {
+                    issues.push(
+                        ScannerIssue::new("img-alt", "warning", rel, "<img> without alt text")
+                            .at_line(tag.line),
+                    );
+                }
+                if tag.name == "html" && !tag.has_attr("lang") {
+                    issues.push(
+                        ScannerIssue::new(
+                            "html-lang",
+                            "warning",
+                            rel,
+                            "<html> without a lang attribute",
+                        )
+                        .at_line(tag.line),
+                    );
+                }
+            }
+        }
+
+        if self.check_html_semantics {
+            let mut previous: Option<u8> = None;
+            for tag in &tags {
+                let Some(level) = heading_level(&tag.name) else {
+                    continue;
+                };
+                if let Some(prev) = previous {
+                    if level > prev + 1 {
+                        issues.push(
+                            ScannerIssue::new(
+                                "heading-order",
+                                "warning",
+                                rel,
+                                format!("<h{}> follows <h{}>, skipping a level", level, prev),
+                            )
+                            .at_line(tag.line),
+                        );
+                    }
+                }
+                previous = Some(level);
+            }
+        }
+
+        // Only full documents are pages; components and fragments have no
+        // <html> of their own
+        if self.check_seo_meta {
+            if let Some(html) = tags.iter().find(|t| t.name == "html") {
+                if !tags.iter().any(|t| t.name == "title") {
+                    issues.push(
+                        ScannerIssue::new("missing-title", "warning", rel, "page has no <title>")
+                            .at_line(html.line),
+                    );
+                }
+                let has_description = tags.iter().any(|t| {
+                    t.name == "meta"
+                        && t.attr("name")
+                            .is_some_and(|v| v.eq_ignore_ascii_case("description"))
+                });
+                if !has_description {
+                    issues.push(
+                        ScannerIssue::new(
+                            "missing-meta-description",
+                            "warning",
+                            rel,
+                            "page has no <meta name=\"description\">",
+                        )
+                        .at_line(html.line),
+                    );
+                }
+            }
+        }
+
+        issues
+    }
+
+    fn scan_image(&self, path: &Path, rel: &str) -> Result<Vec<ScannerIssue>> {
+        let mut issues = Vec::new();
+        let size = std::fs::metadata(path)?.len();
+        if size > self.max_image_bytes {
+            issues.push(ScannerIssue::new(
+                "image-size",
+                "warning",
+                rel,
+                format!(
+                    "image is {} KB (limit {} KB)",
+                    size / 1024,
+                    self.max_image_bytes / 1024
+                ),
+            ));
+        }
+
+        let mut header = Vec::new();
+        std::fs::File::open(path)?
+            .take(IMAGE_HEADER_BYTES)
+            .read_to_end(&mut header)?;
+        if let Some((width, height)) = image_dimensions(&header) {
+            if width > self.max_image_dimension || height > self.max_image_dimension {
+                issues.push(ScannerIssue::new(
+                    "image-dimensions",
+                    "warning",
+                    rel,
+                    format!(
+                        "image is {}x{} px (limit {} px per side)",
+                        width, height, self.max_image_dimension
+                    ),
+                ));
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+impl Default for WebScanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An opening tag found in markup.
+struct Tag {
+    name: String,
+    attrs: Vec<(String, Option<String>)>,
+    line: usize,
+}
+
+impl Tag {
+    fn has_attr(&self, name: &str) -> bool {
+        self.attrs.iter().any(|(n, _)| n == name)
+    }
+
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(n, _)| n == name)
+            .and_then(|(_, v)| v.as_deref())
+    }
+}
+
+/// Opening tags in document order. Quotes and JSX `{...}` expressions are
+/// skipped when looking for the end of a tag; comments are ignored. Tag
+/// names are lowercased only when `fold_case` is set.
+fn tags(content: &str, fold_case: bool) -> Vec<Tag> {
+    let chars: Vec<char> = content.chars().collect();
+    let mut tags = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '\n' => line += 1,
+            '<' if chars[i..].starts_with(&['<', '!', '-', '-']) => {
+                while i < chars.len() && !chars[i..].starts_with(&['-', '-', '>']) {
+                    if chars[i] == '\n' {
+                        line += 1;
+                    }
+                    i += 1;
+                }
+            }
+            '<' if chars.get(i + 1).is_some_and(|c| c.is_ascii_alphabetic()) => {
+                let start_line = line;
+                let mut j = i + 1;
+                while j < chars.len() && (chars[j].is_ascii_alphanumeric() || chars[j] == '-') {
+                    j += 1;
+                }
+                let name: String = chars[i + 1..j].iter().collect();
+
+                let mut body = String::new();
+                let mut quote = None;
+                let mut depth = 0usize;
+                while j < chars.len() {
+                    let c = chars[j];
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    match (quote, c) {
+                        (Some(q), c) if c == q => quote = None,
+                        (Some(_), _) => {}
+                        (None, '"' | '\'') => quote = Some(c),
+                        (None, '{') => depth += 1,
+                        (None, '}') => depth = depth.saturating_sub(1),
+                        (None, '>') if depth == 0 => break,
+                        _ => {}
+                    }
+                    body.push(c);
+                    j += 1;
+                }
+
+                tags.push(Tag {
+                    name: if fold_case { name.to_lowercase() } else { name },
+                    attrs: parse_attrs(&body),
+                    line: start_line,
+                });
+                i = j;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    tags
+}
+
+/// Attribute names (lowercased) and unquoted values from a tag body.
+fn parse_attrs(body: &str) -> Vec<(String, Option<String>)> {
+    let chars: Vec<char> = body.chars().collect();
+    let mut attrs = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() || c == '/' {
+            i += 1;
+            continue;
+        }
+        if c == '{' {
+            // JSX spread: {...props}
+            let mut depth = 0;
+            while i < chars.len() {
+                match chars[i] {
+                    '{' => depth += 1,
+                    '}' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        while i < chars.len() && !chars[i].is_whitespace() && !matches!(chars[i], '=' | '/') {
+            i += 1;
+        }
+        let name: String = chars[start..i].iter().collect::<String>().to_lowercase();
+        while i < chars.len() && chars[i].is_whitespace() {
+            i += 1;
+        }
+
+        let mut value = None;
+        if chars.get(i) == Some(&'=') {
+            i += 1;
+            while i < chars.len() && chars[i].is_whitespace() {
+                i += 1;
+            }
+            let (open, close) = match chars.get(i) {
+                Some('"') => (Some('"'), '"'),
+                Some('\'') => (Some('\''), '\''),
+                Some('{') => (Some('{'), '}'),
+                _ => (None, ' '),
+            };
+            let start = if open.is_some() { i + 1 } else { i };
+            let mut j = start;
+            let mut depth = 0;
+            while j < chars.len() {
+                let c = chars[j];
+                if open == Some('{') && c == '{' {
+                    depth += 1;
+                } else if open.is_some() && c == close {
+                    if depth == 0 {
+                        break;
+                    }
+                    depth -= 1;
+                } else if open.is_none() && c.is_whitespace() {
+                    break;
+                }
+                j += 1;
+            }
+            value = Some(chars[start..j.min(chars.len())].iter().collect());
+            i = j + 1;
+        }
+        if !name.is_empty() {
+            attrs.push((name, value));
+        }
+    }
+
+    attrs
+}
+
+fn heading_level(name: &str) -> Option<u8> {
+    match name.as_bytes() {
+        [b'h', level @ b'1'..=b'6'] => Some(level - b'0'),
+        _ => None,
+    }
+}
+
+/// Flag declarations whose property is not a known CSS property. Custom
+/// properties, vendor-prefixed properties and SCSS variables are skipped.
+fn scan_stylesheet(content: &str, rel: &str) -> Vec<ScannerIssue> {
+    let mut issues = Vec::new();
+    let mut segment = String::new();
+    let mut segment_line = 1;
+    let mut line = 1;
+    let mut parens = 0usize;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut last = ' ';
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    if last == '*' && c == '/' {
+                        break;
+                    }
+                    last = c;
+                }
+            }
+            // SCSS line comment; `//` inside `url(...)` is not one
+            '/' if chars.peek() == Some(&'/') && parens == 0 => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        line += 1;
+                        break;
+                    }
+                }
+            }
+            '{' => segment.clear(),
+            ';' | '}' => {
+                if let Some(property) = declared_property(&segment) {
+                    if !KNOWN_CSS_PROPERTIES.contains(&property.as_str()) {
+                        issues.push(
+                            ScannerIssue::new(
+                                "unknown-css-property",
+                                "warning",
+                                rel,
+                                format!("unknown CSS property '{}'", property),
+                            )
+                            .at_line(segment_line),
+                        );
+                    }
+                }
+                segment.clear();
+                parens = 0;
+            }
+            _ => {
+                match c {
+                    '(' => parens += 1,
+                    ')' => parens = parens.saturating_sub(1),
+                    _ => {}
+                }
+                if segment.trim().is_empty() && !c.is_whitespace() {
+                    segment_line = line;
+                }
+                if c == '\n' {
+                    line += 1;
+                }
+                segment.push(c);
+            }
+        }
+    }
+
+    issues
+}
+
+fn declared_property(segment: &str) -> Option<String> {
+    let (property, _) = segment.split_once(':')?;
+    let property = property.trim().to_lowercase();
+    let plain = property.chars().all(|c| c.is_ascii_lowercase() || c == '-');
+    if property.is_empty() || !plain || property.starts_with('-') {
+        return None;
+    }
+    Some(property)
+}
+
+/// Width and height from a PNG, GIF, JPEG or WebP header.
+fn image_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |at: usize| Some(u16::from_be_bytes(data.get(at..at + 2)?.try_into().ok()?) as u32);
+    let le16 = |at: usize| Some(u16::from_le_bytes(data.get(at..at + 2)?.try_into().ok()?) as u32);
+    let be32 = |at: usize| Some(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?));
+    let le24 = |at: usize| {
+        let b = data.get(at..at + 3)?;
+        Some(b[0] as u32 | (b[1] as u32) << 8 | (b[2] as u32) << 16)
+    };
+
+    if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return Some((be32(16)?, be32(20)?));
+    }
+    if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        return Some((le16(6)?, le16(8)?));
+    }
+    if data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WEBP") {
+        return match data.get(12..16)? {
+            b"VP8 " => Some((le16(26)? & 0x3fff, le16(28)? & 0x3fff)),
+            b"VP8L" => {
+                let bits = u32::from_le_bytes(data.get(21..25)?.try_into().ok()?);
+                Some(((bits & 0x3fff) + 1, ((bits >> 14) & 0x3fff) + 1))
+            }
+            b"VP8X" => Some((le24(24)? + 1, le24(27)? + 1)),
+            _ => None,
+        };
+    }
+    if data.starts_with(&[0xff, 0xd8]) {
+        // Walk the JPEG segments to the first start-of-frame marker
+        let mut at = 2;
+        while at + 4 <= data.len() {
+            if data[at] != 0xff {
+                return None;
+            }
+            let marker = data[at + 1];
+            let length = be16(at + 2)? as usize;
+            let is_sof = matches!(marker, 0xc0..=0xcf) && !matches!(marker, 0xc4 | 0xc8 | 0xcc);
+            if is_sof {
+                return Some((be16(at + 7)?, be16(at + 5)?));
+            }
+            at += 2 + length;
+        }
+    }
+    None
+}
+
+/// Standard CSS properties accepted by `validate_css_properties`.
+const KNOWN_CSS_PROPERTIES: &[&str] = &[
+    "accent-color",
+    "align-content",
+    "align-items",
+    "align-self",
+    "all",
+    "animation",
+    "animation-composition",
+    "animation-delay",
+    "animation-direction",
+    "animation-duration",
+    "animation-fill-mode",
+    "animation-iteration-count",
+    "animation-name",
+    "animation-play-state",
+    "animation-timing-function",
+    "appearance",
+    "aspect-ratio",
+    "backdrop-filter",
+    "backface-visibility",
+    "background",
+    "background-attachment",
+    "background-blend-mode",
+    "background-clip",
+    "background-color",
+    "background-image",
+    "background-origin",
+    "background-position",
+    "background-position-x",
+    "background-position-y",
+    "background-repeat",
+    "background-size",
+    "block-size",
+    "border",
+    "border-block",
+    "border-block-color",
+    "border-block-end",
+    "border-block-start",
+    "border-block-style",
+    "border-block-width",
+    "border-bottom",
+    "border-bottom-color",
+    "border-bottom-left-radius",
+    "border-bottom-right-radius",
+    "border-bottom-style",
+    "border-bottom-width",
+    "border-collapse",
+    "border-color",
+    "border-end-end-radius",
+    "border-end-start-radius",
+    "border-image",
+    "border-image-outset",
+    "border-image-repeat",
+    "border-image-slice",
+    "border-image-source",
+    "border-image-width",
+    "border-inline",
+    "border-inline-color",
+    "border-inline-end",
+    "border-inline-start",
+    "border-inline-style",
+    "border-inline-width",
+    "border-left",
+    "border-left-color",
+    "border-left-style",
+    "border-left-width",
+    "border-radius",
+    "border-right",
+    "border-right-color",
+    "border-right-style",
+    "border-right-width",
+    "border-spacing",
+    "border-start-end-radius",
+    "border-start-start-radius",
+    "border-style",
+    "border-top",
+    "border-top-color",
+    "border-top-left-radius",
+    "border-top-right-radius",
+    "border-top-style",
+    "border-top-width",
+    "border-width",
+    "bottom",
+    "box-decoration-break",
+    "box-shadow",
+    "box-sizing",
+    "break-after",
+    "break-before",
+    "break-inside",
+    "caption-side",
+    "caret-color",
+    "clear",
+    "clip",
+    "clip-path",
+    "clip-rule",
+    "color",
+    "color-scheme",
+    "column-count",
+    "column-fill",
+    "column-gap",
+    "column-rule",
+    "column-rule-color",
+    "column-rule-style",
+    "column-rule-width",
+    "column-span",
+    "column-width",
+    "columns",
+    "contain",
+    "contain-intrinsic-size",
+    "container",
+    "container-name",
+    "container-type",
+    "content",
+    "content-visibility",
+    "counter-increment",
+    "counter-reset",
+    "counter-set",
+    "cursor",
+    "direction",
+    "display",
+    "empty-cells",
+    "fill",
+    "fill-opacity",
+    "fill-rule",
+    "filter",
+    "flex",
+    "flex-basis",
+    "flex-direction",
+    "flex-flow",
+    "flex-grow",
+    "flex-shrink",
+    "flex-wrap",
+    "float",
+    "font",
+    "font-display",
+    "font-family",
+    "font-feature-settings",
+    "font-kerning",
+    "font-optical-sizing",
+    "font-size",
+    "font-size-adjust",
+    "font-stretch",
+    "font-style",
+    "font-synthesis",
+    "font-variant",
+    "font-variant-caps",
+    "font-variant-ligatures",
+    "font-variant-numeric",
+    "font-variation-settings",
+    "font-weight",
+    "gap",
+    "grid",
+    "grid-area",
+    "grid-auto-columns",
+    "grid-auto-flow",
+    "grid-auto-rows",
+    "grid-column",
+    "grid-column-end",
+    "grid-column-start",
+    "grid-row",
+    "grid-row-end",
+    "grid-row-start",
+    "grid-template",
+    "grid-template-areas",
+    "grid-template-columns",
+    "grid-template-rows",
+    "hanging-punctuation",
+    "height",
+    "hyphens",
+    "image-rendering",
+    "inline-size",
+    "inset",
+    "inset-block",
+    "inset-block-end",
+    "inset-block-start",
+    "inset-inline",
+    "inset-inline-end",
+    "inset-inline-start",
+    "isolation",
+    "justify-content",
+    "justify-items",
+    "justify-self",
+    "left",
+    "letter-spacing",
+    "line-break",
+    "line-clamp",
+    "line-height",
+    "list-style",
+    "list-style-image",
+    "list-style-position",
+    "list-style-type",
+    "margin",
+    "margin-block",
+    "margin-block-end",
+    "margin-block-start",
+    "margin-bottom",
+    "margin-inline",
+    "margin-inline-end",
+    "margin-inline-start",
+    "margin-left",
+    "margin-right",
+    "margin-top",
+    "marker",
+    "mask",
+    "mask-clip",
+    "mask-composite",
+    "mask-image",
+    "mask-mode",
+    "mask-origin",
+    "mask-position",
+    "mask-repeat",
+    "mask-size",
+    "max-block-size",
+    "max-height",
+    "max-inline-size",
+    "max-width",
+    "min-block-size",
+    "min-height",
+    "min-inline-size",
+    "min-width",
+    "mix-blend-mode",
+    "object-fit",
+    "object-position",
+    "offset",
+    "offset-distance",
+    "offset-path",
+    "offset-rotate",
+    "opacity",
+    "order",
+    "orphans",
+    "outline",
+    "outline-color",
+    "outline-offset",
+    "outline-style",
+    "outline-width",
+    "overflow",
+    "overflow-anchor",
+    "overflow-wrap",
+    "overflow-x",
+    "overflow-y",
+    "overscroll-behavior",
+    "overscroll-behavior-x",
+    "overscroll-behavior-y",
+    "padding",
+    "padding-block",
+    "padding-block-end",
+    "padding-block-start",
+    "padding-bottom",
+    "padding-inline",
+    "padding-inline-end",
+    "padding-inline-start",
+    "padding-left",
+    "padding-right",
+    "padding-top",
+    "page-break-after",
+    "page-break-before",
+    "page-break-inside",
+    "paint-order",
+    "perspective",
+    "perspective-origin",
+    "place-content",
+    "place-items",
+    "place-self",
+    "pointer-events",
+    "position",
+    "print-color-adjust",
+    "quotes",
+    "resize",
+    "right",
+    "rotate",
+    "row-gap",
+    "scale",
+    "scroll-behavior",
+    "scroll-margin",
+    "scroll-margin-block",
+    "scroll-margin-bottom",
+    "scroll-margin-inline",
+    "scroll-margin-left",
+    "scroll-margin-right",
+    "scroll-margin-top",
+    "scroll-padding",
+    "scroll-padding-block",
+    "scroll-padding-bottom",
+    "scroll-padding-inline",
+    "scroll-padding-left",
+    "scroll-padding-right",
+    "scroll-padding-top",
+    "scroll-snap-align",
+    "scroll-snap-stop",
+    "scroll-snap-type",
+    "scrollbar-color",
+    "scrollbar-gutter",
+    "scrollbar-width",
+    "shape-image-threshold",
+    "shape-margin",
+    "shape-outside",
+    "shape-rendering",
+    "speak",
+    "src",
+    "stop-color",
+    "stop-opacity",
+    "stroke",
+    "stroke-dasharray",
+    "stroke-dashoffset",
+    "stroke-linecap",
+    "stroke-linejoin",
+    "stroke-miterlimit",
+    "stroke-opacity",
+    "stroke-width",
+    "tab-size",
+    "table-layout",
+    "text-align",
+    "text-align-last",
+    "text-anchor",
+    "text-combine-upright",
+    "text-decoration",
+    "text-decoration-color",
+    "text-decoration-line",
+    "text-decoration-skip-ink",
+    "text-decoration-style",
+    "text-decoration-thickness",
+    "text-emphasis",
+    "text-emphasis-color",
+    "text-emphasis-position",
+    "text-emphasis-style",
+    "text-indent",
+    "text-justify",
+    "text-orientation",
+    "text-overflow",
+    "text-rendering",
+    "text-shadow",
+    "text-transform",
+    "text-underline-offset",
+    "text-underline-position",
+    "text-wrap",
+    "top",
+    "touch-action",
+    "transform",
+    "transform-box",
+    "transform-origin",
+    "transform-style",
+    "transition",
+    "transition-behavior",
+    "transition-delay",
+    "transition-duration",
+    "transition-property",
+    "transition-timing-function",
+    "translate",
+    "unicode-bidi",
+    "unicode-range",
+    "user-select",
+    "vector-effect",
+    "vertical-align",
+    "view-transition-name",
+    "visibility",
+    "white-space",
+    "white-space-collapse",
+    "widows",
+    "width",
+    "will-change",
+    "word-break",
+    "word-spacing",
+    "word-wrap",
+    "writing-mode",
+    "z-index",
+    "zoom",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn flags_markup_and_stylesheet_problems() -> Result<()> {
+        let dir = TempDir::new()?;
+        std::fs::write(
+            dir.path().join("index.html"),
+            "<!doctype html>\n<html>\n<head><!-- <title> --></head>\n<body>\n\
+             <h1>Home</h1>\n<h3>News</h3>\n<img src=\"a.png\">\n</body>\n</html>\n",
+        )?;
+        std::fs::write(
+            dir.path().join("Card.tsx"),
+            "export const Card = (p: Props) => (\n  <div {...p}>\n    <h2>{p.title}</h2>\n\
+             \x20   <img src={p.src} alt={p.title > '' ? p.title : 'card'} />\n  </div>\n);\n",
+        )?;
+        std::fs::write(
+            dir.path().join("site.scss"),
+            "/* colr: red; */\n.a {\n  colr: red;\n  background: url(https://x.test/a.png);\n  --brand: blue;\n  -webkit-appearance: none;\n\
+             \x20 &:hover { color: var(--brand); }\n}\n",
+        )?;
+
+        let issues = WebScanner::new().scan(&dir.path().to_string_lossy())?;
+        let mut found: Vec<(&str, &str, usize)> = issues
+            .iter()
+            .map(|i| (i.file.as_str(), i.rule.as_str(), i.line))
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                ("index.html", "heading-order", 6),
+                ("index.html", "html-lang", 2),
+                ("index.html", "img-alt", 7),
+                ("index.html", "missing-meta-description", 2),
+                ("index.html", "missing-title", 2),
+                ("site.scss", "unknown-css-property", 3),
+            ]
+        );
+
+        // Toggles switch individual checks off
+        let issues = WebScanner::with_config(false, true, false, false, false)
+            .scan(&dir.path().to_string_lossy())?;
+        let rules: Vec<&str> = issues.iter().map(|i| i.rule.as_str()).collect();
+        assert_eq!(rules.len(), 2);
+        assert!(rules.contains(&"img-alt") && rules.contains(&"html-lang"));
+        Ok(())
+    }
+
+    #[test]
+    fn tag_names_are_case_sensitive_outside_html() {
+        let scanner = WebScanner::new();
+        let markup = "<H1>Title</H1>\n<H3>Sub</H3>\n<IMG src=\"a.png\">\n";
+        let rules = |issues: Vec<ScannerIssue>| -> Vec<(String, usize)> {
+            issues.into_iter().map(|i| (i.rule, i.line)).collect()
+        };
+        assert_eq!(
+            rules(scanner.scan_markup(markup, "legacy.html", true)),
+            vec![("img-alt".to_string(), 3), ("heading-order".to_string(), 2)]
+        );
+
+        // Components that share a name with an element are not that element
+        let jsx = "<Layout>\n  <H1>Title</H1>\n  <H3>Sub</H3>\n  <Img src={src} />\n  <img src={src} />\n</Layout>\n";
+        assert_eq!(
+            rules(scanner.scan_markup(jsx, "Page.tsx", false)),
+            vec![("img-alt".to_string(), 5)]
+        );
+    }
+
+    #[test]
+    fn flags_oversized_images_from_headers() -> Result<()> {
+        let dir = TempDir::new()?;
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec();
+        png.extend_from_slice(&4000u32.to_be_bytes());
+        png.extend_from_slice(&300u32.to_be_bytes());
+        std::fs::write(dir.path().join("hero.png"), &png)?;
+        let mut gif = b"GIF89a".to_vec();
+        gif.extend_from_slice(&64u16.to_le_bytes());
+        gif.extend_from_slice(&64u16.to_le_bytes());
+        gif.resize(2048, 0);
+        std::fs::write(dir.path().join("icon.gif"), &gif)?;
+
+        let issues = WebScanner::new()
+            .with_image_limits(1024, 2560)
+            .scan(&dir.path().to_string_lossy())?;
+        let mut found: Vec<(&str, &str)> = issues
+            .iter()
+            .map(|i| (i.file.as_str(), i.rule.as_str()))
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            vec![("hero.png", "image-dimensions"), ("icon.gif", "image-size")]
+        );
+
+        let jpeg = [
+            0xff, 0xd8, 0xff, 0xe0, 0x00, 0x04, 0x00, 0x00, 0xff, 0xc0, 0x00, 0x11, 0x08, 0x02,
+            0x58, 0x03, 0x20,
+        ];
+        assert_eq!(image_dimensions(&jpeg), Some((800, 600)));
+        Ok(())
+    }
+}
@@ -0,0 +1,260 @@
+//! Minimal YAML document model built on tree-sitter-yaml, shared by the
+//! manifest scanners. Values keep the line they start on so findings can
+//! point at the offending key. Anchors and tags are ignored and aliases read
+//! as null.
+
+use crate::utils::Result;
+use tree_sitter::{Node, Parser};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum YamlValue {
+    Mapping(Vec<(String, YamlNode)>),
+    Sequence(Vec<YamlNode>),
+    Scalar(String),
+    Null,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct YamlNode {
+    pub value: YamlValue,
+    /// 1-based line the value starts on.
+    pub line: usize,
+}
+
+impl YamlNode {
+    /// Value under `key` when this is a mapping.
+    pub fn get(&self, key: &str) -> Option<&YamlNode> {
+        self.entries()
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+
+    /// Value at a path of mapping keys.
+    pub fn path(&self, keys: &[&str]) -> Option<&YamlNode> {
+        keys.iter().try_fold(self, |node, key| node.get(key))
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.value {
+            YamlValue::Scalar(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.as_str()? {
+            "true" | "True" | "TRUE" => Some(true),
+            "false" | "False" | "FALSE" => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Whether the value is a scalar `true`.
+    pub fn is_true(&self) -> bool {
+        self.as_bool() == Some(true)
+    }
+
+    /// Mapping entries; empty for anything else.
+    pub fn entries(&self) -> &[(String, YamlNode)] {
+        match &self.value {
+            YamlValue::Mapping(entries) => entries,
+            _ => &[],
+        }
+    }
+
+    /// Sequence items; empty for anything else.
+    pub fn items(&self) -> &[YamlNode] {
+        match &self.value {
+            YamlValue::Sequence(items) => items,
+            _ => &[],
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.value == YamlValue::Null
+    }
+}
+
+/// Parse every document of a (possibly multi-document) YAML source.
+pub fn parse_documents(source: &str) -> Result<Vec<YamlNode>> {
+    let mut parser = Parser::new();
+    parser.set_language(&tree_sitter_yaml::LANGUAGE.into())?;
+    let tree = parser
+        .parse(source, None)
+        .ok_or_else(|| anyhow::anyhow!("YAML parser returned no tree"))?;
+
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+    let documents = root
+        .named_children(&mut cursor)
+        .filter(|n| n.kind() == "document")
+        .map(|n| convert(n, source))
+        .collect();
+    Ok(documents)
+}
+
+fn convert(node: Node, source: &str) -> YamlNode {
+    let line = node.start_position().row + 1;
+    let text = node.utf8_text(source.as_bytes()).unwrap_or_default();
+    let value = match node.kind() {
+        "document" | "block_node" | "flow_node" => {
+            let mut cursor = node.walk();
+            let content = node
+                .named_children(&mut cursor)
+                .filter(|c| !matches!(c.kind(), "anchor" | "tag" | "comment"))
+                .last();
+            return match content {
+                Some(content) => convert(content, source),
+                None => YamlNode {
+                    value: YamlValue::Null,
+                    line,
+                },
+            };
+        }
+        "block_mapping" | "flow_mapping" => {
+            let mut cursor = node.walk();
+            let entries = node
+                .named_children(&mut cursor)
+                .filter_map(|pair| match pair.kind() {
+                    "block_mapping_pair" | "flow_pair" => {
+                        let key = pair.child_by_field_name("key")?;
+                        let key = convert(key, source).as_str()?.to_string();
+                        let value = match pair.child_by_field_name("value") {
+                            Some(value) => convert(value, source),
+                            None => YamlNode {
+                                value: YamlValue::Null,
+                                line: pair.start_position().row + 1,
+                            },
+                        };
+                        Some((key, value))
+                    }
+                    // `{a, b}`: keys without values
+                    "flow_node" => {
+                        let key = convert(pair, source).as_str()?.to_string();
+                        Some((
+                            key,
+                            YamlNode {
+                                value: YamlValue::Null,
+                                line: pair.start_position().row + 1,
+                            },
+                        ))
+                    }
+                    _ => None,
+                })
+                .collect();
+            YamlValue::Mapping(entries)
+        }
+        "block_sequence" | "flow_sequence" => {
+            let mut cursor = node.walk();
+            let items = node
+                .named_children(&mut cursor)
+                .filter(|c| c.kind() != "comment")
+                .map(|item| {
+                    if item.kind() == "block_sequence_item" {
+                        let mut cursor = item.walk();
+                        let content = item
+                            .named_children(&mut cursor)
+                            .find(|c| c.kind() != "comment");
+                        match content {
+                            Some(content) => convert(content, source),
+                            None => YamlNode {
+                                value: YamlValue::Null,
+                                line: item.start_position().row + 1,
+                            },
+                        }
+                    } else {
+                        convert(item, source)
+                    }
+                })
+                .collect();
+            YamlValue::Sequence(items)
+        }
+        "plain_scalar" => match text.trim() {
+            "~" | "null" | "Null" | "NULL" => YamlValue::Null,
+            plain => YamlValue::Scalar(plain.to_string()),
+        },
+        "double_quote_scalar" => YamlValue::Scalar(unescape_double_quoted(text)),
+        "single_quote_scalar" => {
+            YamlValue::Scalar(text.trim_matches('\'').replace("''", "'").to_string())
+        }
+        "block_scalar" => YamlValue::Scalar(block_scalar_content(text)),
+        "alias" | "ERROR" => YamlValue::Null,
+        _ => YamlValue::Scalar(text.to_string()),
+    };
+    YamlNode { value, line }
+}
+
+fn unescape_double_quoted(text: &str) -> String {
+    let inner = text
+        .strip_prefix('"')
+        .and_then(|t| t.strip_suffix('"'))
+        .unwrap_or(text);
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    out
+}
+
+/// Body of a `|` or `>` scalar with its common indentation removed.
+fn block_scalar_content(text: &str) -> String {
+    let body: Vec<&str> = text.lines().skip(1).collect();
+    let indent = body
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    body.iter()
+        .map(|l| l.get(indent..).unwrap_or("").trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_documents_with_lines() -> Result<()> {
+        let source = "kind: Pod\nmetadata: {name: web, labels: {app: \"a\"}}\nspec:\n  containers:\n    - name: c\n      image: 'nginx:1.25'\n      ports: [80, 443]\n---\n# second\nkind: Secret\ndata:\n  token: |\n    abc\n  empty:\n";
+        let docs = parse_documents(source)?;
+        assert_eq!(docs.len(), 2);
+
+        let pod = &docs[0];
+        assert_eq!(pod.get("kind").and_then(|k| k.as_str()), Some("Pod"));
+        assert_eq!(
+            pod.path(&["metadata", "labels", "app"])
+                .and_then(|v| v.as_str()),
+            Some("a")
+        );
+        let container = &pod.path(&["spec", "containers"]).unwrap().items()[0];
+        assert_eq!(container.line, 5);
+        assert_eq!(
+            container.get("image").and_then(|v| v.as_str()),
+            Some("nginx:1.25")
+        );
+        assert_eq!(container.get("ports").unwrap().items().len(), 2);
+
+        let secret = &docs[1];
+        assert_eq!(
+            secret.path(&["data", "token"]).and_then(|v| v.as_str()),
+            Some("abc")
+        );
+        assert!(secret.path(&["data", "empty"]).unwrap().is_null());
+        Ok(())
+    }
+}
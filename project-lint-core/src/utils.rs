@@ -1,4 +1,6 @@
 use anyhow::Result as AnyhowResult;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock, PoisonError};
 use thiserror::Error;
 
 pub type Result<T> = AnyhowResult<T>;
@@ -28,10 +30,13 @@ pub enum ProjectLintError {
 }
 
 pub fn get_project_root() -> Result<std::path::PathBuf> {
-    let current_dir = std::env::current_dir()?;
+    find_project_root(&std::env::current_dir()?)
+}
 
+/// The nearest directory at or above `start` that holds a git repository.
+pub fn find_project_root(start: &std::path::Path) -> Result<std::path::PathBuf> {
     // Walk up the directory tree to find a git repository
-    let mut path = current_dir.clone();
+    let mut path = start.to_path_buf();
     while path.parent().is_some() {
         if path.join(".git").exists() {
             return Ok(path);
@@ -45,8 +50,13 @@ pub fn get_project_root() -> Result<std::path::PathBuf> {
 }
 
 pub fn get_config_dir() -> Result<std::path::PathBuf> {
+    config_dir_for(&std::env::current_dir()?)
+}
+
+/// [`get_config_dir`] as seen from `start` instead of the current directory.
+pub fn config_dir_for(start: &std::path::Path) -> Result<std::path::PathBuf> {
     // First try project-specific config
-    let project_root = get_project_root()?;
+    let project_root = find_project_root(start)?;
     let project_config = project_root.join(".config").join("project-lint");
     if project_config.exists() {
         return Ok(project_config);
@@ -106,3 +116,60 @@ pub fn path_exists_glob(project_root: &std::path::Path, spec: &str) -> bool {
     }
     false
 }
+
+/// Process-wide cache of values compiled from a source string (regexes,
+/// matchers, parsed expressions). Each key is compiled once, failures
+/// included when `T` carries them. A panic while the lock was held leaves
+/// the map consistent, so a poisoned lock is recovered rather than
+/// propagated.
+pub struct CompiledCache<T> {
+    entries: OnceLock<Mutex<HashMap<String, T>>>,
+}
+
+impl<T: Clone> CompiledCache<T> {
+    pub const fn new() -> Self {
+        Self {
+            entries: OnceLock::new(),
+        }
+    }
+
+    /// The cached value for `key`, compiling it with `compile` on first use.
+    pub fn get_or_compile(&self, key: &str, compile: impl FnOnce() -> T) -> T {
+        let mut entries = self
+            .entries
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(value) = entries.get(key) {
+            return value.clone();
+        }
+        let value = compile();
+        entries.insert(key.to_string(), value.clone());
+        value
+    }
+}
+
+impl<T: Clone> Default for CompiledCache<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compiled_cache_compiles_once_and_survives_a_poisoned_lock() {
+        static CACHE: CompiledCache<usize> = CompiledCache::new();
+        assert_eq!(CACHE.get_or_compile("a", || 1), 1);
+        assert_eq!(CACHE.get_or_compile("a", || 2), 1);
+
+        let panicked = std::panic::catch_unwind(|| {
+            CACHE.get_or_compile("b", || panic!("compile failed"));
+        });
+        assert!(panicked.is_err());
+        assert_eq!(CACHE.get_or_compile("a", || 3), 1);
+        assert_eq!(CACHE.get_or_compile("b", || 4), 4);
+    }
+}
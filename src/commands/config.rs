@@ -0,0 +1,145 @@
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use project_lint_core::config::CURRENT_CONFIG_VERSION;
+use project_lint_core::migrate::plan_migration;
+use project_lint_core::utils::Result;
+use std::path::PathBuf;
+use tracing::info;
+
+#[derive(Args)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub command: ConfigCommand,
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// Migrate legacy [git]/[files]/[directories] sections into modular rules
+    Migrate(MigrateArgs),
+}
+
+#[derive(Args)]
+pub struct MigrateArgs {
+    /// Config directory containing config.toml (overrides project-local discovery)
+    #[arg(long)]
+    pub config_dir: Option<String>,
+
+    /// Show the diff without writing any files
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+pub async fn run(args: ConfigArgs) -> Result<()> {
+    match args.command {
+        ConfigCommand::Migrate(migrate_args) => run_migrate(migrate_args).await,
+    }
+}
+
+async fn run_migrate(args: MigrateArgs) -> Result<()> {
+    let config_dir = match &args.config_dir {
+        Some(dir) => PathBuf::from(dir),
+        None => project_lint_core::utils::get_config_dir()?,
+    };
+
+    let plan = plan_migration(&config_dir)?;
+    if plan.is_empty() {
+        println!(
+            "{}",
+            format!("✓ Config is already at version {}", CURRENT_CONFIG_VERSION).green()
+        );
+        return Ok(());
+    }
+
+    for name in &plan.skipped_rules {
+        println!(
+            "{}",
+            format!(
+                "Keeping existing rule '{}'; its legacy section is dropped",
+                name
+            )
+            .yellow()
+        );
+    }
+    for file in &plan.files {
+        println!("{}", file.diff());
+    }
+
+    if args.dry_run {
+        println!(
+            "{}",
+            format!(
+                "Dry run: would migrate config from version {} to {} ({} files)",
+                plan.from_version,
+                CURRENT_CONFIG_VERSION,
+                plan.files.len()
+            )
+            .yellow()
+        );
+        return Ok(());
+    }
+
+    plan.apply()?;
+    info!("Migrated config in {:?}", config_dir);
+    println!(
+        "{}",
+        format!(
+            "✓ Migrated config from version {} to {} ({} files written)",
+            plan.from_version,
+            CURRENT_CONFIG_VERSION,
+            plan.files.len()
+        )
+        .green()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct TestCli {
+        #[command(subcommand)]
+        command: TestCommands,
+    }
+
+    #[derive(Subcommand)]
+    enum TestCommands {
+        Config(ConfigArgs),
+    }
+
+    #[test]
+    fn test_config_migrate_args_default() {
+        let cli = TestCli::parse_from(["test", "config", "migrate"]);
+        match cli.command {
+            TestCommands::Config(ConfigArgs {
+                command: ConfigCommand::Migrate(args),
+            }) => {
+                assert!(args.config_dir.is_none());
+                assert!(!args.dry_run);
+            }
+        }
+    }
+
+    #[test]
+    fn test_config_migrate_args_flags() {
+        let cli = TestCli::parse_from([
+            "test",
+            "config",
+            "migrate",
+            "--config-dir",
+            "/tmp/pl",
+            "--dry-run",
+        ]);
+        match cli.command {
+            TestCommands::Config(ConfigArgs {
+                command: ConfigCommand::Migrate(args),
+            }) => {
+                assert_eq!(args.config_dir.as_deref(), Some("/tmp/pl"));
+                assert!(args.dry_run);
+            }
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use clap::Args;
+use colored::Colorize;
+use project_lint_core::hooks::daemon::{serve, DaemonClient};
+use project_lint_core::utils::Result;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Args)]
+pub struct DaemonArgs {
+    /// Socket to listen on (defaults to the runtime directory)
+    #[arg(long)]
+    pub socket: Option<PathBuf>,
+
+    /// Report whether a daemon answers on the socket, then exit
+    #[arg(long, conflicts_with = "stop")]
+    pub status: bool,
+
+    /// Ask the daemon on the socket to stop, then exit
+    #[arg(long)]
+    pub stop: bool,
+}
+
+pub async fn run(args: DaemonArgs) -> Result<()> {
+    let socket = args.socket.unwrap_or_else(DaemonClient::default_socket);
+    let client = DaemonClient::new(socket.clone(), Duration::from_secs(2));
+
+    if args.status {
+        match client.ping() {
+            Ok(info) => println!(
+                "{} daemon {} (pid {}) listening on {}",
+                "✅".green(),
+                info["version"].as_str().unwrap_or("?"),
+                info["pid"],
+                socket.display()
+            ),
+            Err(e) => println!("{} No daemon on {}: {}", "❌".red(), socket.display(), e),
+        }
+        return Ok(());
+    }
+
+    if args.stop {
+        client.shutdown()?;
+        println!("{} Stopped daemon on {}", "✅".green(), socket.display());
+        return Ok(());
+    }
+
+    serve(&socket).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct TestCli {
+        #[command(subcommand)]
+        command: TestCommands,
+    }
+
+    #[derive(clap::Subcommand)]
+    enum TestCommands {
+        Daemon(DaemonArgs),
+    }
+
+    #[test]
+    fn test_daemon_args() {
+        let cli = TestCli::parse_from(["test", "daemon", "--socket", "/tmp/pl.sock", "--stop"]);
+        match cli.command {
+            TestCommands::Daemon(args) => {
+                assert_eq!(args.socket, Some(PathBuf::from("/tmp/pl.sock")));
+                assert!(args.stop);
+                assert!(!args.status);
+            }
+        }
+        assert!(TestCli::try_parse_from(["test", "daemon", "--status", "--stop"]).is_err());
+    }
+}
@@ -1,16 +1,20 @@
+use super::lint::lint_files;
 use clap::Args;
-use project_lint_core::config::Config;
+use project_lint_core::config::{env_overrides, Config, RuleSeverity};
 use project_lint_core::hooks::{
+    daemon::DaemonClient,
+    dispatch::{evaluate, evaluate_detached, Evaluation, HookContext},
     initialize_global_logger, log_hook_event,
     mappers::{ClaudeMapper, KiroMapper, WindsurfMapper},
-    Decision, EventMapper, HookResult, RuleEngine,
+    session::{report_pending, PendingFinding, SessionStore},
+    session_files_written, Decision, EventMapper, EventType, HookResult, ProjectLintEvent,
 };
-use project_lint_core::profiles;
 use project_lint_core::utils::Result;
 use serde_json::json;
-use std::io::{self, Read};
-use std::path::Path;
-use std::time::Instant;
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
 use tracing::{debug, error, info, warn};
 
 #[derive(Args)]
@@ -38,9 +42,40 @@ pub struct HookArgs {
     /// Project identifier included in hook log entries
     #[arg(long)]
     pub project_id: Option<String>,
+
+    /// Evaluate only `LocalAsync` rules and queue their findings for the
+    /// session; used by the detached evaluation a hook spawns
+    #[arg(long, hide = true)]
+    pub async_rules: bool,
+
+    /// Send the whole event to `project-lint daemon`; evaluate in-process
+    /// when it does not answer
+    #[arg(long)]
+    pub daemon: bool,
+
+    /// Socket of the daemon (defaults to the runtime directory)
+    #[arg(long)]
+    pub daemon_socket: Option<PathBuf>,
+
+    /// How long to wait for the daemon before evaluating in-process
+    #[arg(long, default_value_t = 1000)]
+    pub daemon_timeout_ms: u64,
 }
 
-pub async fn run(args: HookArgs) -> Result<()> {
+/// Event mapper for a hook `--source`; unknown sources fall back to Windsurf.
+pub fn mapper_for(source: &str) -> Box<dyn EventMapper> {
+    match source.to_lowercase().as_str() {
+        "windsurf" => Box::new(WindsurfMapper),
+        "claude" => Box::new(ClaudeMapper),
+        "kiro" => Box::new(KiroMapper),
+        _ => {
+            warn!("Unknown source '{}', defaulting to Windsurf mapper", source);
+            Box::new(WindsurfMapper)
+        }
+    }
+}
+
+pub async fn run(args: HookArgs, overrides: &[String]) -> Result<()> {
     // Initialize hook logger
     initialize_global_logger(None)?;
 
@@ -57,18 +92,7 @@ pub async fn run(args: HookArgs) -> Result<()> {
     debug!("Received hook input from {}: {}", args.source, buffer);
 
     // Select mapper
-    let mapper: Box<dyn EventMapper> = match args.source.to_lowercase().as_str() {
-        "windsurf" => Box::new(WindsurfMapper),
-        "claude" => Box::new(ClaudeMapper),
-        "kiro" => Box::new(KiroMapper),
-        _ => {
-            warn!(
-                "Unknown source '{}', defaulting to Windsurf mapper",
-                args.source
-            );
-            Box::new(WindsurfMapper)
-        }
-    };
+    let mapper = mapper_for(&args.source);
 
     // Parse event
     let mut event = match mapper.map_event(&buffer) {
@@ -86,29 +110,105 @@ pub async fn run(args: HookArgs) -> Result<()> {
 
     info!("Processing event: {:?}", event.event_type);
 
-    // Load config
-    let project_path_str = args.path.unwrap_or_else(|| ".".to_string());
-    let project_path = Path::new(&project_path_str);
+    let context = HookContext {
+        cwd: std::env::current_dir()?,
+        project_path: PathBuf::from(args.path.as_deref().unwrap_or(".")),
+        config_file: args.config_file.as_ref().map(PathBuf::from),
+        overrides: overrides.to_vec(),
+        env_overrides: env_overrides(),
+        session_dir: Some(SessionStore::default_dir()),
+    };
+    let daemon = DaemonClient::new(
+        args.daemon_socket
+            .clone()
+            .unwrap_or_else(DaemonClient::default_socket),
+        Duration::from_millis(args.daemon_timeout_ms),
+    );
+
+    if args.async_rules {
+        if let Some(session_id) = &event.session_id {
+            let config = context.load_config(&event)?;
+            let findings: Vec<PendingFinding> =
+                evaluate_detached(&config, &context, &event, Some(&daemon))?
+                    .into_iter()
+                    .map(PendingFinding::from)
+                    .collect();
+            SessionStore::new(SessionStore::default_dir()).push(session_id, &findings)?;
+        }
+        return Ok(());
+    }
 
-    let mut config = match &args.config_file {
-        Some(config_file) => {
-            let config_path = Path::new(config_file);
-            Config::load_from_file(config_path)?
+    // Evaluate rules, in the daemon when asked to and it answers
+    let forwarded = if args.daemon {
+        daemon
+            .evaluate(&event, &context)
+            .map_err(|e| debug!("Daemon unavailable, evaluating in-process: {}", e))
+            .ok()
+    } else {
+        None
+    };
+    let mut config: Option<Config> = None;
+    let Evaluation {
+        mut result,
+        detached_rules,
+    } = match forwarded {
+        Some(evaluation) => evaluation,
+        None => {
+            let loaded = config.insert(context.load_config(&event)?);
+            evaluate(loaded, &context, &event, (!args.daemon).then_some(&daemon))?
         }
-        None => Config::load()?,
     };
 
-    // Determine active profiles for this event
-    let active_profiles =
-        profiles::get_active_profiles(project_path, &config.active_profiles, Some(&event))?;
-    config.active_profiles = active_profiles;
+    // Findings of earlier async rules, then start this event's async rules
+    if let Some(session_id) = &event.session_id {
+        match SessionStore::new(SessionStore::default_dir()).take(session_id) {
+            Ok(pending) => report_pending(&mut result, &pending),
+            Err(e) => warn!("Failed to read pending findings: {}", e),
+        }
+        if detached_rules {
+            if let Err(e) = spawn_async_rules(&buffer, &args, session_id, overrides) {
+                warn!("Failed to start async rule evaluation: {}", e);
+            }
+        }
+    }
 
-    // Evaluate rules
-    let engine = RuleEngine::new(&config);
-    let result = engine.evaluate_event(&event)?;
+    // Finishing: the files the session wrote have to lint clean
+    let stopping = matches!(event.event_type, EventType::Stop | EventType::SubagentStop);
+    if stopping && result.decision != Decision::Deny {
+        // The configuration the event was evaluated with
+        let config = match config {
+            Some(config) => Some(config),
+            None => context
+                .load_config(&event)
+                .map_err(|e| warn!("Stop gate skipped, failed to load config: {}", e))
+                .ok(),
+        };
+        let gate = match &config {
+            Some(config) => stop_gate(config, &context, &event).await,
+            None => None,
+        };
+        if let Some(gate) = gate {
+            if gate.decision == Decision::Deny || result.decision == Decision::Allow {
+                result.decision = gate.decision;
+            }
+            result.message = Some(match result.message.take() {
+                Some(message) => format!("{}\n{}", message, gate.message.unwrap_or_default()),
+                None => gate.message.unwrap_or_default(),
+            });
+        }
+    }
 
     // Output IDE-specific response
-    let output = mapper.format_response(result.clone())?;
+    let stop_response = if stopping {
+        mapper.format_stop_response(result.clone()).transpose()?
+    } else {
+        None
+    };
+    let blocks_in_response = stop_response.is_some();
+    let output = match stop_response {
+        Some(output) => output,
+        None => mapper.format_response(result.clone())?,
+    };
     if !output.is_empty() {
         println!("{}", output);
     }
@@ -168,14 +268,125 @@ pub async fn run(args: HookArgs) -> Result<()> {
         error!("Failed to log hook event: {}", e);
     }
 
-    // Handle blocking (exit code 2 is standard for blocking in many agent hook systems)
-    if result.decision == Decision::Deny {
+    // Handle blocking (exit code 2 is standard for blocking in many agent hook systems).
+    // A stop response carries the block itself, which exit code 2 would discard
+    if result.decision == Decision::Deny && !blocks_in_response {
         std::process::exit(2);
     }
 
     Ok(())
 }
 
+/// How far back the hook logs are read for the files a session wrote.
+const STOP_GATE_LOG_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Lint the files the session wrote (`stop_gate` check). Errors deny the
+/// stop so the agent fixes them first; when the agent is already continuing
+/// from a blocked stop they only warn, so it cannot loop forever.
+async fn stop_gate(
+    config: &Config,
+    context: &HookContext,
+    event: &ProjectLintEvent,
+) -> Option<HookResult> {
+    let session_id = event.session_id.as_deref()?;
+    if !config.is_check_enabled("stop_gate") {
+        return None;
+    }
+
+    let project_root = context.project_root();
+    let files: Vec<PathBuf> = session_files_written(session_id, STOP_GATE_LOG_AGE)
+        .map_err(|e| warn!("Stop gate skipped, failed to read hook logs: {}", e))
+        .ok()?
+        .into_iter()
+        .filter_map(|file| {
+            let file = PathBuf::from(file);
+            let relative = match file.strip_prefix(&project_root) {
+                Ok(relative) => relative.to_path_buf(),
+                Err(_) if file.is_relative() => file,
+                // Outside the project
+                Err(_) => return None,
+            };
+            project_root.join(&relative).is_file().then_some(relative)
+        })
+        .collect();
+    if files.is_empty() {
+        return None;
+    }
+    debug!("Stop gate linting {} file(s)", files.len());
+
+    let errors: Vec<String> = lint_files(&project_root.to_string_lossy(), config, &files)
+        .await
+        .map_err(|e| warn!("Stop gate skipped, lint failed: {}", e))
+        .ok()?
+        .into_iter()
+        .filter(|finding| finding.severity == RuleSeverity::Error)
+        .map(|finding| finding.message)
+        .collect();
+    if errors.is_empty() {
+        return None;
+    }
+
+    let stop_hook_active = event
+        .context
+        .original_payload
+        .as_ref()
+        .and_then(|payload| payload["stop_hook_active"].as_bool())
+        .unwrap_or(false);
+    Some(HookResult {
+        decision: if stop_hook_active {
+            Decision::Warn
+        } else {
+            Decision::Deny
+        },
+        message: Some(format!(
+            "Fix these lint errors in files you changed before finishing:\n{}",
+            errors.join("\n")
+        )),
+        modified_input: None,
+    })
+}
+
+/// Re-run this hook detached with `--async-rules`; the child outlives us
+/// and queues its findings for the session.
+fn spawn_async_rules(
+    input: &str,
+    args: &HookArgs,
+    session_id: &str,
+    overrides: &[String],
+) -> Result<()> {
+    let mut command = Command::new(std::env::current_exe()?);
+    for assignment in overrides {
+        command.arg("--set").arg(assignment);
+    }
+    command.args([
+        "hook",
+        "--async-rules",
+        "--source",
+        &args.source,
+        "--session-id",
+        session_id,
+    ]);
+    if let Some(path) = &args.path {
+        command.args(["--path", path]);
+    }
+    if let Some(config_file) = &args.config_file {
+        command.args(["--config-file", config_file]);
+    }
+    if let Some(socket) = &args.daemon_socket {
+        command.arg("--daemon-socket").arg(socket);
+    }
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.as_bytes())?;
+    }
+    debug!("Started async rule evaluation (pid {})", child.id());
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -202,6 +413,10 @@ mod tests {
                 assert!(args.config_file.is_none());
                 assert!(args.session_id.is_none());
                 assert!(args.project_id.is_none());
+                assert!(!args.async_rules);
+                assert!(!args.daemon);
+                assert!(args.daemon_socket.is_none());
+                assert_eq!(args.daemon_timeout_ms, 1000);
             }
         }
     }
@@ -256,6 +471,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_hook_args_daemon_flags() {
+        let cli = TestCli::parse_from([
+            "test",
+            "hook",
+            "--daemon",
+            "--daemon-socket",
+            "/tmp/pl.sock",
+            "--daemon-timeout-ms",
+            "250",
+        ]);
+        match cli.command {
+            TestCommands::Hook(args) => {
+                assert!(args.daemon);
+                assert_eq!(args.daemon_socket, Some(PathBuf::from("/tmp/pl.sock")));
+                assert_eq!(args.daemon_timeout_ms, 250);
+            }
+        }
+    }
+
     #[test]
     fn test_hook_args_all_flags_combined() {
         let cli = TestCli::parse_from([
@@ -1,10 +1,12 @@
 use colored::Colorize;
 use project_lint_core::utils::Result;
+use std::io::{self, BufRead, Write};
 use tracing::{info, warn};
 
-use project_lint_core::config::Config;
+use project_lint_core::config::RulesMode;
+use project_lint_core::inventory::{ProjectInventory, PRESETS};
 
-pub async fn run(force: bool) -> Result<()> {
+pub async fn run(force: bool, interactive: bool, preset: Option<String>) -> Result<()> {
     info!("Initializing project-lint configuration");
 
     // Initialize in the current project root
@@ -19,12 +21,69 @@ pub async fn run(force: bool) -> Result<()> {
         return Ok(());
     }
 
-    // Create default configuration
-    let config = Config::default();
-    config.save_to(&config_dir)?;
+    // Take the project inventory, or start from a named preset
+    let inventory = match &preset {
+        Some(name) => ProjectInventory::preset(name).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unknown preset '{}' (available: {})",
+                name,
+                PRESETS.join(", ")
+            )
+        })?,
+        None => ProjectInventory::detect(&project_root),
+    };
+
+    let summary = inventory.summary();
+    match &preset {
+        Some(name) => println!("{} {}", "Using preset:".blue(), name),
+        None if summary.is_empty() => {
+            println!("{}", "No languages or tooling detected".blue())
+        }
+        None => println!("{}", "Detected:".blue()),
+    }
+    for line in &summary {
+        println!("  • {}", line);
+    }
+    println!();
+
+    let mut stacks = inventory.stacks();
+    let mut allowlist = true;
+    if interactive {
+        let stdin = io::stdin();
+        let mut input = stdin.lock();
+        let mut output = io::stdout();
+        let mut selected = Vec::new();
+        for stack in stacks {
+            let question = format!(
+                "Enable the '{}' profile? {}",
+                stack.name(),
+                stack.description()
+            );
+            if confirm(&mut input, &mut output, &question, true)? {
+                selected.push(stack);
+            }
+        }
+        stacks = selected;
+        allowlist = confirm(
+            &mut input,
+            &mut output,
+            "Run only the generated checks (allowlist mode)?",
+            true,
+        )?;
+        println!();
+    }
+
+    let mut starter = inventory.starter_config(&stacks);
+    if !allowlist {
+        starter.rules.mode = RulesMode::Denylist;
+    }
+    let written = starter.write_to(&config_dir)?;
 
     println!("{}", "✓ Project-lint initialized successfully!".green());
     println!("Configuration created at: {:?}", config_file);
+    for path in written.iter().skip(1) {
+        println!("  • {}", path.display());
+    }
     println!();
     println!("You can now:");
     println!("  • Run 'project-lint lint' to check your project");
@@ -33,3 +92,49 @@ pub async fn run(force: bool) -> Result<()> {
 
     Ok(())
 }
+
+/// Ask a yes/no question; an empty answer (or end of input) takes `default`.
+fn confirm<R: BufRead, W: Write>(
+    input: &mut R,
+    output: &mut W,
+    question: &str,
+    default: bool,
+) -> Result<bool> {
+    let hint = if default { "[Y/n]" } else { "[y/N]" };
+    loop {
+        write!(output, "{} {} ", question, hint)?;
+        output.flush()?;
+
+        let mut answer = String::new();
+        if input.read_line(&mut answer)? == 0 {
+            return Ok(default);
+        }
+        match answer.trim().to_lowercase().as_str() {
+            "" => return Ok(default),
+            "y" | "yes" => return Ok(true),
+            "n" | "no" => return Ok(false),
+            _ => writeln!(output, "Please answer 'y' or 'n'.")?,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_confirm_answers_and_default() -> Result<()> {
+        let mut output = Vec::new();
+        let mut input = Cursor::new("maybe\nn\n\n");
+        assert!(!confirm(&mut input, &mut output, "Q?", true)?);
+        assert!(confirm(&mut input, &mut output, "Q?", true)?);
+        // End of input falls back to the default
+        assert!(!confirm(&mut input, &mut output, "Q?", false)?);
+
+        let printed = String::from_utf8(output)?;
+        assert!(printed.contains("Please answer"));
+        assert!(printed.contains("[y/N]"));
+        Ok(())
+    }
+}
@@ -1,11 +1,15 @@
 use colored::Colorize;
 use glob::Pattern;
 use project_lint_core::utils::{matches_pattern, path_exists_glob, Result};
-use std::path::Path;
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
 use tracing::{debug, info, warn};
 use walkdir::WalkDir;
 
-use project_lint_core::config::{Config, ModularRule};
+use project_lint_core::config::{
+    Config, DevOpsSpecificConfig, ModularRule, RuleSeverity, WebSpecificConfig,
+};
+use project_lint_core::content_match::ContentMatcher;
 use project_lint_core::profiles;
 use project_lint_core::scanners::ast::{ASTAnalyzer, ASTIssue};
 use project_lint_core::scanners::dependency_version_checker::{
@@ -16,13 +20,20 @@ use project_lint_core::scanners::git::{check_branch_allowed, get_git_info};
 use project_lint_core::scanners::security::SecurityScanner;
 use project_lint_core::scanners::typescript::TypeScriptScanner;
 use project_lint_core::scanners::{
-    ci_cd_parity::CiCdParityScanner, dev_environment::DevEnvironmentScanner,
-    dockerfile_lint::DockerfileLintScanner, rust_conventions::RustConventionsScanner,
+    ci_cd_parity::CiCdParityScanner, compose::ComposeScanner,
+    dev_environment::DevEnvironmentScanner, dockerfile_lint::DockerfileLintScanner,
+    kubernetes::KubernetesScanner, profile_structure::ProfileStructureScanner,
+    rust_conventions::RustConventionsScanner, terraform::TerraformScanner,
     typescript_monorepo::TypeScriptMonorepoScanner, vault_security::VaultSecurityScanner,
-    ScannerIssue,
+    web::WebScanner, ScannerIssue,
 };
 
-pub async fn run(project_path: &str, apply_fixes: bool, dry_run: bool) -> Result<()> {
+pub async fn run(
+    project_path: &str,
+    apply_fixes: bool,
+    dry_run: bool,
+    overrides: &[String],
+) -> Result<()> {
     info!("Running linting checks on project: {}", project_path);
 
     if apply_fixes && dry_run {
@@ -36,21 +47,211 @@ pub async fn run(project_path: &str, apply_fixes: bool, dry_run: bool) -> Result
         info!("Dry-run mode enabled - showing what would be fixed without making changes");
     }
 
-    let mut config = Config::load()?;
-    let mut issues = Vec::new();
+    let config = Config::load()?.with_runtime_overrides(overrides)?;
 
     // Check if project path exists
-    let project_path_obj = Path::new(project_path);
-    if !project_path_obj.exists() {
+    if !Path::new(project_path).exists() {
         return Err(anyhow::anyhow!(
             "Project path does not exist: {}",
             project_path
         ));
     }
 
+    let issues = lint_project(project_path, &config, None, apply_fixes, dry_run).await?;
+
+    // Report results
+    if issues.is_empty() {
+        println!("{}", "✓ No issues found!".green());
+    } else {
+        println!("{}", "Issues found:".yellow());
+        for issue in &issues {
+            println!("  {}", issue);
+        }
+        println!();
+        println!("{}", format!("Found {} issue(s)", issues.len()).yellow());
+    }
+
+    Ok(())
+}
+
+/// Lint only `files` (relative to `project_path`): the checks that look at
+/// files run on them, and findings elsewhere are left out.
+pub async fn lint_files(
+    project_path: &str,
+    config: &Config,
+    files: &[PathBuf],
+) -> Result<Vec<Finding>> {
+    lint_project(project_path, config, Some(files), false, false).await
+}
+
+/// One lint finding: the line reported for it and its severity.
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub severity: RuleSeverity,
+    pub message: String,
+}
+
+impl Finding {
+    fn new(severity: RuleSeverity, message: String) -> Self {
+        Self { severity, message }
+    }
+}
+
+impl std::fmt::Display for Finding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Severity of a scanner's `severity` string; critical findings are errors.
+fn severity_of(severity: &str) -> RuleSeverity {
+    match severity {
+        "critical" | "error" => RuleSeverity::Error,
+        "info" | "low" => RuleSeverity::Info,
+        _ => RuleSeverity::Warning,
+    }
+}
+
+/// Lint the project root and each nested config scope. With `files`, only
+/// findings for those files are reported.
+async fn lint_project(
+    project_path: &str,
+    config: &Config,
+    files: Option<&[PathBuf]>,
+    apply_fixes: bool,
+    dry_run: bool,
+) -> Result<Vec<Finding>> {
+    let mut issues = Vec::new();
+    let project_path_obj = Path::new(project_path);
+
+    // Subdirectories with their own config are linted as separate scopes and
+    // skipped by the enclosing scope.
+    let nested_dirs = Config::nested_config_dirs(project_path_obj);
+
+    let root_scope = Scope {
+        path: project_path,
+        excluded: &nested_dirs,
+        is_root: true,
+        files,
+    };
+    lint_scope(
+        &root_scope,
+        config.clone(),
+        &mut issues,
+        apply_fixes,
+        dry_run,
+    )
+    .await?;
+
+    for dir in &nested_dirs {
+        let scope_files: Option<Vec<PathBuf>> = files.map(|files| {
+            files
+                .iter()
+                .filter_map(|f| f.strip_prefix(dir).ok())
+                .map(Path::to_path_buf)
+                .collect()
+        });
+        if scope_files.as_ref().is_some_and(Vec::is_empty) {
+            continue;
+        }
+        info!("Linting nested config scope: {}", dir.display());
+        let scope_config = config.for_path(project_path_obj, dir)?;
+        let excluded: Vec<PathBuf> = nested_dirs
+            .iter()
+            .filter_map(|d| d.strip_prefix(dir).ok())
+            .filter(|d| !d.as_os_str().is_empty())
+            .map(Path::to_path_buf)
+            .collect();
+        let scope_path = project_path_obj.join(dir);
+        let scope_path = scope_path.to_string_lossy();
+        let scope = Scope {
+            path: &scope_path,
+            excluded: &excluded,
+            is_root: false,
+            files: scope_files.as_deref(),
+        };
+
+        let mut scope_issues = Vec::new();
+        lint_scope(
+            &scope,
+            scope_config,
+            &mut scope_issues,
+            apply_fixes,
+            dry_run,
+        )
+        .await?;
+        issues.extend(scope_issues.into_iter().map(|mut issue| {
+            issue.message = format!("[{}] {}", dir.display(), issue.message);
+            issue
+        }));
+    }
+
+    Ok(issues)
+}
+
+/// A directory linted under one effective configuration: the project root or
+/// a subdirectory carrying its own nested config.
+struct Scope<'a> {
+    path: &'a str,
+    /// Nested config directories below `path`, relative to it. They are linted
+    /// as scopes of their own, so walkers here skip them.
+    excluded: &'a [PathBuf],
+    is_root: bool,
+    /// Files to lint, relative to `path`; `None` lints every file.
+    files: Option<&'a [PathBuf]>,
+}
+
+impl Scope<'_> {
+    /// Whether findings for `file` (absolute, or relative to the scope)
+    /// belong to this scope.
+    fn covers(&self, file: &Path) -> bool {
+        let relative = file.strip_prefix(self.path).unwrap_or(file);
+        !self.excluded.iter().any(|d| relative.starts_with(d))
+            && self
+                .files
+                .is_none_or(|files| files.iter().any(|f| f == relative))
+    }
+
+    /// Walk the files of the scope, pruning `excluded` subtrees.
+    fn walk_files(&self) -> Box<dyn Iterator<Item = walkdir::DirEntry> + '_> {
+        let entries: Box<dyn Iterator<Item = walkdir::Result<walkdir::DirEntry>>> = match self.files
+        {
+            Some(files) => Box::new(
+                files
+                    .iter()
+                    .flat_map(|f| WalkDir::new(Path::new(self.path).join(f)).max_depth(0)),
+            ),
+            None => {
+                let root = PathBuf::from(self.path);
+                Box::new(WalkDir::new(self.path).into_iter().filter_entry(move |e| {
+                    !(e.file_type().is_dir()
+                        && e.path()
+                            .strip_prefix(&root)
+                            .map(|rel| self.excluded.iter().any(|d| rel == d))
+                            .unwrap_or(false))
+                }))
+            }
+        };
+        Box::new(
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_file() && self.covers(e.path())),
+        )
+    }
+}
+
+async fn lint_scope(
+    scope: &Scope<'_>,
+    mut config: Config,
+    issues: &mut Vec<Finding>,
+    apply_fixes: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let project_path = scope.path;
+
     // Determine active profiles
     let active_profiles =
-        profiles::get_active_profiles(project_path_obj, &config.active_profiles, None)?;
+        profiles::get_active_profiles(Path::new(project_path), &config.active_profiles, None)?;
     if !active_profiles.is_empty() {
         info!(
             "Active profiles: {}",
@@ -61,18 +262,22 @@ pub async fn run(project_path: &str, apply_fixes: bool, dry_run: bool) -> Result
                 .join(", ")
                 .green()
         );
-
-        // In the future: Apply profile configurations here
-        // For now, we just replace the available profiles with the active ones in the config
-        config.active_profiles = active_profiles;
     } else {
         debug!("No specific profiles activated");
     }
+    // Only active profiles (and the slices they include) shape this run
+    config = config.with_active_profiles(active_profiles)?;
+    for diagnostic in &config.diagnostics {
+        issues.push(Finding::new(
+            RuleSeverity::Warning,
+            format!("⚠️ [Profiles] {}", diagnostic),
+        ));
+    }
 
     // Perform file naming analysis
-    if config.is_check_enabled("file_naming") {
+    if config.is_check_enabled_anywhere("file_naming", Some("naming")) {
         debug!("Performing file naming analysis");
-        perform_file_naming_analysis(project_path, &mut issues, apply_fixes, dry_run)?;
+        perform_file_naming_analysis(scope, &config, issues, apply_fixes, dry_run)?;
     }
 
     // Initialize AST analyzer
@@ -82,163 +287,130 @@ pub async fn run(project_path: &str, apply_fixes: bool, dry_run: bool) -> Result
     debug!("Processing {} modular rules", config.modular_rules.len());
     for rule in &config.modular_rules {
         if rule.enabled {
-            process_modular_rule(project_path, rule, &mut issues, &config)?;
+            process_modular_rule(scope, rule, issues, &config)?;
         }
     }
 
     // Perform AST-based analysis
-    if config.is_check_enabled("ast_analysis") {
+    if config.is_check_enabled_anywhere("ast_analysis", Some("ast")) {
         debug!("Performing AST-based analysis");
-        perform_ast_analysis(project_path, &mut ast_analyzer, &mut issues)?;
+        perform_ast_analysis(scope, &config, &mut ast_analyzer, issues)?;
     }
 
     // Perform security scanning
-    if config.is_check_enabled("security_analysis") {
+    if config.is_check_enabled_anywhere("security_analysis", Some("security")) {
         debug!("Performing security analysis");
-        perform_security_analysis(project_path, &mut issues, apply_fixes, dry_run)?;
+        perform_security_analysis(scope, &config, issues, apply_fixes, dry_run)?;
     }
 
     // Perform TypeScript linting
-    if config.is_check_enabled("typescript_analysis") {
+    if config.is_check_enabled_anywhere("typescript_analysis", Some("typescript")) {
         debug!("Performing TypeScript analysis");
-        perform_typescript_analysis(project_path, &mut issues, apply_fixes, dry_run)?;
+        perform_typescript_analysis(scope, &config, issues, apply_fixes, dry_run)?;
     }
 
     // Perform dependency version checking
-    if config.is_check_enabled("dependency_versions") {
+    if config.is_check_enabled_anywhere("dependency_versions", Some("dependencies")) {
         debug!("Performing dependency version analysis");
-        perform_dependency_analysis(project_path, &mut issues, apply_fixes, dry_run).await?;
+        perform_dependency_analysis(scope, &config, issues, apply_fixes, dry_run).await?;
     }
 
-    // Knowledge-bundle-driven scanners (Phase 3b). Each is gated by its own
-    // check name so profiles/custom rules can disable them individually.
-    if config.is_check_enabled("rust_conventions") {
-        debug!("Performing rust conventions analysis");
-        perform_scanner_issues(
-            "Rust",
-            &RustConventionsScanner::with_forbidden_crates(
-                config
-                    .scanner_config
-                    .rust_security
-                    .as_ref()
-                    .map(|c| c.forbidden_crates.clone())
-                    .unwrap_or_default(),
-            )
-            .scan(project_path)?,
-            &mut issues,
-        );
+    // Directory layout required by the active profiles
+    if config.active_profiles.iter().any(|p| p.structure.is_some()) {
+        debug!("Performing profile structure analysis");
+        perform_profile_structure_analysis(scope, &config, issues, apply_fixes, dry_run)?;
     }
 
-    if config.is_check_enabled("dev_environment") {
-        debug!("Performing dev environment analysis");
-        let scanner = match &config.scanner_config.dev_environment_files {
-            Some(c) => DevEnvironmentScanner::with_files(
-                c.required_files.clone(),
-                c.forbidden_files.clone(),
-            ),
-            None => DevEnvironmentScanner::new(),
-        };
-        perform_scanner_issues("DevEnv", &scanner.scan(project_path)?, &mut issues);
+    // Web checks switched on by the active profiles' [web_specific] toggles
+    if config.is_check_enabled_anywhere("web", Some("web")) {
+        debug!("Performing web analysis");
+        perform_web_analysis(scope, &config, issues)?;
     }
 
-    if config.is_check_enabled("ci_cd_parity") {
-        debug!("Performing CI/CD parity analysis");
-        perform_scanner_issues(
-            "CICD",
-            &CiCdParityScanner::new().scan(project_path)?,
-            &mut issues,
-        );
-    }
-
-    if config.is_check_enabled("dockerfile_lint") {
-        debug!("Performing Dockerfile lint analysis");
-        let scanner = match &config.scanner_config.dockerfile_security {
-            Some(c) => DockerfileLintScanner::with_config(
-                c.require_pinned_digests,
-                c.require_non_root_user,
-                c.forbid_copy_dot,
-            ),
-            None => DockerfileLintScanner::new(),
-        };
-        perform_scanner_issues("Docker", &scanner.scan(project_path)?, &mut issues);
-    }
-
-    if config.is_check_enabled("typescript_monorepo") {
-        debug!("Performing TypeScript monorepo analysis");
-        let scanner = match &config.scanner_config.typescript_monorepo {
-            Some(c) => {
-                TypeScriptMonorepoScanner::with_config(c.catalog_mode, c.allowed_extensions.clone())
-            }
-            None => TypeScriptMonorepoScanner::new(),
-        };
-        perform_scanner_issues("TSMonorepo", &scanner.scan(project_path)?, &mut issues);
-    }
-
-    if config.is_check_enabled("vault_security") {
-        debug!("Performing vault security analysis");
-        let scanner = match &config.scanner_config.vault_security {
-            Some(c) => VaultSecurityScanner::with_config(
-                c.required_env_prefix.clone(),
-                c.allowed_backends.clone(),
-            ),
-            None => VaultSecurityScanner::new(),
-        };
-        perform_scanner_issues("Vault", &scanner.scan(project_path)?, &mut issues);
-    }
-
-    // Legacy checks (for backward compatibility)
-    if !config
-        .modular_rules
-        .iter()
-        .any(|r| r.name == "git-branch-rules")
-        && config.is_check_enabled("git_branch")
-    {
-        check_legacy_git_branches(project_path, &config, &mut issues)?;
-    }
-
-    if !config
-        .modular_rules
-        .iter()
-        .any(|r| r.name == "file-organization")
-        && config.is_check_enabled("file_location")
+    // Kubernetes manifests, when an active profile asks for them
+    if devops_toggle(&config, |d| d.check_kubernetes_manifests)
+        && config.is_check_enabled_anywhere("kubernetes", Some("k8s"))
     {
-        check_legacy_file_structure(project_path, &config, &mut issues)?;
+        debug!("Performing Kubernetes manifest analysis");
+        let scanner = match &config.scanner_config.kubernetes {
+            Some(c) => KubernetesScanner::with_config(c.require_probes, c.require_resources),
+            None => KubernetesScanner::new(),
+        };
+        let found = scoped_issues(
+            &config,
+            "kubernetes",
+            "K8s",
+            scope,
+            scanner.scan(project_path)?,
+        );
+        perform_scanner_issues("K8s", &found, issues);
     }
 
-    if !config
-        .modular_rules
-        .iter()
-        .any(|r| r.name == "script-location")
-        && config.is_check_enabled("directory_structure")
+    // Terraform modules, when an active profile asks for them
+    if devops_toggle(&config, |d| d.validate_terraform)
+        && config.is_check_enabled_anywhere("terraform", Some("terraform"))
     {
-        check_legacy_directory_structure(project_path, &config, &mut issues)?;
+        debug!("Performing Terraform analysis");
+        let scanner = match &config.scanner_config.terraform {
+            Some(c) => TerraformScanner::with_config(c.check_fmt),
+            None => TerraformScanner::new(),
+        };
+        let found = scoped_issues(
+            &config,
+            "terraform",
+            "Terraform",
+            scope,
+            scanner.scan(project_path)?,
+        );
+        perform_scanner_issues("Terraform", &found, issues);
     }
 
-    // Report results
-    if issues.is_empty() {
-        println!("{}", "✓ No issues found!".green());
-    } else {
-        println!("{}", "Issues found:".yellow());
-        for issue in &issues {
-            println!("  {}", issue);
+    // Knowledge-bundle-driven scanners (Phase 3b). Each is gated by its own
+    // check name so profiles/custom rules can disable them individually.
+    for (check, label) in CORE_SCANNERS {
+        if config.is_check_enabled_anywhere(check, Some(&label.to_lowercase())) {
+            debug!("Performing {} analysis", check);
+            let found = scan_with_overrides(check, label, &config, scope)?;
+            perform_scanner_issues(label, &found, issues);
         }
-        println!();
-        println!("{}", format!("Found {} issue(s)", issues.len()).yellow());
     }
 
     Ok(())
 }
 
 fn perform_file_naming_analysis(
-    project_path: &str,
-    issues: &mut Vec<String>,
+    scope: &Scope<'_>,
+    config: &Config,
+    issues: &mut Vec<Finding>,
     apply_fixes: bool,
     dry_run: bool,
 ) -> Result<()> {
+    let project_path = scope.path;
     let scanner = FileNamingScanner::new();
 
     match scanner.scan(project_path) {
-        Ok(detected_issues) => {
+        Ok(mut detected_issues) => {
+            detected_issues.retain_mut(|issue| {
+                let relative = issue.path.strip_prefix(project_path).unwrap_or(&issue.path);
+                if !scope.covers(&issue.path) {
+                    return false;
+                }
+                match effective_severity(
+                    config,
+                    "file_naming",
+                    "naming",
+                    "file_naming",
+                    relative,
+                    &issue.severity,
+                ) {
+                    Some(severity) => {
+                        issue.severity = severity;
+                        true
+                    }
+                    None => false,
+                }
+            });
+
             for issue in &detected_issues {
                 let severity_icon = match issue.severity.as_str() {
                     "error" => "❌",
@@ -247,11 +419,14 @@ fn perform_file_naming_analysis(
                     _ => "⚠️",
                 };
 
-                issues.push(format!(
-                    "{} [Naming] {} (at {})",
-                    severity_icon,
-                    issue.message,
-                    issue.path.display()
+                issues.push(Finding::new(
+                    severity_of(&issue.severity),
+                    format!(
+                        "{} [Naming] {} (at {})",
+                        severity_icon,
+                        issue.message,
+                        issue.path.display()
+                    ),
                 ));
             }
 
@@ -281,10 +456,95 @@ fn perform_file_naming_analysis(
     Ok(())
 }
 
+/// Check each active profile's `[structure]` section and, under `--fix`,
+/// scaffold the expected directories that are missing. The section asks for
+/// the `profile_structure` check, so it runs unless disabled by name.
+fn perform_profile_structure_analysis(
+    scope: &Scope,
+    config: &Config,
+    issues: &mut Vec<Finding>,
+    apply_fixes: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let Some(config) = &config.with_requested_check("profile_structure") else {
+        debug!("profile_structure is disabled");
+        return Ok(());
+    };
+    for profile in &config.active_profiles {
+        let Some(structure) = &profile.structure else {
+            continue;
+        };
+        let scanner = ProfileStructureScanner::new(
+            &profile.metadata.name,
+            structure.expected_dirs.clone(),
+            structure.forbidden_dirs.clone(),
+        );
+        let found = scoped_issues(
+            config,
+            "profile_structure",
+            "Structure",
+            scope,
+            scanner.scan(scope.path)?,
+        );
+        perform_scanner_issues("Structure", &found, issues);
+
+        if apply_fixes || dry_run {
+            let created = scanner.apply_fixes(scope.path, &found, dry_run)?;
+            if created > 0 {
+                if dry_run {
+                    info!("📋 Would create {} directories", created);
+                } else {
+                    info!("✅ Created {} directories", created);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether any active profile turns on a `[devops_specific]` toggle.
+fn devops_toggle(config: &Config, toggle: fn(&DevOpsSpecificConfig) -> bool) -> bool {
+    config
+        .active_profiles
+        .iter()
+        .filter_map(|p| p.devops_specific.as_ref())
+        .any(toggle)
+}
+
+/// Run each web check that at least one active profile turns on.
+fn perform_web_analysis(scope: &Scope, config: &Config, issues: &mut Vec<Finding>) -> Result<()> {
+    let toggles: Vec<&WebSpecificConfig> = config
+        .active_profiles
+        .iter()
+        .filter_map(|p| p.web_specific.as_ref())
+        .collect();
+    let on = |toggle: fn(&WebSpecificConfig) -> bool| toggles.iter().any(|w| toggle(w));
+    let (html, a11y, seo, css, images) = (
+        on(|w| w.check_html_semantics),
+        on(|w| w.check_accessibility),
+        on(|w| w.check_seo_meta),
+        on(|w| w.validate_css_properties),
+        on(|w| w.optimize_images),
+    );
+    if !(html || a11y || seo || css || images) {
+        return Ok(());
+    }
+
+    let mut scanner = WebScanner::with_config(html, a11y, seo, css, images);
+    if let Some(limits) = &config.scanner_config.web {
+        scanner = scanner.with_image_limits(limits.max_image_bytes, limits.max_image_dimension);
+    }
+    let found = scoped_issues(config, "web", "Web", scope, scanner.scan(scope.path)?);
+    perform_scanner_issues("Web", &found, issues);
+
+    Ok(())
+}
+
 /// Format and append a batch of [`ScannerIssue`]s to the user-facing issue list.
 /// `label` is the category prefix shown in the bracketed tag (e.g. `Rust`,
 /// `DevEnv`, `CICD`).
-fn perform_scanner_issues(label: &str, scanner_issues: &[ScannerIssue], issues: &mut Vec<String>) {
+fn perform_scanner_issues(label: &str, scanner_issues: &[ScannerIssue], issues: &mut Vec<Finding>) {
     for si in scanner_issues {
         let icon = match si.severity.as_str() {
             "error" => "❌",
@@ -297,23 +557,171 @@ fn perform_scanner_issues(label: &str, scanner_issues: &[ScannerIssue], issues:
         } else {
             si.file.clone()
         };
-        issues.push(format!(
-            "{} [{}] {} ({}: {})",
-            icon, label, si.message, loc, si.rule
+        issues.push(Finding::new(
+            severity_of(&si.severity),
+            format!("{} [{}] {} ({}: {})", icon, label, si.message, loc, si.rule),
         ));
     }
 }
 
+/// Check names and display labels of the [`ScannerIssue`]-based scanners. The
+/// lowercased label is the category used in `category/rule` identifiers.
+const CORE_SCANNERS: &[(&str, &str)] = &[
+    ("rust_conventions", "Rust"),
+    ("dev_environment", "DevEnv"),
+    ("ci_cd_parity", "CICD"),
+    ("dockerfile_lint", "Docker"),
+    ("compose_lint", "Compose"),
+    ("typescript_monorepo", "TSMonorepo"),
+    ("vault_security", "Vault"),
+];
+
+fn run_core_scanner(check: &str, config: &Config, project_path: &str) -> Result<Vec<ScannerIssue>> {
+    match check {
+        "rust_conventions" => RustConventionsScanner::with_forbidden_crates(
+            config
+                .scanner_config
+                .rust_security
+                .as_ref()
+                .map(|c| c.forbidden_crates.clone())
+                .unwrap_or_default(),
+        )
+        .scan(project_path),
+        "dev_environment" => match &config.scanner_config.dev_environment_files {
+            Some(c) => DevEnvironmentScanner::with_files(
+                c.required_files.clone(),
+                c.forbidden_files.clone(),
+            ),
+            None => DevEnvironmentScanner::new(),
+        }
+        .scan(project_path),
+        "ci_cd_parity" => CiCdParityScanner::new().scan(project_path),
+        "dockerfile_lint" => match &config.scanner_config.dockerfile_security {
+            Some(c) => DockerfileLintScanner::with_config(
+                c.require_pinned_digests,
+                c.require_non_root_user,
+                c.forbid_copy_dot,
+            ),
+            None => DockerfileLintScanner::new(),
+        }
+        .scan(project_path),
+        "compose_lint" => match &config.scanner_config.dockerfile_security {
+            Some(c) => {
+                ComposeScanner::with_config(c.require_no_new_privileges, c.require_healthcheck)
+            }
+            None => ComposeScanner::new(),
+        }
+        .scan(project_path),
+        "typescript_monorepo" => match &config.scanner_config.typescript_monorepo {
+            Some(c) => {
+                TypeScriptMonorepoScanner::with_config(c.catalog_mode, c.allowed_extensions.clone())
+            }
+            None => TypeScriptMonorepoScanner::new(),
+        }
+        .scan(project_path),
+        "vault_security" => match &config.scanner_config.vault_security {
+            Some(c) => VaultSecurityScanner::with_config(
+                c.required_env_prefix.clone(),
+                c.allowed_backends.clone(),
+            ),
+            None => VaultSecurityScanner::new(),
+        }
+        .scan(project_path),
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Run a core scanner for `scope`. Files matched by an `[[overrides]]` block
+/// carrying a `scanner_config` fragment take their findings from a re-run
+/// with that fragment applied.
+fn scan_with_overrides(
+    check: &str,
+    label: &str,
+    config: &Config,
+    scope: &Scope,
+) -> Result<Vec<ScannerIssue>> {
+    let mut found = run_core_scanner(check, config, scope.path)?;
+    for o in &config.overrides {
+        let Some(fragment) = &o.scanner_config else {
+            continue;
+        };
+        let tuned = config.with_scanner_config_overlay(fragment)?;
+        found.retain(|si| !o.matches(Path::new(&si.file)));
+        found.extend(
+            run_core_scanner(check, &tuned, scope.path)?
+                .into_iter()
+                .filter(|si| o.matches(Path::new(&si.file))),
+        );
+    }
+    Ok(scoped_issues(config, check, label, scope, found))
+}
+
+/// Drop scanner findings that do not belong to the scope or are disabled
+/// for their file, and apply severity overrides.
+fn scoped_issues(
+    config: &Config,
+    check: &str,
+    label: &str,
+    scope: &Scope,
+    scanner_issues: Vec<ScannerIssue>,
+) -> Vec<ScannerIssue> {
+    let category = label.to_lowercase();
+    scanner_issues
+        .into_iter()
+        .filter(|si| scope.covers(Path::new(&si.file)))
+        .filter_map(|mut si| {
+            si.severity = effective_severity(
+                config,
+                check,
+                &category,
+                &si.rule,
+                Path::new(&si.file),
+                &si.severity,
+            )?;
+            Some(si)
+        })
+        .collect()
+}
+
+/// Apply global rules and `[[overrides]]` to one finding of `check`. Returns
+/// `None` when the finding is disabled for `file` (relative to the scope),
+/// otherwise its severity after overrides.
+fn effective_severity(
+    config: &Config,
+    check: &str,
+    category: &str,
+    rule: &str,
+    file: &Path,
+    severity: &str,
+) -> Option<String> {
+    let qualified = format!("{}/{}", category, rule);
+    let ids = [check, rule, qualified.as_str()];
+    if !config.is_enabled_for(&ids, file) {
+        debug!("{} disabled for {}", qualified, file.display());
+        return None;
+    }
+    Some(
+        config
+            .severity_override_for(&ids, file)
+            .map(|s| s.as_str().to_string())
+            .unwrap_or_else(|| severity.to_string()),
+    )
+}
+
+/// `file` relative to `project_path` when it lies inside it.
+fn relative_to<'a>(project_path: &str, file: &'a str) -> &'a Path {
+    let path = Path::new(file);
+    path.strip_prefix(project_path).unwrap_or(path)
+}
+
 fn perform_ast_analysis(
-    project_path: &str,
+    scope: &Scope<'_>,
+    config: &Config,
     ast_analyzer: &mut ASTAnalyzer,
-    issues: &mut Vec<String>,
+    issues: &mut Vec<Finding>,
 ) -> Result<()> {
-    for entry in WalkDir::new(project_path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-    {
+    let project_path = scope.path;
+    for entry in scope.walk_files() {
         let path = entry.path();
         let extension = path.extension().unwrap_or_default().to_string_lossy();
 
@@ -326,21 +734,34 @@ fn perform_ast_analysis(
                 Ok(content) => match ast_analyzer.analyze_file(path, &content) {
                     Ok(ast_issues) => {
                         for ast_issue in ast_issues {
-                            let severity_icon = match ast_issue.severity.as_str() {
+                            let Some(severity) = effective_severity(
+                                config,
+                                "ast_analysis",
+                                "ast",
+                                &ast_issue.rule,
+                                relative_to(project_path, &ast_issue.file),
+                                &ast_issue.severity,
+                            ) else {
+                                continue;
+                            };
+                            let severity_icon = match severity.as_str() {
                                 "error" => "❌",
                                 "warning" => "⚠️",
                                 "info" => "ℹ️",
                                 _ => "ℹ️",
                             };
 
-                            issues.push(format!(
-                                "{} {}:{}:{} - {} ({})",
-                                severity_icon,
-                                ast_issue.file,
-                                ast_issue.line,
-                                ast_issue.column,
-                                ast_issue.message,
-                                ast_issue.rule
+                            issues.push(Finding::new(
+                                severity_of(&severity),
+                                format!(
+                                    "{} {}:{}:{} - {} ({})",
+                                    severity_icon,
+                                    ast_issue.file,
+                                    ast_issue.line,
+                                    ast_issue.column,
+                                    ast_issue.message,
+                                    ast_issue.rule
+                                ),
                             ));
                         }
                     }
@@ -359,15 +780,16 @@ fn perform_ast_analysis(
 }
 
 fn process_modular_rule(
-    project_path: &str,
+    scope: &Scope,
     rule: &ModularRule,
-    issues: &mut Vec<String>,
+    issues: &mut Vec<Finding>,
     config: &Config,
 ) -> Result<()> {
     debug!("Processing rule: {}", rule.name);
+    let project_path = scope.path;
 
-    // Git branch rules
-    if config.is_check_enabled("git_branch") {
+    // Git branch rules (repository-wide, so only evaluated once at the root)
+    if scope.is_root && scope.files.is_none() && config.is_check_enabled("git_branch") {
         if let Some(git_config) = &rule.git {
             if let Some(git_info) = get_git_info(project_path)? {
                 if git_config.warn_wrong_branch {
@@ -386,7 +808,10 @@ fn process_modular_rule(
                                 "⚠️  Working on branch '{branch}' which may not be appropriate for file creation".to_string()
                             );
 
-                        issues.push(message.replace("{branch}", &git_info.current_branch));
+                        issues.push(Finding::new(
+                            RuleSeverity::Warning,
+                            message.replace("{branch}", &git_info.current_branch),
+                        ));
                     }
                 }
             }
@@ -394,24 +819,24 @@ fn process_modular_rule(
     }
 
     // File organization rules
-    if config.is_check_enabled("file_location") {
+    if config.is_check_enabled_anywhere("file_location", None) {
         if let Some(file_mappings) = &rule.file_mappings {
-            check_file_organization(project_path, file_mappings, rule, issues)?;
+            check_file_organization(scope, config, file_mappings, rule, issues)?;
         }
     }
 
     // Script location rules
-    if config.is_check_enabled("directory_structure") {
+    if config.is_check_enabled_anywhere("directory_structure", None) {
         if let Some(script_config) = &rule.scripts {
-            check_script_locations(project_path, script_config, rule, issues)?;
+            check_script_locations(scope, config, script_config, rule, issues)?;
         }
     }
 
     // Custom rules
-    if config.is_check_enabled("custom_rules") {
+    if config.is_check_enabled_anywhere("custom_rules", None) {
         if let Some(custom_rules) = &rule.rules {
             for custom_rule in custom_rules {
-                check_custom_rule(project_path, custom_rule, issues)?;
+                check_custom_rule(scope, config, custom_rule, issues)?;
             }
         }
     }
@@ -419,23 +844,52 @@ fn process_modular_rule(
     Ok(())
 }
 
+/// `config` with the `[[overrides]]` matching `file` (relative to the scope)
+/// folded in; borrowed when none match.
+fn config_for_file<'c>(config: &'c Config, file: &Path) -> Result<Cow<'c, Config>> {
+    if config.overrides_for(file).next().is_none() {
+        return Ok(Cow::Borrowed(config));
+    }
+    Ok(Cow::Owned(config.for_file(file)?))
+}
+
+/// Severity of a finding of `rule` (part of `check`) in `file` under the
+/// file's config, or `None` when that config disables it.
+fn file_rule_severity(
+    config: &Config,
+    check: &str,
+    rule: &str,
+    file: &Path,
+    severity: &RuleSeverity,
+) -> Result<Option<RuleSeverity>> {
+    let config = config_for_file(config, file)?;
+    let ids = [check, rule];
+    if !config.is_enabled_for(&ids, file) {
+        debug!("{} disabled for {}", rule, file.display());
+        return Ok(None);
+    }
+    Ok(Some(
+        config
+            .severity_override_for(&ids, file)
+            .unwrap_or_else(|| severity.clone()),
+    ))
+}
+
 fn check_file_organization(
-    project_path: &str,
+    scope: &Scope<'_>,
+    config: &Config,
     file_mappings: &std::collections::HashMap<String, String>,
     rule: &ModularRule,
-    issues: &mut Vec<String>,
+    issues: &mut Vec<Finding>,
 ) -> Result<()> {
+    let project_path = scope.path;
     let ignored_patterns = rule
         .ignored_patterns
         .as_ref()
         .map(|patterns| patterns.keys().cloned().collect::<Vec<_>>())
         .unwrap_or_default();
 
-    for entry in WalkDir::new(project_path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-    {
+    for entry in scope.walk_files() {
         let path = entry.path();
         let relative_path = path.strip_prefix(project_path).unwrap_or(path);
         let file_name = path.file_name().unwrap_or_default().to_string_lossy();
@@ -448,6 +902,16 @@ fn check_file_organization(
         // Check file mappings
         for (pattern, target_dir) in file_mappings {
             if matches_pattern(&file_name, pattern) {
+                let Some(severity) = file_rule_severity(
+                    config,
+                    "file_location",
+                    &rule.name,
+                    relative_path,
+                    &rule.severity,
+                )?
+                else {
+                    continue;
+                };
                 let current_dir = relative_path.parent().unwrap_or_else(|| Path::new(""));
                 if current_dir.to_string_lossy() != target_dir.trim_end_matches('/') {
                     let message: String = rule
@@ -458,12 +922,13 @@ fn check_file_organization(
                             "📁 File '{file}' should be in '{target_dir}' directory (matches pattern '{pattern}')".to_string()
                         );
 
-                    issues.push(
+                    issues.push(Finding::new(
+                        severity,
                         message
                             .replace("{file}", &relative_path.display().to_string())
                             .replace("{target_dir}", target_dir)
                             .replace("{pattern}", pattern),
-                    );
+                    ));
                 }
             }
         }
@@ -473,16 +938,14 @@ fn check_file_organization(
 }
 
 fn check_script_locations(
-    project_path: &str,
+    scope: &Scope<'_>,
+    config: &Config,
     script_config: &project_lint_core::config::ScriptRuleConfig,
     rule: &ModularRule,
-    issues: &mut Vec<String>,
+    issues: &mut Vec<Finding>,
 ) -> Result<()> {
-    for entry in WalkDir::new(project_path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-    {
+    let project_path = scope.path;
+    for entry in scope.walk_files() {
         let path = entry.path();
         let relative_path = path.strip_prefix(project_path).unwrap_or(path);
         let file_name = path.file_name().unwrap_or_default().to_string_lossy();
@@ -493,6 +956,16 @@ fn check_script_locations(
             .iter()
             .any(|ext| file_name.ends_with(ext))
         {
+            let Some(severity) = file_rule_severity(
+                config,
+                "directory_structure",
+                &rule.name,
+                relative_path,
+                &rule.severity,
+            )?
+            else {
+                continue;
+            };
             let current_dir = relative_path.parent().unwrap_or_else(|| Path::new(""));
             let preferred_dir = script_config.preferred_directory.trim_end_matches('/');
 
@@ -505,11 +978,12 @@ fn check_script_locations(
                         "📜 Script '{file}' should be in '{preferred_dir}' directory".to_string()
                     });
 
-                issues.push(
+                issues.push(Finding::new(
+                    severity,
                     message
                         .replace("{file}", &relative_path.display().to_string())
                         .replace("{preferred_dir}", preferred_dir),
-                );
+                ));
             }
         }
     }
@@ -518,10 +992,21 @@ fn check_script_locations(
 }
 
 fn check_custom_rule(
-    project_path: &str,
+    scope: &Scope<'_>,
+    config: &Config,
     custom_rule: &project_lint_core::config::CustomRule,
-    issues: &mut Vec<String>,
+    issues: &mut Vec<Finding>,
 ) -> Result<()> {
+    let project_path = scope.path;
+    // `when` conditions are about hook events, which a scan has none of
+    if custom_rule.when.is_some() {
+        debug!(
+            "Skipping rule '{}' because it only applies to hook events",
+            custom_rule.name
+        );
+        return Ok(());
+    }
+
     // Project-level activation gate: only evaluate if the marker exists.
     if let Some(enable_spec) = &custom_rule.enabled_if_path_exists {
         if !path_exists_glob(std::path::Path::new(project_path), enable_spec) {
@@ -555,6 +1040,14 @@ fn check_custom_rule(
         }
     }
 
+    let matcher = match ContentMatcher::for_rule(custom_rule) {
+        Ok(matcher) => matcher,
+        Err(e) => {
+            warn!("Skipping rule '{}': {}", custom_rule.name, e);
+            return Ok(());
+        }
+    };
+
     let mut found_match = false;
 
     // Determine effective allow status for filename checks
@@ -564,11 +1057,7 @@ fn check_custom_rule(
     // If `required` is false, we don't want to find it (denylist).
     let is_allowed = custom_rule.required || custom_rule.required_if_path_exists.is_some();
 
-    for entry in WalkDir::new(project_path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-    {
+    for entry in scope.walk_files() {
         let path = entry.path();
         let relative_path = path.strip_prefix(project_path).unwrap_or(path);
         let relative_path_str = relative_path.to_string_lossy();
@@ -615,35 +1104,38 @@ fn check_custom_rule(
             }
 
             found_match = true;
+            let Some(severity) = file_rule_severity(
+                config,
+                "custom_rules",
+                &custom_rule.name,
+                relative_path,
+                &custom_rule.severity,
+            )?
+            else {
+                continue;
+            };
 
             // Check content if required
             if custom_rule.check_content {
                 if let Ok(content) = std::fs::read_to_string(path) {
-                    let mut issue_found = false;
-
-                    if let Some(pattern) = &custom_rule.content_pattern {
-                        let contains = content.contains(pattern);
-
-                        match custom_rule.condition.as_deref() {
-                            Some("must_contain") => {
-                                // Issue if content DOES NOT contain pattern
-                                if !contains {
-                                    issue_found = true;
-                                }
-                            }
-                            _ => {
-                                // Default: Issue if content DOES contain pattern (denylist)
-                                if contains {
-                                    issue_found = true;
+                    let mut location = None;
+                    let issue_found = match &matcher {
+                        Some(matcher) => {
+                            let found = matcher.find(&content);
+                            match custom_rule.condition.as_deref() {
+                                // Issue if content DOES NOT match
+                                Some("must_contain") => found.is_none(),
+                                // Default: Issue if content DOES match (denylist)
+                                _ => {
+                                    location = found;
+                                    location.is_some()
                                 }
                             }
                         }
-                    } else {
-                        // If check_content is true but no pattern specified
-                        // If must_contain is set, and no pattern, that's weird config.
-                        // Assume issue found for file existence if no pattern and default condition.
-                        issue_found = true;
-                    }
+                        // If check_content is true but no pattern specified,
+                        // assume issue found for file existence.
+                        None => true,
+                    };
 
                     if issue_found {
                         // Check for exception pattern
@@ -654,18 +1146,32 @@ fn check_custom_rule(
                             }
                         }
 
-                        let severity_icon = match custom_rule.severity {
+                        let severity_icon = match severity {
                             project_lint_core::config::RuleSeverity::Error => "❌",
                             project_lint_core::config::RuleSeverity::Warning => "⚠️",
                             project_lint_core::config::RuleSeverity::Info => "ℹ️",
                         };
 
-                        issues.push(format!(
-                            "{} {}: {} ({})",
-                            severity_icon,
-                            custom_rule.name,
-                            custom_rule.message,
-                            relative_path.display()
+                        issues.push(Finding::new(
+                            severity.clone(),
+                            match &location {
+                                Some(found) => format!(
+                                    "{} {}: {} ({}:{}:{})",
+                                    severity_icon,
+                                    custom_rule.name,
+                                    found.interpolate(&custom_rule.message),
+                                    relative_path.display(),
+                                    found.line,
+                                    found.column
+                                ),
+                                None => format!(
+                                    "{} {}: {} ({})",
+                                    severity_icon,
+                                    custom_rule.name,
+                                    custom_rule.message,
+                                    relative_path.display()
+                                ),
+                            },
                         ));
                     }
                 }
@@ -675,18 +1181,21 @@ fn check_custom_rule(
                 // If NOT allowed (denylist), finding matches is bad.
 
                 if !is_allowed {
-                    let severity_icon = match custom_rule.severity {
+                    let severity_icon = match severity {
                         project_lint_core::config::RuleSeverity::Error => "❌",
                         project_lint_core::config::RuleSeverity::Warning => "⚠️",
                         project_lint_core::config::RuleSeverity::Info => "ℹ️",
                     };
 
-                    issues.push(format!(
-                        "{} {}: {} ({})",
-                        severity_icon,
-                        custom_rule.name,
-                        custom_rule.message,
-                        relative_path.display()
+                    issues.push(Finding::new(
+                        severity.clone(),
+                        format!(
+                            "{} {}: {} ({})",
+                            severity_icon,
+                            custom_rule.name,
+                            custom_rule.message,
+                            relative_path.display()
+                        ),
                     ));
                 }
             }
@@ -699,8 +1208,9 @@ fn check_custom_rule(
     // So if `required` OR `required_if` is set, we expect a match.
     let expect_match = custom_rule.required || custom_rule.required_if_path_exists.is_some();
 
-    if expect_match && !found_match {
-        let severity_icon = match custom_rule.severity {
+    // A subset of the files cannot tell that a required one is missing
+    if expect_match && !found_match && scope.files.is_none() {
+        let severity_icon = match config.severity_for(&custom_rule.name, &custom_rule.severity) {
             project_lint_core::config::RuleSeverity::Error => "❌",
             project_lint_core::config::RuleSeverity::Warning => "⚠️",
             project_lint_core::config::RuleSeverity::Info => "ℹ️",
@@ -712,109 +1222,22 @@ fn check_custom_rule(
             "".to_string()
         };
 
-        issues.push(format!(
-            "{} {}: {} (Missing required file matching '{}'{})",
-            severity_icon, custom_rule.name, custom_rule.message, custom_rule.pattern, context_msg
+        issues.push(Finding::new(
+            config.severity_for(&custom_rule.name, &custom_rule.severity),
+            format!(
+                "{} {}: {} (Missing required file matching '{}'{})",
+                severity_icon,
+                custom_rule.name,
+                custom_rule.message,
+                custom_rule.pattern,
+                context_msg
+            ),
         ));
     }
 
     Ok(())
 }
 
-// Legacy functions for backward compatibility
-fn check_legacy_git_branches(
-    project_path: &str,
-    config: &Config,
-    issues: &mut Vec<String>,
-) -> Result<()> {
-    if let Some(git_info) = get_git_info(project_path)? {
-        if config.git.warn_wrong_branch {
-            let branch_allowed = check_branch_allowed(
-                &git_info,
-                &config.git.allowed_branches,
-                &config.git.forbidden_branches,
-            )?;
-
-            if !branch_allowed {
-                issues.push(format!(
-                    "⚠️  Working on branch '{}' which may not be appropriate for file creation",
-                    git_info.current_branch
-                ));
-            }
-        }
-    }
-    Ok(())
-}
-
-fn check_legacy_file_structure(
-    project_path: &str,
-    config: &Config,
-    issues: &mut Vec<String>,
-) -> Result<()> {
-    for entry in WalkDir::new(project_path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-    {
-        let path = entry.path();
-        let relative_path = path.strip_prefix(project_path).unwrap_or(path);
-        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
-
-        if should_ignore_path(relative_path, &config.files.ignored_patterns) {
-            continue;
-        }
-
-        if config.files.auto_move {
-            for (pattern, target_dir) in &config.files.type_mappings {
-                if matches_pattern(&file_name, pattern) {
-                    let current_dir = relative_path.parent().unwrap_or_else(|| Path::new(""));
-                    if current_dir.to_string_lossy() != target_dir.trim_end_matches('/') {
-                        issues.push(format!(
-                            "📁 File '{}' should be in '{}' directory (matches pattern '{}')",
-                            relative_path.display(),
-                            target_dir,
-                            pattern
-                        ));
-                    }
-                }
-            }
-        }
-    }
-    Ok(())
-}
-
-fn check_legacy_directory_structure(
-    project_path: &str,
-    config: &Config,
-    issues: &mut Vec<String>,
-) -> Result<()> {
-    if config.directories.warn_scripts_location {
-        let scripts_dir = &config.directories.scripts_directory;
-
-        for entry in WalkDir::new(project_path)
-            .into_iter()
-            .filter_map(|e| e.ok())
-            .filter(|e| e.file_type().is_file())
-        {
-            let path = entry.path();
-            let relative_path = path.strip_prefix(project_path).unwrap_or(path);
-            let file_name = path.file_name().unwrap_or_default().to_string_lossy();
-
-            if is_script_file(&file_name) {
-                let current_dir = relative_path.parent().unwrap_or_else(|| Path::new(""));
-                if current_dir.to_string_lossy() != scripts_dir.trim_end_matches('/') {
-                    issues.push(format!(
-                        "📜 Script '{}' should be in '{}' directory",
-                        relative_path.display(),
-                        scripts_dir
-                    ));
-                }
-            }
-        }
-    }
-    Ok(())
-}
-
 fn should_ignore_path(path: &Path, ignored_patterns: &[String]) -> bool {
     let path_str = path.to_string_lossy();
     ignored_patterns.iter().any(|pattern| {
@@ -827,11 +1250,13 @@ fn should_ignore_path(path: &Path, ignored_patterns: &[String]) -> bool {
 }
 
 fn perform_security_analysis(
-    project_path: &str,
-    issues: &mut Vec<String>,
+    scope: &Scope<'_>,
+    config: &Config,
+    issues: &mut Vec<Finding>,
     apply_fixes: bool,
     dry_run: bool,
 ) -> Result<()> {
+    let project_path = scope.path;
     let scanner = match SecurityScanner::new() {
         Ok(s) => s,
         Err(e) => {
@@ -844,11 +1269,7 @@ fn perform_security_analysis(
     let mut total_fixes = 0;
 
     // Scan all source files
-    for entry in WalkDir::new(project_path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-    {
+    for entry in scope.walk_files() {
         let path = entry.path();
         let file_name = path.file_name().unwrap_or_default().to_string_lossy();
 
@@ -882,15 +1303,34 @@ fn perform_security_analysis(
         }
 
         match scanner.scan_file(path) {
-            Ok(detected_issues) => {
+            Ok(mut detected_issues) => {
+                detected_issues.retain_mut(|issue| {
+                    match effective_severity(
+                        config,
+                        "security_analysis",
+                        "security",
+                        &issue.pattern_name,
+                        relative_to(project_path, &issue.file),
+                        &issue.severity,
+                    ) {
+                        Some(severity) => {
+                            issue.severity = severity;
+                            true
+                        }
+                        None => false,
+                    }
+                });
                 for issue in &detected_issues {
                     security_issues.push(issue.clone());
-                    issues.push(format!(
-                        "🔒 [{}] {} ({}:{})",
-                        issue.severity.to_uppercase(),
-                        issue.message,
-                        issue.file,
-                        issue.line
+                    issues.push(Finding::new(
+                        severity_of(&issue.severity),
+                        format!(
+                            "🔒 [{}] {} ({}:{})",
+                            issue.severity.to_uppercase(),
+                            issue.message,
+                            issue.file,
+                            issue.line
+                        ),
                     ));
                 }
 
@@ -929,11 +1369,13 @@ fn perform_security_analysis(
 }
 
 fn perform_typescript_analysis(
-    project_path: &str,
-    issues: &mut Vec<String>,
+    scope: &Scope<'_>,
+    config: &Config,
+    issues: &mut Vec<Finding>,
     apply_fixes: bool,
     dry_run: bool,
 ) -> Result<()> {
+    let project_path = scope.path;
     let scanner = match TypeScriptScanner::new() {
         Ok(s) => s,
         Err(e) => {
@@ -945,11 +1387,7 @@ fn perform_typescript_analysis(
     let mut total_fixes = 0;
 
     // Scan TypeScript and JavaScript files
-    for entry in WalkDir::new(project_path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-    {
+    for entry in scope.walk_files() {
         let path = entry.path();
         let file_name = path.file_name().unwrap_or_default().to_string_lossy();
 
@@ -984,14 +1422,33 @@ fn perform_typescript_analysis(
         }
 
         match scanner.scan_file(path) {
-            Ok(detected_issues) => {
+            Ok(mut detected_issues) => {
+                detected_issues.retain_mut(|issue| {
+                    match effective_severity(
+                        config,
+                        "typescript_analysis",
+                        "typescript",
+                        &issue.pattern_name,
+                        relative_to(project_path, &issue.file),
+                        &issue.severity,
+                    ) {
+                        Some(severity) => {
+                            issue.severity = severity;
+                            true
+                        }
+                        None => false,
+                    }
+                });
                 for issue in &detected_issues {
-                    issues.push(format!(
-                        "📘 [TypeScript] [{}] {} ({}:{})",
-                        issue.severity.to_uppercase(),
-                        issue.message,
-                        issue.file,
-                        issue.line
+                    issues.push(Finding::new(
+                        severity_of(&issue.severity),
+                        format!(
+                            "📘 [TypeScript] [{}] {} ({}:{})",
+                            issue.severity.to_uppercase(),
+                            issue.message,
+                            issue.file,
+                            issue.line
+                        ),
                     ));
                 }
 
@@ -1041,21 +1498,48 @@ fn perform_typescript_analysis(
     Ok(())
 }
 
-fn is_script_file(file_name: &str) -> bool {
-    let script_extensions = [".sh", ".py", ".js", ".ts", ".rb", ".pl", ".php"];
-    script_extensions.iter().any(|ext| file_name.ends_with(ext))
-}
-
 async fn perform_dependency_analysis(
-    project_path: &str,
-    issues: &mut Vec<String>,
+    scope: &Scope<'_>,
+    config: &Config,
+    issues: &mut Vec<Finding>,
     apply_fixes: bool,
     dry_run: bool,
 ) -> Result<()> {
+    let project_path = scope.path;
     let checker = DependencyVersionChecker::new();
 
     match checker.scan(project_path).await {
-        Ok(detected_issues) => {
+        Ok(mut detected_issues) => {
+            detected_issues.retain_mut(|issue| {
+                let file = relative_to(project_path, &issue.file_path);
+                if !scope.covers(file) {
+                    return false;
+                }
+                let default = match issue.severity {
+                    DepSeverity::Error => "error",
+                    DepSeverity::Warning => "warning",
+                    DepSeverity::Info => "info",
+                };
+                match effective_severity(
+                    config,
+                    "dependency_versions",
+                    "dependencies",
+                    &issue.name,
+                    file,
+                    default,
+                ) {
+                    Some(severity) => {
+                        issue.severity = match severity.as_str() {
+                            "error" => DepSeverity::Error,
+                            "info" => DepSeverity::Info,
+                            _ => DepSeverity::Warning,
+                        };
+                        true
+                    }
+                    None => false,
+                }
+            });
+
             for issue in &detected_issues {
                 let severity_icon = match issue.severity {
                     DepSeverity::Error => "🔴",
@@ -1063,9 +1547,16 @@ async fn perform_dependency_analysis(
                     DepSeverity::Info => "🟢",
                 };
 
-                issues.push(format!(
-                    "{} [Dependencies] {} ({})",
-                    severity_icon, issue.message, issue.file_path
+                issues.push(Finding::new(
+                    match issue.severity {
+                        DepSeverity::Error => RuleSeverity::Error,
+                        DepSeverity::Warning => RuleSeverity::Warning,
+                        DepSeverity::Info => RuleSeverity::Info,
+                    },
+                    format!(
+                        "{} [Dependencies] {} ({})",
+                        severity_icon, issue.message, issue.file_path
+                    ),
                 ));
             }
 
@@ -1102,6 +1593,15 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    fn root_scope<'a>(path: &'a str, excluded: &'a [PathBuf]) -> Scope<'a> {
+        Scope {
+            path,
+            excluded,
+            is_root: true,
+            files: None,
+        }
+    }
+
     fn ban_ts_rule() -> CustomRule {
         CustomRule {
             name: "ban_ambiguous_ts".to_string(),
@@ -1110,6 +1610,9 @@ mod tests {
             severity: RuleSeverity::Warning,
             check_content: false,
             content_pattern: None,
+            content_regex: vec![],
+            content_flags: None,
+            content_match: Default::default(),
             exception_pattern: None,
             condition: None,
             required: false,
@@ -1122,6 +1625,8 @@ mod tests {
                 "**/*.test.ts".to_string(),
             ],
             triggers: vec![],
+            when: None,
+            ask: false,
             mode: ExecutionMode::LocalSync,
         }
     }
@@ -1134,12 +1639,17 @@ mod tests {
 
         let rule = ban_ts_rule();
         let mut issues = Vec::new();
-        check_custom_rule(&dir.path().to_string_lossy(), &rule, &mut issues)?;
+        check_custom_rule(
+            &root_scope(&dir.path().to_string_lossy(), &[]),
+            &Config::default(),
+            &rule,
+            &mut issues,
+        )?;
 
         // foo.ts should be flagged; types.d.ts should be exempt.
         assert_eq!(issues.len(), 1);
-        assert!(issues[0].contains("foo.ts"));
-        assert!(!issues[0].contains("types.d.ts"));
+        assert!(issues[0].message.contains("foo.ts"));
+        assert!(!issues[0].message.contains("types.d.ts"));
         Ok(())
     }
 
@@ -1152,11 +1662,49 @@ mod tests {
 
         let rule = ban_ts_rule();
         let mut issues = Vec::new();
-        check_custom_rule(&dir.path().to_string_lossy(), &rule, &mut issues)?;
+        check_custom_rule(
+            &root_scope(&dir.path().to_string_lossy(), &[]),
+            &Config::default(),
+            &rule,
+            &mut issues,
+        )?;
 
         // Only bar.ts should be flagged.
         assert_eq!(issues.len(), 1);
-        assert!(issues[0].contains("bar.ts"));
+        assert!(issues[0].message.contains("bar.ts"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_content_regex_all_reports_location_and_captures() -> Result<()> {
+        let dir = TempDir::new()?;
+        fs::write(
+            dir.path().join("api.ts"),
+            "import x from 'y';\nconst url = \"http://internal.example\";\nfetch(url);\n",
+        )?;
+        fs::write(dir.path().join("util.ts"), "const url = \"http://a\";\n")?;
+
+        let mut rule = ban_ts_rule();
+        rule.check_content = true;
+        rule.message = "plain http to {host}".to_string();
+        rule.content_regex = vec![
+            r#""http://(?P<host>[\w.]+)""#.to_string(),
+            r"FETCH\(".to_string(),
+        ];
+        rule.content_flags = Some("i".to_string());
+        rule.content_match = project_lint_core::ContentMatchMode::All;
+
+        let mut issues = Vec::new();
+        check_custom_rule(
+            &root_scope(&dir.path().to_string_lossy(), &[]),
+            &Config::default(),
+            &rule,
+            &mut issues,
+        )?;
+        assert_eq!(issues.len(), 1, "{:?}", issues);
+        assert!(issues[0]
+            .message
+            .contains("plain http to internal.example (api.ts:2:13)"));
         Ok(())
     }
 
@@ -1173,7 +1721,12 @@ mod tests {
         rule.disabled_if_path_exists = Some("next.config.*".to_string());
 
         let mut issues = Vec::new();
-        check_custom_rule(&dir.path().to_string_lossy(), &rule, &mut issues)?;
+        check_custom_rule(
+            &root_scope(&dir.path().to_string_lossy(), &[]),
+            &Config::default(),
+            &rule,
+            &mut issues,
+        )?;
 
         // Next.js project: .ts ban is disabled entirely.
         assert!(
@@ -1194,11 +1747,16 @@ mod tests {
         rule.disabled_if_path_exists = Some("next.config.*".to_string());
 
         let mut issues = Vec::new();
-        check_custom_rule(&dir.path().to_string_lossy(), &rule, &mut issues)?;
+        check_custom_rule(
+            &root_scope(&dir.path().to_string_lossy(), &[]),
+            &Config::default(),
+            &rule,
+            &mut issues,
+        )?;
 
         // No next.config -> rule active -> utils.ts flagged.
         assert_eq!(issues.len(), 1);
-        assert!(issues[0].contains("utils.ts"));
+        assert!(issues[0].message.contains("utils.ts"));
         Ok(())
     }
 
@@ -1214,7 +1772,12 @@ mod tests {
         rule.enabled_if_path_exists = Some("tsconfig.json".to_string());
 
         let mut issues = Vec::new();
-        check_custom_rule(&dir.path().to_string_lossy(), &rule, &mut issues)?;
+        check_custom_rule(
+            &root_scope(&dir.path().to_string_lossy(), &[]),
+            &Config::default(),
+            &rule,
+            &mut issues,
+        )?;
 
         // Non-TS project (no tsconfig.json) -> rule not activated -> no issues.
         assert!(
@@ -1237,11 +1800,16 @@ mod tests {
         rule.enabled_if_path_exists = Some("tsconfig.json".to_string());
 
         let mut issues = Vec::new();
-        check_custom_rule(&dir.path().to_string_lossy(), &rule, &mut issues)?;
+        check_custom_rule(
+            &root_scope(&dir.path().to_string_lossy(), &[]),
+            &Config::default(),
+            &rule,
+            &mut issues,
+        )?;
 
         // TS project -> rule active -> utils.ts flagged.
         assert_eq!(issues.len(), 1);
-        assert!(issues[0].contains("utils.ts"));
+        assert!(issues[0].message.contains("utils.ts"));
         Ok(())
     }
 
@@ -1259,7 +1827,12 @@ mod tests {
         rule.disabled_if_path_exists = Some("next.config.*".to_string());
 
         let mut issues = Vec::new();
-        check_custom_rule(&dir.path().to_string_lossy(), &rule, &mut issues)?;
+        check_custom_rule(
+            &root_scope(&dir.path().to_string_lossy(), &[]),
+            &Config::default(),
+            &rule,
+            &mut issues,
+        )?;
 
         // Both gates: enabled (tsconfig exists) AND disabled (next.config exists) -> disabled wins.
         assert!(
@@ -1269,4 +1842,354 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_nested_scope_dirs_are_skipped() -> Result<()> {
+        let dir = TempDir::new()?;
+        fs::create_dir_all(dir.path().join("apps/web"))?;
+        fs::write(dir.path().join("apps/web/page.ts"), "export {};\n")?;
+        fs::write(dir.path().join("root.ts"), "export {};\n")?;
+
+        let rule = ban_ts_rule();
+        let mut issues = Vec::new();
+        let excluded = vec![PathBuf::from("apps/web")];
+        check_custom_rule(
+            &root_scope(&dir.path().to_string_lossy(), &excluded),
+            &Config::default(),
+            &rule,
+            &mut issues,
+        )?;
+
+        // apps/web has its own config and is linted as a separate scope.
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("root.ts"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_overrides_scope_scanner_config_and_rules() -> Result<()> {
+        let dir = TempDir::new()?;
+        fs::create_dir_all(dir.path().join("deploy"))?;
+        let dockerfile = "FROM alpine:3.19\nUSER app\n";
+        fs::write(dir.path().join("Dockerfile"), dockerfile)?;
+        fs::write(dir.path().join("deploy/Dockerfile"), dockerfile)?;
+
+        let config: Config = toml::from_str(
+            r#"
+[scanner_config.dockerfile_security]
+require_pinned_digests = false
+require_non_root_user = true
+forbid_copy_dot = true
+
+[[overrides]]
+files = ["deploy/**"]
+scanner_config = { dockerfile_security = { require_pinned_digests = true } }
+severity_overrides = { "docker/pin-image-digests" = "error" }
+"#,
+        )?;
+        let path = dir.path().to_string_lossy();
+        let scope = Scope {
+            path: &path,
+            excluded: &[],
+            is_root: true,
+            files: None,
+        };
+
+        let found = scan_with_overrides("dockerfile_lint", "Docker", &config, &scope)?;
+        // Only deploy/ requires pinned digests, and there it is an error.
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].file, "deploy/Dockerfile");
+        assert_eq!(found[0].rule, "pin-image-digests");
+        assert_eq!(found[0].severity, "error");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_override_enabling_a_rule_runs_its_disabled_check() -> Result<()> {
+        let dir = TempDir::new()?;
+        fs::create_dir_all(dir.path().join("deploy"))?;
+        let dockerfile = "FROM alpine:3.19\nUSER app\n";
+        fs::write(dir.path().join("Dockerfile"), dockerfile)?;
+        fs::write(dir.path().join("deploy/Dockerfile"), dockerfile)?;
+
+        // dockerfile_lint is off globally; only the override turns one rule on
+        let config: Config = toml::from_str(
+            r#"
+[rules]
+mode = "allowlist"
+enabled_checks = ["file_naming"]
+
+[scanner_config.dockerfile_security]
+require_pinned_digests = true
+
+[[overrides]]
+files = ["deploy/**"]
+enabled_checks = ["docker/pin-image-digests"]
+"#,
+        )?;
+        let path = dir.path().to_string_lossy();
+        let scope = Scope {
+            path: &path,
+            excluded: &[],
+            is_root: true,
+            files: None,
+        };
+
+        let mut issues = Vec::new();
+        lint_scope(&scope, config, &mut issues, false, false).await?;
+        let docker: Vec<&Finding> = issues
+            .iter()
+            .filter(|i| i.message.contains("[Docker]"))
+            .collect();
+        assert_eq!(docker.len(), 1, "got: {:?}", issues);
+        assert!(docker[0].message.contains("deploy/Dockerfile"));
+        assert!(docker[0].message.contains("pin-image-digests"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_profile_structure_reports_and_scaffolds() -> Result<()> {
+        let dir = TempDir::new()?;
+        fs::create_dir_all(dir.path().join("secrets"))?;
+
+        let config = Config {
+            active_profiles: vec![toml::from_str(
+                r#"
+[metadata]
+name = "devops"
+version = "1.0.0"
+updated = "2025-01-01"
+description = "devops"
+
+[activation]
+
+[enable]
+domains = []
+plugins = []
+
+[structure]
+expected_dirs = ["infra/"]
+forbidden_dirs = ["secrets/"]
+"#,
+            )?],
+            ..Config::default()
+        };
+        let path = dir.path().to_string_lossy();
+        let scope = Scope {
+            path: &path,
+            excluded: &[],
+            is_root: true,
+            files: None,
+        };
+
+        let mut issues = Vec::new();
+        perform_profile_structure_analysis(&scope, &config, &mut issues, false, true)?;
+        assert_eq!(issues.len(), 2);
+        assert!(issues
+            .iter()
+            .all(|i| i.message.contains("[Structure] profile 'devops'")));
+        assert!(!dir.path().join("infra").exists());
+
+        let mut issues = Vec::new();
+        perform_profile_structure_analysis(&scope, &config, &mut issues, true, false)?;
+        assert!(dir.path().join("infra").is_dir());
+        Ok(())
+    }
+
+    #[test]
+    fn test_profile_structure_runs_under_allowlist_unless_disabled() -> Result<()> {
+        let dir = TempDir::new()?;
+        fs::create_dir_all(dir.path().join("secrets"))?;
+
+        // An allowlist like the one `init` writes, without `profile_structure`
+        let mut config: Config = toml::from_str(
+            r#"
+version = 2
+
+[rules]
+mode = "allowlist"
+enabled_checks = ["file_naming", "security_analysis", "custom_rules"]
+"#,
+        )?;
+        config = config.with_active_profiles(vec![toml::from_str(
+            r#"
+[metadata]
+name = "devops"
+version = "1.0.0"
+updated = "2025-01-01"
+description = "devops"
+
+[activation]
+
+[enable]
+domains = []
+plugins = []
+
+[structure]
+expected_dirs = []
+forbidden_dirs = ["secrets/"]
+"#,
+        )?])?;
+        let path = dir.path().to_string_lossy();
+        let scope = Scope {
+            path: &path,
+            excluded: &[],
+            is_root: true,
+            files: None,
+        };
+
+        let mut issues = Vec::new();
+        perform_profile_structure_analysis(&scope, &config, &mut issues, false, false)?;
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("secrets"));
+
+        config
+            .rules
+            .disabled_checks
+            .push("profile_structure".to_string());
+        let mut issues = Vec::new();
+        perform_profile_structure_analysis(&scope, &config, &mut issues, false, false)?;
+        assert!(issues.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_web_checks_follow_profile_toggles() -> Result<()> {
+        let dir = TempDir::new()?;
+        fs::write(
+            dir.path().join("index.html"),
+            "<html lang=\"en\"><head><title>Home</title></head>\n<img src=\"a.png\"></html>\n",
+        )?;
+
+        let mut config = Config::default();
+        let path = dir.path().to_string_lossy();
+        let scope = Scope {
+            path: &path,
+            excluded: &[],
+            is_root: true,
+            files: None,
+        };
+
+        // No active profile asks for web checks
+        let mut issues = Vec::new();
+        perform_web_analysis(&scope, &config, &mut issues)?;
+        assert!(issues.is_empty());
+
+        config.active_profiles = vec![toml::from_str(
+            r#"
+[metadata]
+name = "web"
+version = "1.0.0"
+updated = "2025-01-01"
+description = "web"
+
+[activation]
+
+[enable]
+domains = []
+plugins = []
+
+[web_specific]
+check_html_semantics = false
+validate_css_properties = false
+lint_javascript = false
+check_accessibility = true
+optimize_images = false
+check_seo_meta = false
+"#,
+        )?];
+        perform_web_analysis(&scope, &config, &mut issues)?;
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0]
+            .message
+            .contains("[Web] <img> without alt text (index.html:2: img-alt)"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_lint_files_reports_only_the_given_files() -> Result<()> {
+        let dir = TempDir::new()?;
+        fs::create_dir(dir.path().join("src"))?;
+        fs::write(dir.path().join("src/changed.ts"), "export {};\n")?;
+        fs::write(dir.path().join("src/untouched.ts"), "export {};\n")?;
+
+        let mut config = Config::default();
+        let mut rule = ban_ts_rule();
+        rule.severity = RuleSeverity::Error;
+        let mut modular: project_lint_core::config::ModularRule = toml::from_str(
+            r#"
+name = "typescript"
+description = "TypeScript files"
+enabled = true
+severity = "error"
+"#,
+        )?;
+        modular.rules = Some(vec![rule]);
+        config.modular_rules = vec![modular];
+
+        let path = dir.path().to_string_lossy();
+        let issues = lint_files(&path, &config, &[PathBuf::from("src/changed.ts")]).await?;
+        let errors: Vec<&Finding> = issues
+            .iter()
+            .filter(|i| i.severity == RuleSeverity::Error)
+            .collect();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message.contains("changed.ts"));
+        assert!(issues.iter().all(|i| !i.message.contains("untouched.ts")));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_lint_applies_overrides_to_modular_rules() -> Result<()> {
+        let dir = TempDir::new()?;
+        for sub in ["src", "legacy", "scripts"] {
+            fs::create_dir(dir.path().join(sub))?;
+        }
+        fs::write(dir.path().join("src/app.ts"), "export {};\n")?;
+        fs::write(dir.path().join("legacy/old.ts"), "export {};\n")?;
+        fs::write(dir.path().join("src/build.sh"), "echo hi\n")?;
+        fs::write(dir.path().join("legacy/run.sh"), "echo hi\n")?;
+
+        let mut layout: ModularRule = toml::from_str(
+            r#"
+name = "layout"
+description = "Project layout"
+enabled = true
+severity = "warning"
+
+[scripts]
+script_extensions = [".sh"]
+preferred_directory = "scripts/"
+alternative_directories = []
+"#,
+        )?;
+        layout.rules = Some(vec![ban_ts_rule()]);
+        let mut config: Config = toml::from_str(
+            r#"
+[[overrides]]
+files = ["legacy/**"]
+disabled_checks = ["ban_ambiguous_ts", "directory_structure"]
+
+[[overrides]]
+files = ["src/**"]
+severity_overrides = { "ban_ambiguous_ts" = "error" }
+"#,
+        )?;
+        config.modular_rules = vec![layout];
+
+        let path = dir.path().to_string_lossy();
+        let issues = lint_project(&path, &config, None, false, false).await?;
+        let relevant: Vec<&str> = issues
+            .iter()
+            .map(|i| i.message.as_str())
+            .filter(|i| i.contains("ban_ambiguous_ts") || i.starts_with("📜"))
+            .collect();
+        assert_eq!(relevant.len(), 2, "{:?}", relevant);
+        assert!(issues.iter().any(|i| i.severity == RuleSeverity::Error
+            && i.message.starts_with("❌ ban_ambiguous_ts")
+            && i.message.contains("src/app.ts")));
+        assert!(relevant.iter().any(|i| i.contains("src/build.sh")));
+        assert!(relevant.iter().all(|i| !i.contains("legacy/")));
+        Ok(())
+    }
 }
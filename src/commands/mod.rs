@@ -1,11 +1,14 @@
+pub mod config;
 pub mod configure;
 pub mod configure_cmd;
+pub mod daemon;
 pub mod hook;
 pub mod init;
 pub mod install_hook;
 pub mod lint;
 pub mod logs;
 pub mod policy;
+pub mod profiles;
 pub mod watch;
 
 pub use configure_cmd::run as configure;
@@ -0,0 +1,148 @@
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use project_lint_core::config::Config;
+use project_lint_core::profiles::explain_profiles;
+use project_lint_core::utils::Result;
+use std::path::Path;
+
+use crate::commands::hook::mapper_for;
+
+#[derive(Args)]
+pub struct ProfilesArgs {
+    #[command(subcommand)]
+    pub command: ProfilesCommand,
+}
+
+#[derive(Subcommand)]
+pub enum ProfilesCommand {
+    /// Show each profile, whether it is active, and what activated it
+    Explain(ExplainArgs),
+}
+
+#[derive(Args)]
+pub struct ExplainArgs {
+    /// Path to the project root (defaults to current directory)
+    #[arg(short, long)]
+    pub path: Option<String>,
+
+    /// Hook payload to evaluate event triggers against, as sent by the IDE
+    #[arg(long, value_name = "JSON")]
+    pub event: Option<String>,
+
+    /// Source of the --event payload (windsurf, claude, kiro)
+    #[arg(long, default_value = "windsurf")]
+    pub source: String,
+
+    /// Path to the config file (overrides project-local discovery)
+    #[arg(long)]
+    pub config_file: Option<String>,
+}
+
+pub async fn run(args: ProfilesArgs, overrides: &[String]) -> Result<()> {
+    match args.command {
+        ProfilesCommand::Explain(explain_args) => run_explain(explain_args, overrides).await,
+    }
+}
+
+async fn run_explain(args: ExplainArgs, overrides: &[String]) -> Result<()> {
+    let project_path = args.path.unwrap_or_else(|| ".".to_string());
+    let config = match &args.config_file {
+        Some(config_file) => Config::load_from_file(Path::new(config_file))?,
+        None => Config::load()?,
+    }
+    .with_runtime_overrides(overrides)?;
+
+    let event = match &args.event {
+        Some(payload) => Some(
+            mapper_for(&args.source)
+                .map_event(payload)
+                .map_err(|e| anyhow::anyhow!("Invalid --event payload: {}", e))?,
+        ),
+        None => None,
+    };
+
+    let reports = explain_profiles(
+        Path::new(&project_path),
+        &config.active_profiles,
+        event.as_ref(),
+    )?;
+    if reports.is_empty() {
+        println!("{}", "No profiles configured".yellow());
+        return Ok(());
+    }
+
+    for report in &reports {
+        if report.active {
+            println!("{} {}", "✓".green(), report.profile.green());
+        } else {
+            println!("{} {}", "✗".dimmed(), report.profile.dimmed());
+        }
+        for matched in &report.matched {
+            println!("    matched {}", matched);
+        }
+        if let Some(expression) = &report.expression {
+            println!("    when {}", expression);
+        }
+        if !report.active && report.matched.is_empty() && report.expression.is_none() {
+            println!("    no activation criteria matched");
+        }
+    }
+
+    // Matching profiles still go through priority, requires and conflicts
+    let matching = config
+        .active_profiles
+        .iter()
+        .zip(&reports)
+        .filter(|(_, report)| report.active)
+        .map(|(profile, _)| profile.clone())
+        .collect();
+    let resolved = config.with_active_profiles(matching)?;
+    if !resolved.diagnostics.is_empty() {
+        println!();
+        for diagnostic in &resolved.diagnostics {
+            println!("{} {}", "⚠️".yellow(), diagnostic);
+        }
+    }
+
+    println!();
+    println!(
+        "{} of {} profile(s) active",
+        resolved.active_profiles.len(),
+        reports.len()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct TestCli {
+        #[command(subcommand)]
+        command: TestCommands,
+    }
+
+    #[derive(clap::Subcommand)]
+    enum TestCommands {
+        Profiles(ProfilesArgs),
+    }
+
+    #[test]
+    fn test_explain_args() {
+        let cli = TestCli::parse_from([
+            "test", "profiles", "explain", "--event", "{}", "--source", "claude",
+        ]);
+        match cli.command {
+            TestCommands::Profiles(args) => match args.command {
+                ProfilesCommand::Explain(explain) => {
+                    assert_eq!(explain.event.as_deref(), Some("{}"));
+                    assert_eq!(explain.source, "claude");
+                    assert!(explain.path.is_none());
+                }
+            },
+        }
+    }
+}
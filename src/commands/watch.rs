@@ -6,12 +6,12 @@ use std::sync::mpsc;
 use std::time::Duration;
 use tracing::{debug, info, warn};
 
-use project_lint_core::config::Config;
+use project_lint_core::config::{Config, ModularRule};
 
-pub async fn run(project_path: &str) -> Result<()> {
+pub async fn run(project_path: &str, overrides: &[String]) -> Result<()> {
     info!("Starting file watcher for project: {}", project_path);
 
-    let config = Config::load()?;
+    let config = Config::load()?.with_runtime_overrides(overrides)?;
 
     // Check if project path exists
     if !Path::new(project_path).exists() {
@@ -70,12 +70,20 @@ async fn run_lint_checks(project_path: &str, config: &Config) -> Result<()> {
 
     // Git branch checks
     if config.is_check_enabled("git_branch") {
-        if let Some(git_info) = project_lint_core::scanners::git::get_git_info(project_path)? {
-            if config.git.warn_wrong_branch {
+        for git_config in config
+            .modular_rules
+            .iter()
+            .filter(|r| r.enabled)
+            .filter_map(|r| r.git.as_ref())
+        {
+            if !git_config.warn_wrong_branch {
+                continue;
+            }
+            if let Some(git_info) = project_lint_core::scanners::git::get_git_info(project_path)? {
                 let branch_allowed = project_lint_core::scanners::git::check_branch_allowed(
                     &git_info,
-                    &config.git.allowed_branches,
-                    &config.git.forbidden_branches,
+                    &git_config.allowed_branches,
+                    &git_config.forbidden_branches,
                 )?;
 
                 if !branch_allowed {
@@ -109,7 +117,6 @@ fn check_recent_file_changes(
     config: &Config,
     issues: &mut Vec<String>,
 ) -> Result<()> {
-    use std::path::Path;
     use walkdir::WalkDir;
 
     let now = std::time::SystemTime::now();
@@ -130,54 +137,76 @@ fn check_recent_file_changes(
                         let relative_path = path.strip_prefix(project_path).unwrap_or(path);
                         let file_name = path.file_name().unwrap_or_default().to_string_lossy();
 
-                        // Skip ignored patterns
-                        if should_ignore_path(relative_path, &config.files.ignored_patterns) {
-                            continue;
+                        for rule in config.modular_rules.iter().filter(|r| r.enabled) {
+                            check_recent_file(config, rule, relative_path, &file_name, issues);
                         }
+                    }
+                }
+            }
+        }
+    }
 
-                        // Check if file should be moved based on type
-                        if config.is_check_enabled("file_location") && config.files.auto_move {
-                            for (pattern, target_dir) in &config.files.type_mappings {
-                                if matches_pattern(&file_name, pattern) {
-                                    let current_dir =
-                                        relative_path.parent().unwrap_or_else(|| Path::new(""));
-                                    if current_dir.to_string_lossy()
-                                        != target_dir.trim_end_matches('/')
-                                    {
-                                        issues.push(format!(
-                                            "📁 File '{}' should be in '{}' directory (matches pattern '{}')",
-                                            relative_path.display(),
-                                            target_dir,
-                                            pattern
-                                        ));
-                                    }
-                                }
-                            }
-                        }
+    Ok(())
+}
 
-                        // Check for scripts in wrong location
-                        if config.is_check_enabled("directory_structure")
-                            && config.directories.warn_scripts_location
-                            && is_script_file(&file_name)
-                        {
-                            let scripts_dir = &config.directories.scripts_directory;
-                            let current_dir =
-                                relative_path.parent().unwrap_or_else(|| Path::new(""));
-                            if current_dir.to_string_lossy() != scripts_dir.trim_end_matches('/') {
-                                issues.push(format!(
-                                    "📜 Script '{}' should be in '{}' directory",
-                                    relative_path.display(),
-                                    scripts_dir
-                                ));
-                            }
-                        }
+/// Apply a modular rule's file-mapping and script-location settings to one
+/// recently changed file.
+fn check_recent_file(
+    config: &Config,
+    rule: &ModularRule,
+    relative_path: &Path,
+    file_name: &str,
+    issues: &mut Vec<String>,
+) {
+    // Skip ignored patterns
+    let ignored_patterns = rule
+        .ignored_patterns
+        .as_ref()
+        .map(|patterns| patterns.keys().cloned().collect::<Vec<_>>())
+        .unwrap_or_default();
+    if should_ignore_path(relative_path, &ignored_patterns) {
+        return;
+    }
+
+    // Check if file should be moved based on type
+    if config.is_check_enabled("file_location") {
+        if let Some(file_mappings) = &rule.file_mappings {
+            for (pattern, target_dir) in file_mappings {
+                if matches_pattern(file_name, pattern) {
+                    let current_dir = relative_path.parent().unwrap_or_else(|| Path::new(""));
+                    if current_dir.to_string_lossy() != target_dir.trim_end_matches('/') {
+                        issues.push(format!(
+                            "📁 File '{}' should be in '{}' directory (matches pattern '{}')",
+                            relative_path.display(),
+                            target_dir,
+                            pattern
+                        ));
                     }
                 }
             }
         }
     }
 
-    Ok(())
+    // Check for scripts in wrong location
+    if config.is_check_enabled("directory_structure") {
+        if let Some(scripts) = &rule.scripts {
+            let is_script = scripts
+                .script_extensions
+                .iter()
+                .any(|ext| file_name.ends_with(ext.as_str()));
+            if is_script {
+                let scripts_dir = scripts.preferred_directory.trim_end_matches('/');
+                let current_dir = relative_path.parent().unwrap_or_else(|| Path::new(""));
+                if current_dir.to_string_lossy() != scripts_dir {
+                    issues.push(format!(
+                        "📜 Script '{}' should be in '{}' directory",
+                        relative_path.display(),
+                        scripts.preferred_directory
+                    ));
+                }
+            }
+        }
+    }
 }
 
 fn should_ignore_path(path: &Path, ignored_patterns: &[String]) -> bool {
@@ -202,8 +231,3 @@ fn matches_pattern(file_name: &str, pattern: &str) -> bool {
         file_name == pattern
     }
 }
-
-fn is_script_file(file_name: &str) -> bool {
-    let script_extensions = [".sh", ".py", ".js", ".ts", ".rb", ".pl", ".php"];
-    script_extensions.iter().any(|ext| file_name.ends_with(ext))
-}
@@ -1,4 +1,4 @@
-use clap::{Parser, Subcommand};
+use clap::{error::ErrorKind, CommandFactory, Parser, Subcommand};
 use tracing::{info, Level};
 use tracing_subscriber::FmtSubscriber;
 
@@ -21,6 +21,13 @@ struct Cli {
     /// Enable verbose logging
     #[arg(short, long)]
     verbose: bool,
+
+    /// Override a config key for this run (repeatable), e.g.
+    /// `rules.mode=allowlist` or `rules.disabled_checks+=ast_analysis`.
+    /// Applied after `PROJECT_LINT__SECTION__KEY` environment variables.
+    /// Honoured by `lint`, `watch`, `hook` and `profiles`.
+    #[arg(long = "set", value_name = "KEY=VALUE", global = true)]
+    set: Vec<String>,
 }
 
 #[derive(Subcommand)]
@@ -30,6 +37,12 @@ enum Commands {
         /// Force overwrite existing configuration
         #[arg(short, long)]
         force: bool,
+        /// Confirm each detected profile and the check mode interactively
+        #[arg(short, long)]
+        interactive: bool,
+        /// Start from a named preset instead of detecting the project type
+        #[arg(long, value_name = "NAME", value_parser = clap::builder::PossibleValuesParser::new(project_lint_core::inventory::PRESETS))]
+        preset: Option<String>,
     },
     /// Run linting checks on the current project
     Lint {
@@ -57,14 +70,57 @@ enum Commands {
     Logs(commands::logs::LogsArgs),
     /// Run as a hook handler for IDE events
     Hook(commands::hook::HookArgs),
+    /// Serve hook evaluations from a long-running process
+    Daemon(commands::daemon::DaemonArgs),
     /// Manage and export policy bundles
     Policy(commands::policy::PolicyArgs),
+    /// Inspect and migrate configuration files
+    Config(commands::config::ConfigArgs),
+    /// Inspect profile activation
+    Profiles(commands::profiles::ProfilesArgs),
+}
+
+impl Commands {
+    /// Name of a subcommand that does not load the project config, and so
+    /// cannot honour `--set`.
+    fn without_config_overrides(&self) -> Option<&'static str> {
+        match self {
+            Commands::Lint { .. }
+            | Commands::Watch { .. }
+            | Commands::Hook(_)
+            | Commands::Profiles(_) => None,
+            Commands::Init { .. } => Some("init"),
+            Commands::Configure => Some("configure"),
+            Commands::InstallHook(_) => Some("install-hook"),
+            Commands::Logs(_) => Some("logs"),
+            Commands::Daemon(_) => Some("daemon"),
+            Commands::Policy(_) => Some("policy"),
+            Commands::Config(_) => Some("config"),
+        }
+    }
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    if let (false, Some(name)) = (cli.set.is_empty(), cli.command.without_config_overrides()) {
+        let hint = if matches!(cli.command, Commands::Daemon(_)) {
+            "; the daemon applies the --set of each hook it serves, so pass it to `project-lint hook`"
+        } else {
+            ""
+        };
+        Cli::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                format!(
+                    "--set is not supported by `{}`; it applies to lint, watch, hook and profiles{}",
+                    name, hint
+                ),
+            )
+            .exit();
+    }
+
     // Setup logging
     let level = if cli.verbose {
         Level::DEBUG
@@ -82,16 +138,20 @@ async fn main() -> Result<()> {
     info!("Starting project-lint");
 
     match cli.command {
-        Commands::Init { force } => {
-            commands::init::run(force).await?;
+        Commands::Init {
+            force,
+            interactive,
+            preset,
+        } => {
+            commands::init::run(force, interactive, preset).await?;
         }
         Commands::Lint { path, fix, dry_run } => {
             let project_path = path.unwrap_or_else(|| ".".to_string());
-            commands::lint::run(&project_path, fix, dry_run).await?;
+            commands::lint::run(&project_path, fix, dry_run, &cli.set).await?;
         }
         Commands::Watch { path } => {
             let project_path = path.unwrap_or_else(|| ".".to_string());
-            commands::watch::run(&project_path).await?;
+            commands::watch::run(&project_path, &cli.set).await?;
         }
         Commands::Configure => {
             commands::configure_cmd::run().await?;
@@ -103,11 +163,20 @@ async fn main() -> Result<()> {
             commands::logs::run(args).await?;
         }
         Commands::Hook(args) => {
-            commands::hook::run(args).await?;
+            commands::hook::run(args, &cli.set).await?;
+        }
+        Commands::Daemon(args) => {
+            commands::daemon::run(args).await?;
         }
         Commands::Policy(args) => {
             commands::policy::run(args).await?;
         }
+        Commands::Config(args) => {
+            commands::config::run(args).await?;
+        }
+        Commands::Profiles(args) => {
+            commands::profiles::run(args, &cli.set).await?;
+        }
     }
 
     Ok(())
@@ -71,3 +71,63 @@ fn cli_unknown_subcommand_exits_nonzero() {
     let mut cmd = Command::cargo_bin("project-lint").expect("binary");
     cmd.args(["no-such-subcommand"]).assert().failure();
 }
+
+#[test]
+fn cli_lint_accepts_repeatable_set_overrides() {
+    let dir = TempDir::new().expect("tempdir");
+    let mut cmd = Command::cargo_bin("project-lint").expect("binary");
+    cmd.args(["lint", "--path"])
+        .arg(dir.path().to_string_lossy().to_string())
+        .args([
+            "--set",
+            "rules.disabled_checks+=ast_analysis",
+            "--set",
+            "rules.mode=denylist",
+        ])
+        .assert()
+        .success();
+}
+
+#[test]
+fn cli_lint_rejects_invalid_set_override() {
+    let dir = TempDir::new().expect("tempdir");
+    let mut cmd = Command::cargo_bin("project-lint").expect("binary");
+    cmd.args(["lint", "--path"])
+        .arg(dir.path().to_string_lossy().to_string())
+        .args(["--set", "rules.mode=sometimes"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid config override"));
+}
+
+#[test]
+fn cli_lint_rejects_invalid_env_override() {
+    let dir = TempDir::new().expect("tempdir");
+    let mut cmd = Command::cargo_bin("project-lint").expect("binary");
+    cmd.env("PROJECT_LINT__RULES__MODE", "sometimes")
+        .args(["lint", "--path"])
+        .arg(dir.path().to_string_lossy().to_string())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid config override"));
+}
+
+#[test]
+fn cli_rejects_set_for_subcommands_without_config() {
+    let mut cmd = Command::cargo_bin("project-lint").expect("binary");
+    cmd.args(["--set", "rules.mode=allowlist", "daemon"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--set is not supported by `daemon`",
+        ))
+        .stderr(predicate::str::contains("pass it to `project-lint hook`"));
+
+    let mut cmd = Command::cargo_bin("project-lint").expect("binary");
+    cmd.args(["config", "migrate", "--set", "rules.mode=allowlist"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--set is not supported by `config`",
+        ));
+}
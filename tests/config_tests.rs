@@ -43,6 +43,9 @@ fn test_custom_rules() {
             severity: project_lint_core::config::RuleSeverity::Warning,
             check_content: false,
             content_pattern: None,
+            content_regex: vec![],
+            content_flags: None,
+            content_match: Default::default(),
             exception_pattern: None,
             condition: None,
             required: false,
@@ -51,6 +54,8 @@ fn test_custom_rules() {
             enabled_if_path_exists: None,
             exclude_patterns: vec![],
             triggers: vec![],
+            when: None,
+            ask: false,
             mode: project_lint_core::config::ExecutionMode::LocalSync,
         });
 
@@ -12,6 +12,9 @@ fn ban_ts_rule(disabled_if_path_exists: Option<&str>, exclude_patterns: &[&str])
         severity: RuleSeverity::Warning,
         check_content: false,
         content_pattern: None,
+        content_regex: vec![],
+        content_flags: None,
+        content_match: Default::default(),
         exception_pattern: None,
         condition: None,
         required: false,
@@ -20,6 +23,8 @@ fn ban_ts_rule(disabled_if_path_exists: Option<&str>, exclude_patterns: &[&str])
         enabled_if_path_exists: None,
         exclude_patterns: exclude_patterns.iter().map(|s| s.to_string()).collect(),
         triggers: vec!["pre_write_code".to_string()],
+        when: None,
+        ask: false,
         mode: ExecutionMode::LocalSync,
     }
 }